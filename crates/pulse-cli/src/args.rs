@@ -0,0 +1,107 @@
+// Argument parsing helpers (durations, regions, default paths)
+
+use screen_capture::CaptureRegion;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Parse a duration like `10s`, `1m30s`, `500ms` or `90` (seconds)
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Duration cannot be empty".to_string());
+    }
+
+    // Bare number: seconds
+    if let Ok(secs) = input.parse::<f64>() {
+        return seconds(secs, input);
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or_else(|| format!("Missing unit in duration: {}", input))?;
+        if digits == 0 {
+            return Err(format!("Invalid duration: {}", input));
+        }
+        let value: f64 = rest[..digits]
+            .parse()
+            .map_err(|_| format!("Invalid duration: {}", input))?;
+
+        rest = &rest[digits..];
+        let unit_len = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            unit => return Err(format!("Unknown duration unit '{}' in {}", unit, input)),
+        };
+        total += seconds(value * factor, input)?;
+        rest = &rest[unit_len..];
+    }
+
+    Ok(total)
+}
+
+fn seconds(secs: f64, input: &str) -> Result<Duration, String> {
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("Invalid duration: {}", input))
+}
+
+/// Parse a capture region given as `x,y,width,height`
+pub fn parse_region(input: &str) -> Result<CaptureRegion, String> {
+    let parts: Vec<u32> = input
+        .split(',')
+        .map(|p| p.trim().parse::<u32>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("Invalid region '{}': expected x,y,width,height", input))?;
+
+    match parts[..] {
+        [x, y, width, height] if width > 0 && height > 0 => Ok(CaptureRegion { x, y, width, height }),
+        [_, _, _, _] => Err("Region width and height must be greater than zero".to_string()),
+        _ => Err(format!("Invalid region '{}': expected x,y,width,height", input)),
+    }
+}
+
+/// The desktop app's default output folder
+pub fn default_library() -> PathBuf {
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("~"));
+
+    if cfg!(target_os = "macos") {
+        home.join("Movies").join("PushToHold")
+    } else {
+        home.join("Videos").join("PushToHold")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("10s").unwrap(), Duration::from_secs(10));
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("1m30s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10").is_ok());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_region() {
+        let region = parse_region("10, 20,640,480").unwrap();
+        assert_eq!((region.x, region.y, region.width, region.height), (10, 20, 640, 480));
+        assert!(parse_region("10,20,640").is_err());
+        assert!(parse_region("10,20,0,480").is_err());
+        assert!(parse_region("a,b,c,d").is_err());
+    }
+}
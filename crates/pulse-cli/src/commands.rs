@@ -0,0 +1,258 @@
+// Subcommand implementations
+
+use crate::{ExportArgs, RecordArgs};
+use pulse_core::{media, EntryKind, EntryStatus, Library, Resolution, TimelineEntry};
+use screen_capture::{AnimationFormat, AnimationOptions, Recorder, RecordingConfig};
+use std::fs;
+use std::io::BufRead;
+use std::process::ExitCode;
+
+/// Record a clip and append it to the project's timeline
+pub fn record(library: &Library, args: RecordArgs) -> Result<ExitCode, String> {
+    let project = library.ensure_project(&args.project)?;
+    let started_at = chrono::Local::now();
+    let partial_path = project.new_partial_path("mp4");
+    let mic_enabled = args.mic || args.mic_device.is_some();
+
+    let config = RecordingConfig {
+        output_path: partial_path.clone(),
+        fps: args.fps,
+        capture_cursor: !args.no_cursor,
+        display_id: args.display,
+        region: args.region,
+        capture_microphone: mic_enabled,
+        microphone_device_id: args.mic_device,
+        variable_frame_rate: args.vfr,
+        keyframe_interval_secs: args.keyframe_interval,
+        comment: media::partial_clip_id(&partial_path).map(media::clip_id_comment),
+        ..Default::default()
+    };
+
+    let mut recorder = Recorder::new(config)?;
+    recorder.start()?;
+
+    match args.duration {
+        Some(duration) => {
+            eprintln!("▶️  Recording for {:.1}s...", duration.as_secs_f64());
+            std::thread::sleep(duration);
+        }
+        None => {
+            eprintln!("▶️  Recording... press Enter to stop");
+            let mut line = String::new();
+            std::io::stdin()
+                .lock()
+                .read_line(&mut line)
+                .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        }
+    }
+
+    let (_, duration_secs) = recorder.stop()?;
+    let published = project.publish_clip(&partial_path, &args.name, args.label.as_deref(), started_at)?;
+    let filename = published.filename().to_string();
+    let (width, height) = args.region.map(|r| (r.width, r.height)).unwrap_or((1920, 1080));
+
+    // Thumbnails are left to the app, which generates missing ones on reconcile
+    project.add_entry(TimelineEntry {
+        id: published.clip_id.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        kind: EntryKind::Video,
+        filename,
+        recorded_at: chrono::Utc::now().to_rfc3339(),
+        duration_ms: (duration_secs * 1000.0) as u64,
+        aspect_ratio: "none".to_string(),
+        resolution: Resolution { width, height },
+        mic_enabled,
+        notes: None,
+        label: args.label.clone(),
+        checksum: Some(published.checksum.clone()),
+        ..Default::default()
+    })?;
+
+    println!("{}", published.path.display());
+    eprintln!("✅ Recorded {:.1}s into project {}", duration_secs, project.name());
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn list_projects(library: &Library, json: bool) -> Result<ExitCode, String> {
+    let projects = library.projects()?;
+
+    if json {
+        print_json(&projects)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    for project in &projects {
+        match &project.error {
+            Some(e) => println!("{:<30} ⚠️  timeline unreadable: {}", project.name, e),
+            None => println!(
+                "{:<30} {:>4} videos   modified {}",
+                project.name, project.video_count, project.last_modified
+            ),
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn create_project(library: &Library, name: &str) -> Result<ExitCode, String> {
+    let project = library.create_project(name)?;
+    println!("{}", project.folder().display());
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn show_timeline(library: &Library, project: &str, json: bool, all: bool) -> Result<ExitCode, String> {
+    let timeline = library.project(project)?.timeline()?;
+
+    if json {
+        print_json(&timeline)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    println!(
+        "{} - {} videos, {:.1}s total",
+        timeline.project_name,
+        timeline.metadata.total_videos,
+        timeline.metadata.total_duration as f64 / 1000.0
+    );
+    for (index, entry) in timeline.entries.iter().enumerate() {
+        if entry.is_deleted() && !all {
+            continue;
+        }
+        let kind = match entry.kind {
+            EntryKind::Video => "video",
+            EntryKind::Image => "image",
+        };
+        let name = entry.label.as_deref().unwrap_or(&entry.filename);
+        let deleted = if entry.is_deleted() { "  (deleted)" } else { "" };
+        println!(
+            "{:>3}  {:.8}  {:<5}  {:>7.1}s  {}  {}{}",
+            index + 1,
+            entry.id,
+            kind,
+            entry.duration_ms as f64 / 1000.0,
+            entry.recorded_at,
+            name,
+            deleted
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn reconcile(
+    library: &Library,
+    project: &str,
+    min_duration_ms: Option<u64>,
+    dry_run: bool,
+    force: bool,
+    max_removal_percent: u32,
+) -> Result<ExitCode, String> {
+    let project = library.project(project)?;
+    let plan = project.plan_reconcile()?;
+    for entry in &plan.removed {
+        println!("remove  {}", entry.filename);
+    }
+    for entry in &plan.renamed {
+        println!("rename  {} -> {} (matched by {:?})", entry.from, entry.to, entry.matched_by);
+    }
+    for file in &plan.added {
+        println!("add     {}", file.filename);
+    }
+    for entry in &plan.checksum_changed {
+        println!("changed {}", entry.filename);
+    }
+    if dry_run {
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if plan.needs_confirmation(max_removal_percent) && !force {
+        eprintln!(
+            "refusing to remove {} of {} entries (is the project folder fully there?) - rerun with --force to apply",
+            plan.removed.len(),
+            plan.total_entries
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+    let changes = project.apply_reconcile(&plan)?;
+    println!("{} changes", changes);
+
+    if let Some(min_duration_ms) = min_duration_ms {
+        let deleted = project.delete_short_clips(min_duration_ms)?;
+        for filename in &deleted {
+            println!("deleted {}", filename);
+        }
+        println!("{} short clips marked deleted (files kept)", deleted.len());
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn verify(library: &Library, project: &str, json: bool) -> Result<ExitCode, String> {
+    let report = library.project(project)?.verify()?;
+
+    if json {
+        print_json(&report)?;
+    } else {
+        for check in &report.entries {
+            let status = match check.status {
+                EntryStatus::Ok => "ok",
+                EntryStatus::Unverified => "no checksum",
+                EntryStatus::Modified => "MODIFIED",
+                EntryStatus::Missing => "MISSING",
+            };
+            println!("{:<12} {}", status, check.filename);
+        }
+        for filename in &report.untracked {
+            println!("{:<12} {}", "untracked", filename);
+        }
+    }
+
+    Ok(if report.is_clean() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+pub fn export(library: &Library, args: ExportArgs) -> Result<ExitCode, String> {
+    let project = library.project(&args.project)?;
+    let timeline = project.timeline()?;
+
+    let matches: Vec<_> = timeline.entries.iter().filter(|e| e.id.starts_with(&args.entry)).collect();
+    let entry = match matches[..] {
+        [entry] => entry,
+        [] => return Err(format!("Timeline entry not found: {}", args.entry)),
+        _ => return Err(format!("Entry id prefix '{}' is ambiguous", args.entry)),
+    };
+    if entry.kind != EntryKind::Video {
+        return Err("Only video clips can be exported as animations".to_string());
+    }
+
+    let options = AnimationOptions {
+        format: if args.format == "webp" { AnimationFormat::WebP } else { AnimationFormat::Gif },
+        start_secs: args.start.map(|d| d.as_secs_f64()).unwrap_or(0.0),
+        end_secs: args.end.map(|d| d.as_secs_f64()),
+        fps: args.fps,
+        max_width: args.max_width,
+        max_bytes: args.max_bytes,
+        loop_forever: !args.no_loop,
+        ..Default::default()
+    };
+
+    let input = project.folder().join(&entry.filename);
+    let output = match args.output {
+        Some(output) => output,
+        None => {
+            let exports_folder = project.exports_folder();
+            fs::create_dir_all(&exports_folder)
+                .map_err(|e| format!("Failed to create exports folder: {}", e))?;
+            media::next_export_path(&exports_folder, &input, options.format.extension())
+        }
+    };
+
+    let export = screen_capture::export_animation(&input, &output, &options)?;
+    println!("{}", export.path.display());
+    eprintln!(
+        "✅ {}x{}, {} frames, {} bytes",
+        export.width, export.height, export.frame_count, export.bytes
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize: {}", e))?;
+    println!("{}", json);
+    Ok(())
+}
@@ -0,0 +1,236 @@
+// pulse - headless recorder and project library tool
+//
+// Works on the same library folder and timeline.json files as the desktop
+// app (via pulse-core), so recordings made here show up in the app and
+// vice versa. Run `pulse --help` for usage.
+
+mod args;
+mod commands;
+
+use clap::{Parser, Subcommand};
+use pulse_core::project::DEFAULT_MAX_REMOVAL_PERCENT;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(name = "pulse", version, about = "Record the screen and manage Pulse projects from the terminal")]
+struct Cli {
+    /// Library folder holding the projects (defaults to the app's output folder)
+    #[arg(long, global = true, env = "PULSE_LIBRARY")]
+    library: Option<PathBuf>,
+
+    /// Show progress logs (-vv for debug logs)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Record the screen into a project and add the clip to its timeline
+    Record(RecordArgs),
+
+    /// List or create projects
+    #[command(subcommand)]
+    Projects(ProjectsCommand),
+
+    /// Inspect a project's timeline
+    #[command(subcommand)]
+    Timeline(TimelineCommand),
+
+    /// Sync a project's timeline with the files in its folder
+    Reconcile {
+        #[arg(short, long)]
+        project: String,
+
+        /// Also mark video clips shorter than this deleted (accidental taps; the files are kept)
+        #[arg(long)]
+        min_duration_ms: Option<u64>,
+
+        /// Only print what would change
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Apply even if most of the timeline would be removed
+        #[arg(long)]
+        force: bool,
+
+        /// Ask for --force when more than this share of the timeline would be removed
+        #[arg(long, default_value_t = DEFAULT_MAX_REMOVAL_PERCENT, value_parser = clap::value_parser!(u32).range(0..=100))]
+        max_removal_percent: u32,
+    },
+
+    /// Check timeline entries against their files' checksums (exits 1 on problems)
+    Verify {
+        #[arg(short, long)]
+        project: String,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export a clip as an animated GIF or WebP
+    Export(ExportArgs),
+}
+
+#[derive(clap::Args)]
+struct RecordArgs {
+    /// Project to record into (created if it doesn't exist)
+    #[arg(short, long)]
+    project: String,
+
+    /// Stop after this long (e.g. 10s, 1m30s); without it, press Enter to stop
+    #[arg(short, long, value_parser = args::parse_duration)]
+    duration: Option<Duration>,
+
+    /// Capture only this region: x,y,width,height
+    #[arg(long, value_parser = args::parse_region)]
+    region: Option<screen_capture::CaptureRegion>,
+
+    /// Display to capture (default: primary)
+    #[arg(long)]
+    display: Option<u32>,
+
+    /// Frames per second
+    #[arg(long, default_value_t = 30)]
+    fps: u32,
+
+    /// Record microphone audio
+    #[arg(long)]
+    mic: bool,
+
+    /// Microphone device ID (implies --mic)
+    #[arg(long)]
+    mic_device: Option<String>,
+
+    /// Hide the mouse cursor
+    #[arg(long)]
+    no_cursor: bool,
+
+    /// Variable frame rate: skip unchanged frames for smaller files
+    #[arg(long)]
+    vfr: bool,
+
+    /// Most seconds between keyframes (lower seeks more precisely, higher makes smaller files)
+    #[arg(long, value_name = "SECS", default_value_t = screen_capture::DEFAULT_KEYFRAME_INTERVAL_SECS, value_parser = clap::value_parser!(u32).range(1..=60))]
+    keyframe_interval: u32,
+
+    /// Filename template: {project}, {date}, {time}, {seq}, {label}, {uuid}
+    #[arg(long, default_value = pulse_core::naming::DEFAULT_TEMPLATE, value_parser = pulse_core::FilenameTemplate::parse)]
+    name: pulse_core::FilenameTemplate,
+
+    /// Label for the clip (shown in the timeline and used by {label})
+    #[arg(long)]
+    label: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum ProjectsCommand {
+    /// List projects, newest first
+    List {
+        /// Print as JSON (same shape as the app's project list)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create an empty project
+    Create { name: String },
+}
+
+#[derive(Subcommand)]
+enum TimelineCommand {
+    /// Print a project's timeline entries
+    Show {
+        #[arg(short, long)]
+        project: String,
+
+        /// Print the raw timeline.json contents
+        #[arg(long)]
+        json: bool,
+
+        /// Include soft-deleted entries
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(clap::Args)]
+struct ExportArgs {
+    #[arg(short, long)]
+    project: String,
+
+    /// Timeline entry id (a unique prefix is enough)
+    #[arg(short, long)]
+    entry: String,
+
+    /// Output format
+    #[arg(long, value_parser = ["gif", "webp"], default_value = "gif")]
+    format: String,
+
+    /// Trim start (e.g. 2s)
+    #[arg(long, value_parser = args::parse_duration)]
+    start: Option<Duration>,
+
+    /// Trim end (e.g. 8s)
+    #[arg(long, value_parser = args::parse_duration)]
+    end: Option<Duration>,
+
+    /// Frames per second
+    #[arg(long, default_value_t = 10)]
+    fps: u32,
+
+    /// Maximum output width in pixels
+    #[arg(long, default_value_t = 640)]
+    max_width: u32,
+
+    /// Target file size in bytes (quality is reduced until it fits)
+    #[arg(long)]
+    max_bytes: Option<u64>,
+
+    /// Play once instead of looping
+    #[arg(long)]
+    no_loop: bool,
+
+    /// Output file (default: <project>/exports/<clip>.<format>)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let level = match cli.verbose {
+        0 => "warn",
+        1 => "info",
+        _ => "debug",
+    };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level)).init();
+
+    let library = pulse_core::Library::new(cli.library.unwrap_or_else(args::default_library));
+
+    let result = match cli.command {
+        Command::Record(record) => commands::record(&library, record),
+        Command::Projects(ProjectsCommand::List { json }) => commands::list_projects(&library, json),
+        Command::Projects(ProjectsCommand::Create { name }) => commands::create_project(&library, &name),
+        Command::Timeline(TimelineCommand::Show { project, json, all }) => {
+            commands::show_timeline(&library, &project, json, all)
+        }
+        Command::Reconcile { project, min_duration_ms, dry_run, force, max_removal_percent } => {
+            commands::reconcile(&library, &project, min_duration_ms, dry_run, force, max_removal_percent)
+        }
+        Command::Verify { project, json } => commands::verify(&library, &project, json),
+        Command::Export(export) => commands::export(&library, export),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
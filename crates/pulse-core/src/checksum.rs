@@ -0,0 +1,246 @@
+// File checksums for integrity checks and rename detection
+//
+// Hashing multi-GB recordings is slow, so reconciliation goes through a
+// per-project `ChecksumCache` (`.checksums.json`): a file is only re-hashed
+// when its size, modification time or inode changed since it was last hashed.
+// The same pass computes the file's `ContentSignature`, which recognizes a
+// clip after edits that change its checksum.
+// `Project::verify` deliberately bypasses the cache - it exists to catch
+// changes that metadata doesn't show.
+
+use crate::signature::{ContentSignature, SignatureBuilder};
+use crate::timeline;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Cache file in each project folder
+pub const CACHE_FILE: &str = ".checksums.json";
+
+const CACHE_VERSION: u32 = 2;
+
+/// Report hashing progress at most this often (in bytes read)
+const PROGRESS_INTERVAL_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Checksum and content signature of a file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileDigest {
+    /// SHA-256 (lowercase hex)
+    pub checksum: String,
+    /// None for an empty file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ContentSignature>,
+}
+
+/// Calculate the SHA256 checksum of a file (lowercase hex)
+pub fn file_checksum(file_path: &Path) -> Result<String, String> {
+    hash_file(file_path, &mut |_| {}, None)
+}
+
+/// Checksum and signature of a file in one read
+pub fn file_digest(file_path: &Path) -> Result<FileDigest, String> {
+    file_digest_with_progress(file_path, &mut |_| {})
+}
+
+/// Like [`file_digest`], calling `on_progress` with the bytes read so far
+/// every few MB
+pub fn file_digest_with_progress(file_path: &Path, on_progress: &mut dyn FnMut(u64)) -> Result<FileDigest, String> {
+    let mut builder = SignatureBuilder::new();
+    let checksum = hash_file(file_path, on_progress, Some(&mut builder))?;
+    Ok(FileDigest { checksum, signature: builder.finish() })
+}
+
+fn hash_file(file_path: &Path, on_progress: &mut dyn FnMut(u64), mut signature: Option<&mut SignatureBuilder>) -> Result<String, String> {
+    let mut file = fs::File::open(file_path)
+        .map_err(|e| format!("Failed to open file for checksum: {}", e))?;
+
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192]; // 8KB buffer for efficient reading
+    let (mut read, mut reported) = (0u64, 0u64);
+
+    loop {
+        let bytes_read = file.read(&mut buffer)
+            .map_err(|e| format!("Failed to read file for checksum: {}", e))?;
+
+        if bytes_read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..bytes_read]);
+        if let Some(builder) = signature.as_deref_mut() {
+            builder.update(&buffer[..bytes_read]);
+        }
+        read += bytes_read as u64;
+        if read - reported >= PROGRESS_INTERVAL_BYTES {
+            on_progress(read);
+            reported = read;
+        }
+    }
+
+    let result = hasher.finalize();
+    Ok(format!("{:x}", result))
+}
+
+/// What a cached checksum is valid for: if any of these change, the file is hashed again
+///
+/// Also stored in timeline.json (`thumbnailFailed`), hence the large fields as strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fingerprint {
+    pub size: u64,
+    #[serde(with = "timeline::u64_string")]
+    pub modified_ns: u64,
+    /// 0 where the platform doesn't expose one
+    #[serde(with = "timeline::u64_string")]
+    pub inode: u64,
+}
+
+impl Fingerprint {
+    pub fn of(meta: &fs::Metadata) -> Self {
+        let modified_ns = meta.modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+
+        #[cfg(unix)]
+        let inode = std::os::unix::fs::MetadataExt::ino(meta);
+        #[cfg(not(unix))]
+        let inode = 0;
+
+        Self { size: meta.len(), modified_ns, inode }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CacheEntry {
+    #[serde(flatten)]
+    fingerprint: Fingerprint,
+    #[serde(flatten)]
+    digest: FileDigest,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    files: HashMap<String, CacheEntry>,
+}
+
+/// Digests of a project's media files, keyed by filename
+#[derive(Debug, Default)]
+pub struct ChecksumCache {
+    files: HashMap<String, CacheEntry>,
+}
+
+impl ChecksumCache {
+    /// Read a project's cache; a missing, unreadable or outdated cache is
+    /// just empty (everything gets hashed once more)
+    pub fn load(folder: &Path) -> Self {
+        let files = fs::read_to_string(folder.join(CACHE_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .map(|cache| cache.files)
+            .unwrap_or_default();
+        Self { files }
+    }
+
+    /// Cached digest, if the file hasn't changed since it was hashed
+    pub fn get(&self, filename: &str, fingerprint: &Fingerprint) -> Option<&FileDigest> {
+        self.files.get(filename)
+            .filter(|entry| entry.fingerprint == *fingerprint)
+            .map(|entry| &entry.digest)
+    }
+
+    pub fn insert(&mut self, filename: &str, fingerprint: Fingerprint, digest: &FileDigest) {
+        self.files.insert(filename.to_string(), CacheEntry { fingerprint, digest: digest.clone() });
+    }
+
+    /// Forget files that are gone
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.files.retain(|filename, _| keep(filename));
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Re-read, modify and write a project's cache as one locked operation,
+    /// so concurrent writers (a recording being published, a reconcile
+    /// finishing) don't drop each other's entries
+    pub fn update(folder: &Path, f: impl FnOnce(&mut ChecksumCache)) -> Result<(), String> {
+        let path = folder.join(CACHE_FILE);
+        timeline::with_lock(&path, || {
+            let mut cache = Self::load(folder);
+            f(&mut cache);
+
+            let json = serde_json::to_string(&CacheFile { version: CACHE_VERSION, files: cache.files })
+                .map_err(|e| format!("Failed to serialize checksum cache: {}", e))?;
+            let temp_path = folder.join(format!("{}.tmp", CACHE_FILE));
+            let result = (|| {
+                let mut file = fs::File::create(&temp_path)?;
+                file.write_all(json.as_bytes())?;
+                fs::rename(&temp_path, &path)
+            })();
+            result.map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                format!("Failed to write checksum cache: {}", e)
+            })
+        })
+    }
+}
+
+/// How far along a batch of hashing is
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HashProgress {
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// File being hashed right now (None once the batch is done)
+    pub current_file: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_only_matches_unchanged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording-1.mp4");
+        fs::write(&path, b"video").unwrap();
+        let fingerprint = Fingerprint::of(&fs::metadata(&path).unwrap());
+
+        let digest = file_digest(&path).unwrap();
+        ChecksumCache::update(dir.path(), |cache| {
+            cache.insert("recording-1.mp4", fingerprint, &digest);
+            cache.insert("gone.mp4", fingerprint, &digest);
+        }).unwrap();
+        ChecksumCache::update(dir.path(), |cache| cache.retain(|f| f != "gone.mp4")).unwrap();
+
+        let cache = ChecksumCache::load(dir.path());
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("recording-1.mp4", &fingerprint), Some(&digest));
+        assert_eq!(digest.checksum, file_checksum(&path).unwrap());
+
+        fs::write(&path, b"longer video").unwrap();
+        let changed = Fingerprint::of(&fs::metadata(&path).unwrap());
+        assert_eq!(cache.get("recording-1.mp4", &changed), None);
+    }
+
+    #[test]
+    fn test_corrupt_cache_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(CACHE_FILE), "{ nope").unwrap();
+        assert!(ChecksumCache::load(dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+// Project library core for Pulse
+//!
+//! # pulse-core
+//!
+//! Everything about projects and timelines that doesn't need a running app:
+//! - [`Library`]: a root folder of projects (the app's output folder)
+//! - [`Project`]: one project folder with its media and `timeline.json`
+//! - [`Timeline`]: the versioned `timeline.json` schema, with locked,
+//!   atomic reads and writes
+//! - [`FilenameTemplate`]: how new clips are named (`recording-{seq}` etc.)
+//!
+//! The desktop app and the `pulse` CLI are both thin layers over this crate,
+//! so they read and write exactly the same files.
+//!
+//! ## Example
+//! ```no_run
+//! use pulse_core::Library;
+//!
+//! let library = Library::new("/Users/me/Movies/PushToHold");
+//! for summary in library.projects()? {
+//!     println!("{} ({} videos)", summary.name, summary.video_count);
+//! }
+//!
+//! let project = library.project("Demo")?;
+//! let changes = project.reconcile()?;
+//! println!("{} changes", changes);
+//! # Ok::<(), String>(())
+//! ```
+
+pub mod checksum;
+pub mod library;
+pub mod media;
+mod mp4;
+pub mod naming;
+pub mod project;
+pub mod signature;
+pub mod timeline;
+
+pub use library::{validate_project_name, Library, ProjectSummary, RecoverableClip};
+pub use naming::FilenameTemplate;
+pub use project::{
+    AddedFile, ChecksumChange, EntryCheck, EntryStatus, Project, PublishedClip, ReconcilePlan, RemovedEntry, RenameMatch,
+    RenamedEntry, VerifyReport,
+};
+pub use timeline::{EntryKind, Resolution, Timeline, TimelineEntry, TimelineMetadata};
@@ -0,0 +1,250 @@
+// A library is the root folder holding one subfolder per project
+
+use crate::project::Project;
+use crate::timeline::{self, Timeline};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Project listing entry (what the project picker shows)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    pub name: String,
+    pub created_at: String,
+    pub video_count: u32,
+    pub last_modified: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>, // Why the project's timeline couldn't be loaded
+}
+
+/// A recording that never finished, found in a project folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoverableClip {
+    pub project: String,
+    pub filename: String,
+    pub size_bytes: u64,
+    pub last_modified: String,
+}
+
+/// Root folder of projects
+#[derive(Debug, Clone)]
+pub struct Library {
+    root: PathBuf,
+}
+
+impl Library {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// List all projects, newest first
+    ///
+    /// A project whose timeline can't be loaded is still listed, with the
+    /// load error, instead of pretending it's empty.
+    pub fn projects(&self) -> Result<Vec<ProjectSummary>, String> {
+        let mut projects = Vec::new();
+
+        if !self.root.exists() {
+            debug!("📁 Library folder doesn't exist, returning empty list");
+            return Ok(projects);
+        }
+
+        let entries = fs::read_dir(&self.root).map_err(|e| format!("Failed to read output folder: {}", e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            let project_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("Unknown")
+                .to_string();
+            let timeline_path = path.join("timeline.json");
+
+            if timeline_path.exists() {
+                match timeline::load(&timeline_path) {
+                    Ok(timeline) => {
+                        debug!("✅ Parsed timeline for {} ({} videos)", project_name, timeline.metadata.total_videos);
+                        projects.push(ProjectSummary {
+                            name: project_name,
+                            created_at: timeline.created_at,
+                            video_count: timeline.metadata.total_videos,
+                            last_modified: timeline.last_modified,
+                            error: None,
+                        });
+                    }
+                    Err(e) => {
+                        warn!("❌ Failed to load timeline for {}: {}", project_name, e);
+                        let modified = fs::metadata(&timeline_path)
+                            .and_then(|m| m.modified())
+                            .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+                            .unwrap_or_default();
+                        projects.push(ProjectSummary {
+                            name: project_name,
+                            created_at: String::new(),
+                            video_count: 0,
+                            last_modified: modified,
+                            error: Some(e),
+                        });
+                    }
+                }
+            } else {
+                debug!("📄 No timeline.json found for {}, listing as empty", project_name);
+                let now = chrono::Utc::now().to_rfc3339();
+                projects.push(ProjectSummary {
+                    name: project_name,
+                    created_at: now.clone(),
+                    video_count: 0,
+                    last_modified: now,
+                    error: None,
+                });
+            }
+        }
+
+        // Sort projects by last modified date (newest first)
+        projects.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+        Ok(projects)
+    }
+
+    /// Leftover partial recordings in every project (see [`Project::recover_partial`])
+    pub fn recoverable_clips(&self) -> Result<Vec<RecoverableClip>, String> {
+        let mut clips = Vec::new();
+        for summary in self.projects()? {
+            let project = Project::new(&summary.name, self.root.join(&summary.name));
+            for path in project.partial_clips()? {
+                let meta = fs::metadata(&path).ok();
+                clips.push(RecoverableClip {
+                    project: summary.name.clone(),
+                    filename: path.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string(),
+                    size_bytes: meta.as_ref().map(|m| m.len()).unwrap_or(0),
+                    last_modified: meta
+                        .and_then(|m| m.modified().ok())
+                        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+                        .unwrap_or_default(),
+                });
+            }
+        }
+        Ok(clips)
+    }
+
+    /// Open an existing project
+    pub fn project(&self, name: &str) -> Result<Project, String> {
+        let project = Project::new(name, self.root.join(name));
+        if !project.folder().is_dir() {
+            return Err("Project does not exist".to_string());
+        }
+        Ok(project)
+    }
+
+    /// Create a new project folder with an empty timeline
+    pub fn create_project(&self, name: &str) -> Result<Project, String> {
+        let name = validate_project_name(name)?;
+        let folder = self.root.join(&name);
+
+        if folder.exists() {
+            return Err("Project already exists".to_string());
+        }
+
+        fs::create_dir_all(&folder).map_err(|e| format!("Failed to create project folder: {}", e))?;
+        let project = Project::new(&name, folder);
+        timeline::save(&project.timeline_path(), &Timeline::new(&name))?;
+        Ok(project)
+    }
+
+    /// Open a project, creating it (and its timeline) if it doesn't exist yet
+    pub fn ensure_project(&self, name: &str) -> Result<Project, String> {
+        let name = validate_project_name(name)?;
+        let folder = self.root.join(&name);
+        fs::create_dir_all(&folder).map_err(|e| format!("Failed to create project folder: {}", e))?;
+
+        let project = Project::new(&name, folder);
+        if !project.timeline_path().exists() {
+            timeline::save(&project.timeline_path(), &Timeline::new(&name))?;
+        }
+        Ok(project)
+    }
+}
+
+/// Check a project name is usable as a folder name, returning it trimmed
+pub fn validate_project_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Project name cannot be empty".to_string());
+    }
+
+    // No special characters that would cause filesystem issues
+    if name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
+        return Err("Project name contains invalid characters".to_string());
+    }
+
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_project_name() {
+        assert_eq!(validate_project_name("  Demo ").unwrap(), "Demo");
+        assert!(validate_project_name("   ").is_err());
+        assert!(validate_project_name("a/b").is_err());
+        assert!(validate_project_name("what?").is_err());
+    }
+
+    #[test]
+    fn test_create_and_list_projects() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = Library::new(dir.path());
+        assert!(library.projects().unwrap().is_empty());
+
+        let project = library.create_project(" Demo ").unwrap();
+        assert_eq!(project.name(), "Demo");
+        assert!(project.timeline_path().exists());
+        assert!(library.create_project("Demo").is_err());
+
+        // A folder without a timeline is still a project
+        fs::create_dir(dir.path().join("Loose")).unwrap();
+        // A file in the root is not
+        fs::write(dir.path().join("notes.txt"), "hi").unwrap();
+
+        let mut names: Vec<_> = library.projects().unwrap().into_iter().map(|p| p.name).collect();
+        names.sort();
+        assert_eq!(names, ["Demo", "Loose"]);
+    }
+
+    #[test]
+    fn test_unreadable_timeline_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = Library::new(dir.path());
+        fs::create_dir(dir.path().join("Broken")).unwrap();
+        fs::write(dir.path().join("Broken/timeline.json"), "{ not json").unwrap();
+
+        let projects = library.projects().unwrap();
+        assert_eq!(projects.len(), 1);
+        assert!(projects[0].error.is_some());
+    }
+
+    #[test]
+    fn test_project_must_exist_unless_ensured() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = Library::new(dir.path());
+        assert!(library.project("Default").is_err());
+
+        let project = library.ensure_project("Default").unwrap();
+        assert_eq!(project.timeline().unwrap().project_name, "Default");
+        // Ensuring again keeps the existing timeline
+        let created_at = project.timeline().unwrap().created_at;
+        library.ensure_project("Default").unwrap();
+        assert_eq!(library.project("Default").unwrap().timeline().unwrap().created_at, created_at);
+    }
+}
@@ -0,0 +1,165 @@
+// Media file helpers: naming conventions, duration and property detection
+
+use std::path::{Path, PathBuf};
+
+const POSTER_SUFFIX: &str = ".thumb.png";
+const FILMSTRIP_SUFFIX: &str = ".filmstrip.png";
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// Recordings carry their clip id in the MP4 comment tag as `pulse-clip:<id>`
+const CLIP_ID_PREFIX: &str = "pulse-clip:";
+
+/// Check if a file is a generated thumbnail (never a timeline entry itself)
+pub fn is_thumbnail_file(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .is_some_and(|n| n.ends_with(POSTER_SUFFIX) || n.ends_with(FILMSTRIP_SUFFIX))
+}
+
+/// Check if a file is a recording still being written, or one that never
+/// finished (recorders write to `<id>.mp4.partial` and rename when done)
+pub fn is_partial_file(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .is_some_and(|n| n.ends_with(PARTIAL_SUFFIX))
+}
+
+/// Filename to record a new `<extension>` clip to before it's published
+pub fn partial_filename(extension: &str) -> String {
+    format!("{}.{}{}", uuid::Uuid::new_v4(), extension, PARTIAL_SUFFIX)
+}
+
+/// Extension the clip will have once published (`abc.mp4.partial` -> `mp4`)
+pub fn published_extension(partial: &Path) -> Option<&str> {
+    Path::new(partial.file_stem()?).extension()?.to_str()
+}
+
+/// Clip id of a partial recording (the `<id>` in `<id>.mp4.partial`)
+pub fn partial_clip_id(partial: &Path) -> Option<&str> {
+    partial.file_name()?.to_str()?.split('.').next().filter(|id| !id.is_empty())
+}
+
+/// Comment tag that embeds a clip id in a recording
+pub fn clip_id_comment(clip_id: &str) -> String {
+    format!("{}{}", CLIP_ID_PREFIX, clip_id)
+}
+
+/// Clip id embedded in a recording, if it was made by Pulse
+///
+/// The id stays in the file when it's renamed, moved or copied, and most
+/// tools (ffmpeg included) keep the comment tag when re-encoding.
+pub fn embedded_clip_id(path: &Path) -> Option<String> {
+    let comment = crate::mp4::read_comment(path)?;
+    comment.strip_prefix(CLIP_ID_PREFIX).map(|id| id.trim().to_string()).filter(|id| !id.is_empty())
+}
+
+fn clip_stem(clip_filename: &str) -> &str {
+    Path::new(clip_filename)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(clip_filename)
+}
+
+/// Poster frame filename for a clip (recording-1.mp4 -> recording-1.thumb.png)
+pub fn poster_filename(clip_filename: &str) -> String {
+    format!("{}{}", clip_stem(clip_filename), POSTER_SUFFIX)
+}
+
+/// Filmstrip sprite filename for a clip (recording-1.mp4 -> recording-1.filmstrip.png)
+pub fn filmstrip_filename(clip_filename: &str) -> String {
+    format!("{}{}", clip_stem(clip_filename), FILMSTRIP_SUFFIX)
+}
+
+/// First free `<prefix>-N.<extension>` path in a folder (N starts at 1)
+pub fn next_sequential_path(folder: &Path, prefix: &str, extension: &str) -> PathBuf {
+    let mut n = 1;
+    loop {
+        let path = folder.join(format!("{}-{}.{}", prefix, n, extension));
+        if !path.exists() {
+            return path;
+        }
+        n += 1;
+    }
+}
+
+/// Free export path for a clip (clip.gif, clip-2.gif, clip-3.gif, etc.)
+pub fn next_export_path(exports_folder: &Path, input: &Path, extension: &str) -> PathBuf {
+    let stem = input.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("clip");
+
+    let mut path = exports_folder.join(format!("{}.{}", stem, extension));
+    let mut n = 2;
+    while path.exists() {
+        path = exports_folder.join(format!("{}-{}.{}", stem, n, extension));
+        n += 1;
+    }
+    path
+}
+
+/// Guess aspect ratio and resolution from common filename patterns
+/// Returns (aspect ratio, width, height)
+pub fn detect_video_properties_from_filename(filename: &str) -> (String, u32, u32) {
+    let lower = filename.to_lowercase();
+
+    // Look for common resolution patterns in filename
+    if lower.contains("1920x1080") || lower.contains("1080p") || lower.contains("fhd") {
+        ("16:9".to_string(), 1920, 1080)
+    } else if lower.contains("2560x1440") || lower.contains("1440p") || lower.contains("qhd") {
+        ("16:9".to_string(), 2560, 1440)
+    } else if lower.contains("3840x2160") || lower.contains("4k") || lower.contains("uhd") {
+        ("16:9".to_string(), 3840, 2160)
+    } else if lower.contains("1080x1920") || lower.contains("vertical") || lower.contains("portrait") {
+        ("9:16".to_string(), 1080, 1920)
+    } else if lower.contains("1440x2560") {
+        ("9:16".to_string(), 1440, 2560)
+    } else if lower.contains("2160x3840") {
+        ("9:16".to_string(), 2160, 3840)
+    } else {
+        // Default to common recording resolution
+        ("16:9".to_string(), 1920, 1080)
+    }
+}
+
+/// Calculate actual video duration in milliseconds
+#[cfg(target_os = "macos")]
+pub fn video_duration_ms(file_path: &Path) -> Result<u64, String> {
+    use std::process::Command;
+
+    // Use ffprobe to get actual video duration
+    let output = Command::new("ffprobe")
+        .args([
+            "-v", "quiet",
+            "-show_entries", "format=duration",
+            "-of", "csv=p=0",
+            file_path.to_str().ok_or("Invalid file path")?
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err("ffprobe failed to read video duration".to_string());
+    }
+
+    let duration_str = String::from_utf8_lossy(&output.stdout);
+    let duration_seconds: f64 = duration_str.trim().parse()
+        .map_err(|e| format!("Failed to parse duration: {}", e))?;
+
+    Ok((duration_seconds * 1000.0) as u64) // Convert to milliseconds
+}
+
+/// Calculate actual video duration in milliseconds
+#[cfg(not(target_os = "macos"))]
+pub fn video_duration_ms(file_path: &Path) -> Result<u64, String> {
+    // Fallback for non-macOS platforms
+    let metadata = std::fs::metadata(file_path)
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?;
+    Ok(estimate_duration_from_file_size(metadata.len()))
+}
+
+/// Estimate duration in milliseconds from file size (fallback)
+pub fn estimate_duration_from_file_size(file_size: u64) -> u64 {
+    // Very rough estimate: assume ~1MB per second for typical screen recordings
+    let estimated_seconds = (file_size / (1024 * 1024)).max(1); // At least 1 second
+    estimated_seconds * 1000 // Convert to milliseconds
+}
@@ -0,0 +1,127 @@
+// Just enough MP4/QuickTime box parsing to read a file's comment tag
+//
+// The comment lives at moov/udta/meta/ilst/©cmt/data, which is where both
+// AVAssetWriter and ffmpeg put it. Only the top-level boxes are walked on
+// disk (mdat is skipped with a seek), so this is cheap even for long clips.
+
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Don't read a moov box larger than this (a long clip's is a few MB)
+const MAX_MOOV_SIZE: u64 = 64 * 1024 * 1024;
+
+const COMMENT: [u8; 4] = [0xA9, b'c', b'm', b't'];
+
+/// The comment tag of an MP4/MOV file, if it has one
+pub fn read_comment(path: &Path) -> Option<String> {
+    let moov = read_top_level_box(path, b"moov")?;
+    let udta = find_box(&moov, b"udta")?;
+    let meta = find_box(udta, b"meta")?;
+    // `meta` is a full box (version + flags) in MP4, a plain one in QuickTime
+    let meta = if meta.get(4..8) == Some(b"hdlr") { meta } else { meta.get(4..)? };
+    let ilst = find_box(meta, b"ilst")?;
+    let comment = find_box(ilst, &COMMENT)?;
+    let data = find_box(comment, b"data")?;
+    // Type indicator and locale come before the value
+    String::from_utf8(data.get(8..)?.to_vec()).ok()
+}
+
+/// Contents of the first top-level box of the given type
+fn read_top_level_box(path: &Path, kind: &[u8; 4]) -> Option<Vec<u8>> {
+    let mut file = fs::File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+    let mut offset = 0u64;
+
+    while offset + 8 <= file_len {
+        let mut header = [0u8; 8];
+        file.seek(SeekFrom::Start(offset)).ok()?;
+        file.read_exact(&mut header).ok()?;
+
+        let mut header_len = 8u64;
+        let mut size = u32::from_be_bytes(header[..4].try_into().ok()?) as u64;
+        if size == 1 {
+            let mut large = [0u8; 8];
+            file.read_exact(&mut large).ok()?;
+            size = u64::from_be_bytes(large);
+            header_len = 16;
+        } else if size == 0 {
+            size = file_len - offset;
+        }
+        if size < header_len {
+            return None;
+        }
+
+        if &header[4..] == kind {
+            let body_len = size - header_len;
+            if body_len > MAX_MOOV_SIZE {
+                return None;
+            }
+            let mut body = vec![0u8; body_len as usize];
+            file.read_exact(&mut body).ok()?;
+            return Some(body);
+        }
+        offset += size;
+    }
+    None
+}
+
+/// Contents of the first child box of the given type
+fn find_box<'a>(mut data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    while data.len() >= 8 {
+        let size = u32::from_be_bytes(data[..4].try_into().ok()?) as usize;
+        let (header_len, size) = match size {
+            0 => (8, data.len()),
+            1 => (16, u64::from_be_bytes(data.get(8..16)?.try_into().ok()?) as usize),
+            _ => (8, size),
+        };
+        if size < header_len || size > data.len() {
+            return None;
+        }
+        if &data[4..8] == kind {
+            return Some(&data[header_len..size]);
+        }
+        data = &data[size..];
+    }
+    None
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    fn mp4_box(kind: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut out = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(body);
+        out
+    }
+
+    /// A minimal MP4 with the given comment tag, laid out like AVAssetWriter's
+    /// output (media data first, moov last)
+    pub(crate) fn mp4_with_comment(comment: &str, media: &[u8]) -> Vec<u8> {
+        let mut data = vec![0, 0, 0, 1, 0, 0, 0, 0];
+        data.extend_from_slice(comment.as_bytes());
+        let mut meta = vec![0u8; 4];
+        meta.extend(mp4_box(b"hdlr", &[0u8; 25]));
+        meta.extend(mp4_box(b"ilst", &mp4_box(&COMMENT, &mp4_box(b"data", &data))));
+        let moov = mp4_box(b"moov", &[mp4_box(b"mvhd", &[0u8; 100]), mp4_box(b"udta", &mp4_box(b"meta", &meta))].concat());
+
+        [mp4_box(b"ftyp", b"isom\0\0\0\0"), mp4_box(b"mdat", media), moov].concat()
+    }
+
+    #[test]
+    fn test_read_comment() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clip.mp4");
+        fs::write(&path, mp4_with_comment("pulse-clip:abc", b"frames")).unwrap();
+        assert_eq!(read_comment(&path).as_deref(), Some("pulse-clip:abc"));
+
+        // No comment, or not an MP4 at all
+        let plain = [mp4_box(b"ftyp", b"isom"), mp4_box(b"moov", &mp4_box(b"mvhd", &[0u8; 100]))].concat();
+        fs::write(&path, plain).unwrap();
+        assert_eq!(read_comment(&path), None);
+        fs::write(&path, b"not a video").unwrap();
+        assert_eq!(read_comment(&path), None);
+    }
+}
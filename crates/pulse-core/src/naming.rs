@@ -0,0 +1,289 @@
+// Output filename templates and the per-project clip counter
+//
+// A template such as `{project}-{date}-{seq}` is rendered to the file stem of
+// a new clip. `{seq}` comes from a counter stored in the project folder
+// (`.sequence`), so naming a clip is O(1) however many clips a project has,
+// and the numbers of deleted clips aren't handed out again. The counter only
+// advances when a clip is actually kept, see `Project::publish_clip`.
+//
+// Files created outside the app are still never overwritten: a rendered name
+// that's already taken moves on to the next number, or gets a `-2`, `-3`, ...
+// suffix if the template has no `{seq}`.
+
+use crate::media;
+use crate::timeline::EntryKind;
+use chrono::{DateTime, Local};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Template used when none is configured (recording-1, recording-2, ...)
+pub const DEFAULT_TEMPLATE: &str = "recording-{seq}";
+
+/// Counter file in each project folder
+pub const SEQUENCE_FILE: &str = ".sequence";
+
+/// Stem used if a template renders to nothing (e.g. just `{label}` without a label)
+const FALLBACK_STEM: &str = "clip";
+
+/// Characters that aren't allowed in filenames on at least one platform
+const FORBIDDEN_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Project,
+    Date,
+    Time,
+    Seq,
+    Label,
+    Uuid,
+}
+
+impl Token {
+    const ALL: [Token; 6] = [Token::Project, Token::Date, Token::Time, Token::Seq, Token::Label, Token::Uuid];
+
+    fn name(self) -> &'static str {
+        match self {
+            Token::Project => "project",
+            Token::Date => "date",
+            Token::Time => "time",
+            Token::Seq => "seq",
+            Token::Label => "label",
+            Token::Uuid => "uuid",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Token(Token),
+}
+
+/// A parsed filename template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameTemplate {
+    source: String,
+    parts: Vec<Part>,
+}
+
+/// Values substituted into a template
+#[derive(Debug, Clone, Copy)]
+pub struct NameContext<'a> {
+    pub project: &'a str,
+    pub label: Option<&'a str>,
+    /// When the clip was recorded (`{date}` and `{time}`)
+    pub time: DateTime<Local>,
+    pub seq: u64,
+}
+
+impl FilenameTemplate {
+    /// Parse a template, rejecting unknown tokens and characters that can't
+    /// appear in a filename
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let source = template.trim();
+        if source.is_empty() {
+            return Err("Filename template cannot be empty".to_string());
+        }
+
+        let mut parts = Vec::new();
+        let mut rest = source;
+        while let Some(open) = rest.find('{') {
+            push_literal(&mut parts, &rest[..open])?;
+            let close = rest[open..]
+                .find('}')
+                .map(|i| open + i)
+                .ok_or_else(|| format!("Unclosed '{{' in filename template: {}", source))?;
+            let name = &rest[open + 1..close];
+            let token = Token::ALL
+                .into_iter()
+                .find(|t| t.name() == name)
+                .ok_or_else(|| format!(
+                    "Unknown token {{{}}} in filename template (available: {})",
+                    name,
+                    Token::ALL.map(|t| format!("{{{}}}", t.name())).join(", ")
+                ))?;
+            parts.push(Part::Token(token));
+            rest = &rest[close + 1..];
+        }
+        push_literal(&mut parts, rest)?;
+
+        Ok(Self { source: source.to_string(), parts })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the template numbers its clips
+    pub fn uses_seq(&self) -> bool {
+        self.parts.contains(&Part::Token(Token::Seq))
+    }
+
+    /// File stem for a clip (without extension)
+    pub fn render(&self, ctx: &NameContext) -> String {
+        let mut stem = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => stem.push_str(text),
+                Part::Token(Token::Project) => stem.push_str(&sanitize(ctx.project)),
+                Part::Token(Token::Date) => stem.push_str(&ctx.time.format("%Y-%m-%d").to_string()),
+                Part::Token(Token::Time) => stem.push_str(&ctx.time.format("%H-%M-%S").to_string()),
+                Part::Token(Token::Seq) => stem.push_str(&ctx.seq.to_string()),
+                Part::Token(Token::Label) => stem.push_str(&sanitize(ctx.label.unwrap_or(""))),
+                Part::Token(Token::Uuid) => stem.push_str(&uuid::Uuid::new_v4().to_string()),
+            }
+        }
+
+        // An empty {label} at either end shouldn't leave a dangling separator
+        // (or a leading dot that hides the file)
+        let stem = stem.trim_matches(|c: char| c == '-' || c == '_' || c == '.' || c.is_whitespace());
+        if stem.is_empty() {
+            FALLBACK_STEM.to_string()
+        } else {
+            stem.to_string()
+        }
+    }
+}
+
+impl Default for FilenameTemplate {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TEMPLATE).expect("default template is valid")
+    }
+}
+
+fn push_literal(parts: &mut Vec<Part>, text: &str) -> Result<(), String> {
+    if text.contains('}') {
+        return Err("Unmatched '}' in filename template".to_string());
+    }
+    if let Some(c) = text.chars().find(|c| FORBIDDEN_CHARS.contains(c) || c.is_control()) {
+        return Err(format!("Filename template cannot contain '{}'", c.escape_default()));
+    }
+    if !text.is_empty() {
+        parts.push(Part::Literal(text.to_string()));
+    }
+    Ok(())
+}
+
+/// Make a project name or label safe to use inside a filename
+fn sanitize(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .map(|c| if FORBIDDEN_CHARS.contains(&c) || c.is_control() { '-' } else { c })
+        .collect()
+}
+
+fn sequence_path(folder: &Path) -> PathBuf {
+    folder.join(SEQUENCE_FILE)
+}
+
+/// Next unused clip number for a project folder
+///
+/// Projects without a counter yet (created before templates existed) start
+/// after the highest number already used by a media file, so old clips
+/// aren't renumbered into.
+pub fn next_sequence(folder: &Path) -> u64 {
+    fs::read_to_string(sequence_path(folder))
+        .ok()
+        .and_then(|content| content.trim().parse().ok())
+        .unwrap_or_else(|| highest_used_number(folder) + 1)
+}
+
+/// Record that clip number `used` was taken
+///
+/// Callers hold the lock for the sequence file (see `Project::publish_clip`).
+pub(crate) fn commit_sequence(folder: &Path, used: u64) -> Result<(), String> {
+    if used < next_sequence(folder) {
+        return Ok(());
+    }
+    write_sequence(folder, used + 1)
+}
+
+fn write_sequence(folder: &Path, next: u64) -> Result<(), String> {
+    let path = sequence_path(folder);
+    let temp_path = folder.join(format!("{}.tmp", SEQUENCE_FILE));
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        writeln!(file, "{}", next)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, &path)
+    })();
+    result.map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to save clip counter: {}", e)
+    })
+}
+
+/// Largest trailing number among media filenames (recording-12.mp4 -> 12)
+fn highest_used_number(folder: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| EntryKind::from_path(path).is_some() && !media::is_thumbnail_file(path))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?.to_string();
+            let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+            stem[stem.len() - digits..].parse().ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn context<'a>(label: Option<&'a str>, seq: u64) -> NameContext<'a> {
+        NameContext {
+            project: "Demo: Part 1",
+            label,
+            time: Local.with_ymd_and_hms(2026, 3, 4, 9, 5, 7).unwrap(),
+            seq,
+        }
+    }
+
+    #[test]
+    fn test_render_tokens() {
+        let template = FilenameTemplate::parse("{project}_{date}_{time}-{seq}-{label}").unwrap();
+        assert!(template.uses_seq());
+        assert_eq!(template.render(&context(Some("intro/take 2"), 7)), "Demo- Part 1_2026-03-04_09-05-07-7-intro-take 2");
+        // No label: no trailing separator
+        assert_eq!(template.render(&context(None, 7)), "Demo- Part 1_2026-03-04_09-05-07-7");
+
+        let uuid = FilenameTemplate::parse("clip-{uuid}").unwrap().render(&context(None, 1));
+        assert_eq!(uuid.len(), "clip-".len() + 36);
+        assert_eq!(FilenameTemplate::parse("{label}").unwrap().render(&context(None, 1)), "clip");
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_templates() {
+        assert!(FilenameTemplate::parse("  ").is_err());
+        assert!(FilenameTemplate::parse("clip-{number}").is_err());
+        assert!(FilenameTemplate::parse("clip-{seq").is_err());
+        assert!(FilenameTemplate::parse("clip-seq}").is_err());
+        assert!(FilenameTemplate::parse("clips/{seq}").is_err());
+        assert_eq!(FilenameTemplate::default().as_str(), DEFAULT_TEMPLATE);
+    }
+
+    #[test]
+    fn test_sequence_starts_after_existing_files_and_only_moves_forward() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("recording-3.mp4"), b"").unwrap();
+        fs::write(dir.path().join("recording-9.thumb.png"), b"").unwrap();
+        fs::write(dir.path().join("notes-12.txt"), b"").unwrap();
+        assert_eq!(next_sequence(dir.path()), 4);
+
+        commit_sequence(dir.path(), 4).unwrap();
+        assert_eq!(next_sequence(dir.path()), 5);
+
+        // Deleting clips doesn't hand their numbers out again
+        fs::remove_file(dir.path().join("recording-3.mp4")).unwrap();
+        commit_sequence(dir.path(), 2).unwrap();
+        assert_eq!(next_sequence(dir.path()), 5);
+    }
+}
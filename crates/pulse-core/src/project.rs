@@ -0,0 +1,1223 @@
+// A project folder: media files plus the timeline.json describing them
+
+use crate::checksum::{self, ChecksumCache, FileDigest, Fingerprint, HashProgress};
+use crate::media;
+use crate::naming::{self, FilenameTemplate, NameContext};
+use crate::signature::SIMILARITY_THRESHOLD;
+use crate::timeline::{self, EntryKind, Resolution, Timeline, TimelineEntry};
+use chrono::{DateTime, Local};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Result of checking one timeline entry against its file on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryStatus {
+    /// File exists and matches the recorded checksum
+    Ok,
+    /// File exists but the timeline has no checksum to compare against
+    Unverified,
+    /// File content differs from the recorded checksum
+    Modified,
+    /// File is gone
+    Missing,
+}
+
+/// One line of a [`Project::verify`] report
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryCheck {
+    pub id: String,
+    pub filename: String,
+    pub status: EntryStatus,
+}
+
+/// Integrity report for a project
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    pub entries: Vec<EntryCheck>,
+    /// Media files in the folder that the timeline doesn't reference
+    pub untracked: Vec<String>,
+}
+
+impl VerifyReport {
+    /// True if every entry matches its file and nothing is untracked
+    pub fn is_clean(&self) -> bool {
+        self.untracked.is_empty() && self.entries.iter().all(|e| e.status == EntryStatus::Ok)
+    }
+}
+
+/// Reconciles that would drop more than this share of a timeline's entries
+/// need confirmation (see [`ReconcilePlan::needs_confirmation`])
+pub const DEFAULT_MAX_REMOVAL_PERCENT: u32 = 50;
+
+/// An entry whose file is gone
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemovedEntry {
+    pub id: String,
+    pub filename: String,
+}
+
+/// How a renamed file was recognized, strongest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RenameMatch {
+    /// The clip id embedded in the recording (survives copies and re-encodes)
+    ClipId,
+    /// Same inode (renamed or moved on the same volume, maybe edited in place)
+    FileId,
+    /// Identical content
+    Checksum,
+    /// Mostly the same content (trimmed or re-muxed)
+    Content,
+}
+
+/// An entry whose file now lives under another name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedEntry {
+    pub id: String,
+    pub from: String,
+    pub to: String,
+    pub matched_by: RenameMatch,
+    /// Checksum of the file under its new name
+    pub checksum: String,
+}
+
+/// A media file the timeline doesn't reference yet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddedFile {
+    pub filename: String,
+    pub checksum: String,
+}
+
+/// An entry whose file content changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumChange {
+    pub id: String,
+    pub filename: String,
+    /// New checksum of the file
+    pub checksum: String,
+}
+
+/// What [`Project::apply_reconcile`] would do to a timeline
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcilePlan {
+    pub removed: Vec<RemovedEntry>,
+    pub renamed: Vec<RenamedEntry>,
+    pub added: Vec<AddedFile>,
+    pub checksum_changed: Vec<ChecksumChange>,
+    /// Entries in the timeline when the plan was made
+    pub total_entries: usize,
+}
+
+impl ReconcilePlan {
+    /// True if applying the plan wouldn't change anything
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.renamed.is_empty() && self.added.is_empty() && self.checksum_changed.is_empty()
+    }
+
+    /// Whether the plan drops more than `max_removal_percent` of the
+    /// timeline - usually a sign that the folder is only partly there (an
+    /// unmounted network drive, a sync in progress) rather than that the
+    /// user deleted that many clips. Removing a single entry never counts,
+    /// so small projects can still lose a clip without a prompt.
+    pub fn needs_confirmation(&self, max_removal_percent: u32) -> bool {
+        self.removed.len() > 1 && self.removed.len() * 100 > self.total_entries * max_removal_percent as usize
+    }
+}
+
+/// A recording that [`Project::publish_clip`] moved to its final name
+#[derive(Debug, Clone)]
+pub struct PublishedClip {
+    pub path: PathBuf,
+    /// SHA-256 of the finished file
+    pub checksum: String,
+    /// Id the recorder embedded in the file (use it as the timeline entry id)
+    pub clip_id: Option<String>,
+}
+
+impl PublishedClip {
+    pub fn filename(&self) -> &str {
+        self.path.file_name().and_then(|n| n.to_str()).unwrap_or_default()
+    }
+}
+
+/// A media file found by [`Project::scan_media`]
+struct ScannedFile {
+    digest: FileDigest,
+    /// Inode, where the platform has one
+    file_id: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Project {
+    name: String,
+    folder: PathBuf,
+}
+
+impl Project {
+    pub(crate) fn new(name: &str, folder: PathBuf) -> Self {
+        Self { name: name.to_string(), folder }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    pub fn timeline_path(&self) -> PathBuf {
+        self.folder.join("timeline.json")
+    }
+
+    /// Load the project's timeline
+    pub fn timeline(&self) -> Result<Timeline, String> {
+        let path = self.timeline_path();
+        if !path.exists() {
+            return Err("Timeline not found".to_string());
+        }
+        timeline::load(&path)
+    }
+
+    /// Read, modify and write the timeline as one locked operation
+    /// (see [`timeline::update`])
+    pub fn update_timeline<T>(&self, f: impl FnOnce(&mut Timeline) -> Result<T, String>) -> Result<T, String> {
+        timeline::update(&self.timeline_path(), &self.name, f)
+    }
+
+    /// Replace the timeline with a copy edited elsewhere, unless the file
+    /// changed since that copy was loaded (see [`timeline::save_if_unmodified`])
+    pub fn save_timeline(&self, timeline: Timeline) -> Result<Timeline, String> {
+        timeline::save_if_unmodified(&self.timeline_path(), timeline)
+    }
+
+    /// Append an entry, creating the timeline if needed
+    pub fn add_entry(&self, entry: TimelineEntry) -> Result<(), String> {
+        self.update_timeline(|timeline| {
+            timeline.entries.push(entry);
+            timeline.refresh_metadata();
+            Ok(())
+        })
+    }
+
+    /// Find a timeline entry by id
+    pub fn find_entry(&self, id: &str) -> Result<TimelineEntry, String> {
+        self.timeline()?
+            .entries
+            .into_iter()
+            .find(|e| e.id == id)
+            .ok_or_else(|| format!("Timeline entry not found: {}", id))
+    }
+
+    /// Folder for animated exports
+    pub fn exports_folder(&self) -> PathBuf {
+        self.folder.join("exports")
+    }
+
+    /// Path to record a new clip to (`<id>.<extension>.partial`)
+    ///
+    /// The recording only gets its real name in [`Project::publish_clip`],
+    /// so other tools never see a half-written clip. Recorders should embed
+    /// `<id>` in the file (see [`media::clip_id_comment`]).
+    pub fn new_partial_path(&self, extension: &str) -> PathBuf {
+        self.folder.join(media::partial_filename(extension))
+    }
+
+    /// Give a finished recording its final name and advance the clip counter
+    ///
+    /// The partial file is synced to disk and hashed, then renamed in one
+    /// step to a free name rendered from `template` (with the real recording
+    /// time and label). A failure leaves the partial file in place, so it can
+    /// be recovered later.
+    pub fn publish_clip(&self, partial: &Path, template: &FilenameTemplate, label: Option<&str>, time: DateTime<Local>) -> Result<PublishedClip, String> {
+        let extension = media::published_extension(partial)
+            .ok_or_else(|| format!("Not a partial recording: {:?}", partial))?
+            .to_string();
+
+        fs::File::open(partial)
+            .and_then(|file| file.sync_all())
+            .map_err(|e| format!("Failed to sync recording to disk: {}", e))?;
+        let digest = checksum::file_digest(partial)?;
+        let clip_id = media::partial_clip_id(partial).map(str::to_string);
+
+        let path = timeline::with_lock(&self.folder.join(naming::SEQUENCE_FILE), || {
+            let (path, seq) = self.free_clip_path(template, label, time, &extension);
+            fs::rename(partial, &path).map_err(|e| format!("Failed to rename recording to {:?}: {}", path, e))?;
+            timeline::sync_dir(&self.folder);
+            if template.uses_seq() {
+                naming::commit_sequence(&self.folder, seq)?;
+            }
+            Ok(path)
+        })?;
+        debug!("📝 Published {:?} as {:?}", partial, path);
+
+        // Already hashed - the next reconcile doesn't need to
+        let published = PublishedClip { path, checksum: digest.checksum.clone(), clip_id };
+        if let Ok(meta) = fs::metadata(&published.path) {
+            let result = ChecksumCache::update(&self.folder, |cache| {
+                cache.insert(published.filename(), Fingerprint::of(&meta), &digest)
+            });
+            if let Err(e) = result {
+                warn!("⚠️  {}", e);
+            }
+        }
+        Ok(published)
+    }
+
+    /// Render `template` to a path that doesn't exist yet, returning it with
+    /// the clip number it used
+    fn free_clip_path(&self, template: &FilenameTemplate, label: Option<&str>, time: DateTime<Local>, extension: &str) -> (PathBuf, u64) {
+        let mut ctx = NameContext { project: &self.name, label, time, seq: naming::next_sequence(&self.folder) };
+        let mut attempt = 1;
+        loop {
+            let stem = template.render(&ctx);
+            let stem = if attempt > 1 { format!("{}-{}", stem, attempt) } else { stem };
+            let path = self.folder.join(format!("{}.{}", stem, extension));
+            if !path.exists() {
+                return (path, ctx.seq);
+            }
+
+            // Taken by a file created outside the app
+            if template.uses_seq() {
+                ctx.seq += 1;
+            } else {
+                attempt += 1;
+            }
+        }
+    }
+
+    /// Recordings that never finished (left behind by a crash or power loss)
+    pub fn partial_clips(&self) -> Result<Vec<PathBuf>, String> {
+        let entries = fs::read_dir(&self.folder).map_err(|e| format!("Failed to read project folder: {}", e))?;
+        let mut partials: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && media::is_partial_file(path))
+            .collect();
+        partials.sort();
+        Ok(partials)
+    }
+
+    /// Publish a leftover partial recording and add it to the timeline
+    ///
+    /// The clip is named as if it had been recorded when the file was last
+    /// written. Its duration is measured from the file, which may fail for a
+    /// recording that was cut off before the recorder finalized it.
+    pub fn recover_partial(&self, partial: &Path, template: &FilenameTemplate) -> Result<TimelineEntry, String> {
+        let modified = fs::metadata(partial)
+            .and_then(|m| m.modified())
+            .map_err(|e| format!("Failed to read partial recording: {}", e))?;
+        let published = self.publish_clip(partial, template, None, DateTime::<Local>::from(modified))?;
+
+        let filename = published.filename().to_string();
+        let mut entry = self.new_entry_for_file(&filename, &published.checksum);
+        if let Some(clip_id) = published.clip_id {
+            entry.id = clip_id;
+        }
+        entry.notes = Some("Recovered from an interrupted recording".to_string());
+        self.add_entry(entry.clone())?;
+        info!("🩹 Recovered interrupted recording as {}", filename);
+        Ok(entry)
+    }
+
+    /// Check every (non-deleted) timeline entry against its file, without
+    /// changing anything
+    pub fn verify(&self) -> Result<VerifyReport, String> {
+        let timeline = self.timeline()?;
+        let mut report = VerifyReport::default();
+
+        for entry in timeline.entries.iter().filter(|e| !e.is_deleted()) {
+            let path = self.folder.join(&entry.filename);
+            let status = if !path.is_file() {
+                EntryStatus::Missing
+            } else {
+                match &entry.checksum {
+                    None => EntryStatus::Unverified,
+                    Some(expected) => {
+                        if &checksum::file_checksum(&path)? == expected {
+                            EntryStatus::Ok
+                        } else {
+                            EntryStatus::Modified
+                        }
+                    }
+                }
+            };
+            report.entries.push(EntryCheck {
+                id: entry.id.clone(),
+                filename: entry.filename.clone(),
+                status,
+            });
+        }
+
+        let tracked: HashSet<&str> = timeline.entries.iter().map(|e| e.filename.as_str()).collect();
+        let files = fs::read_dir(&self.folder).map_err(|e| format!("Failed to read project folder: {}", e))?;
+        for file in files {
+            let path = file.map_err(|e| format!("Failed to read directory entry: {}", e))?.path();
+            if path.is_file() && EntryKind::from_path(&path).is_some() {
+                let filename = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                if !tracked.contains(filename.as_str()) {
+                    report.untracked.push(filename);
+                }
+            }
+        }
+        report.untracked.sort();
+
+        Ok(report)
+    }
+
+    /// Media files in the project folder with their digests (videos and images)
+    ///
+    /// Only files that changed since the last scan are hashed (see
+    /// [`ChecksumCache`]); `on_progress` follows the hashing.
+    fn scan_media(&self, on_progress: &mut dyn FnMut(&HashProgress)) -> Result<HashMap<String, ScannedFile>, String> {
+        let entries = fs::read_dir(&self.folder).map_err(|e| format!("Failed to read project folder: {}", e))?;
+        let cache = ChecksumCache::load(&self.folder);
+
+        let mut files = HashMap::new();
+        let mut to_hash = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let path = entry.path();
+
+            if path.is_file() && EntryKind::from_path(&path).is_some() {
+                let filename = entry.file_name().to_string_lossy().to_string();
+                let fingerprint = match entry.metadata() {
+                    Ok(meta) => Fingerprint::of(&meta),
+                    Err(e) => {
+                        warn!("⚠️  Failed to read metadata for {}: {}", filename, e);
+                        continue;
+                    }
+                };
+                match cache.get(&filename, &fingerprint) {
+                    Some(digest) => {
+                        files.insert(filename, ScannedFile { digest: digest.clone(), file_id: file_id(&fingerprint) });
+                    }
+                    None => to_hash.push((filename, path, fingerprint)),
+                }
+            }
+        }
+
+        let mut progress = HashProgress {
+            files_total: to_hash.len(),
+            bytes_total: to_hash.iter().map(|(_, _, fingerprint)| fingerprint.size).sum(),
+            ..Default::default()
+        };
+        let mut hashed = Vec::new();
+        for (filename, path, fingerprint) in to_hash {
+            progress.current_file = Some(filename.clone());
+            on_progress(&progress);
+
+            let bytes_before = progress.bytes_done;
+            let result = checksum::file_digest_with_progress(&path, &mut |read| {
+                progress.bytes_done = bytes_before + read;
+                on_progress(&progress);
+            });
+            progress.files_done += 1;
+            progress.bytes_done = bytes_before + fingerprint.size;
+
+            match result {
+                Ok(digest) => {
+                    files.insert(filename.clone(), ScannedFile { digest: digest.clone(), file_id: file_id(&fingerprint) });
+                    hashed.push((filename, fingerprint, digest));
+                }
+                Err(e) => warn!("⚠️  Failed to calculate checksum for {}: {}", filename, e),
+            }
+        }
+        if progress.files_total > 0 {
+            progress.current_file = None;
+            on_progress(&progress);
+        }
+
+        debug!("🔁 Checksums: {} cached, {} hashed", files.len() - hashed.len(), hashed.len());
+        if !hashed.is_empty() || cache.len() != files.len() {
+            let result = ChecksumCache::update(&self.folder, |cache| {
+                for (filename, fingerprint, digest) in &hashed {
+                    cache.insert(filename, *fingerprint, digest);
+                }
+                cache.retain(|filename| files.contains_key(filename));
+            });
+            if let Err(e) = result {
+                warn!("⚠️  {}", e);
+            }
+        }
+        Ok(files)
+    }
+
+    /// Reconcile the timeline with the actual files in the project folder
+    ///
+    /// Plans the changes (see [`Project::plan_reconcile`]) and applies them
+    /// straight away. Returns the number of entries removed, renamed or
+    /// added. The timeline is only written if something changed, so a
+    /// watcher reacting to timeline.json writes doesn't loop.
+    pub fn reconcile(&self) -> Result<u32, String> {
+        self.reconcile_with_progress(|_| {})
+    }
+
+    /// [`Project::reconcile`], reporting progress while new or changed files are hashed
+    pub fn reconcile_with_progress(&self, on_progress: impl FnMut(&HashProgress)) -> Result<u32, String> {
+        let plan = self.plan_reconcile_with_progress(on_progress)?;
+        self.apply_reconcile(&plan)
+    }
+
+    /// Work out what reconciling would change, without touching the timeline
+    ///
+    /// 1. Entries whose file is gone are matched to untracked files, by
+    ///    embedded clip id, then inode, then checksum, then content
+    ///    similarity (see [`RenameMatch`]) - a match is a rename, so the
+    ///    entry keeps its id, label and notes
+    /// 2. Other entries whose file is gone are removed
+    /// 3. Entries whose file changed get the new checksum
+    /// 4. Untracked files that weren't matched are added
+    pub fn plan_reconcile(&self) -> Result<ReconcilePlan, String> {
+        self.plan_reconcile_with_progress(|_| {})
+    }
+
+    /// [`Project::plan_reconcile`], reporting progress while new or changed files are hashed
+    pub fn plan_reconcile_with_progress(&self, mut on_progress: impl FnMut(&HashProgress)) -> Result<ReconcilePlan, String> {
+        if !self.folder.exists() {
+            return Err("Project folder does not exist".to_string());
+        }
+
+        // Checksums are computed here, outside the timeline lock
+        let actual_files = self.scan_media(&mut on_progress)?;
+        let timeline = self.timeline()?;
+
+        let tracked: HashSet<&str> = timeline.entries.iter().map(|entry| entry.filename.as_str()).collect();
+        let mut untracked: Vec<(&str, &ScannedFile)> = actual_files
+            .iter()
+            .filter(|(filename, _)| !tracked.contains(filename.as_str()))
+            .map(|(filename, file)| (filename.as_str(), file))
+            .collect();
+        untracked.sort_by_key(|(filename, _)| *filename);
+
+        let mut plan = ReconcilePlan { total_entries: timeline.entries.len(), ..Default::default() };
+        let mut missing = Vec::new();
+        for entry in &timeline.entries {
+            match actual_files.get(&entry.filename) {
+                Some(file) => {
+                    if entry.checksum.as_ref() != Some(&file.digest.checksum) {
+                        plan.checksum_changed.push(ChecksumChange {
+                            id: entry.id.clone(),
+                            filename: entry.filename.clone(),
+                            checksum: file.digest.checksum.clone(),
+                        });
+                    }
+                }
+                None => missing.push(entry),
+            }
+        }
+
+        let renames = if missing.is_empty() || untracked.is_empty() {
+            HashMap::new()
+        } else {
+            self.match_renames(&missing, &untracked)
+        };
+        for entry in missing {
+            match renames.get(entry.id.as_str()) {
+                Some(&(index, matched_by)) => {
+                    let (to, file) = untracked[index];
+                    plan.renamed.push(RenamedEntry {
+                        id: entry.id.clone(),
+                        from: entry.filename.clone(),
+                        to: to.to_string(),
+                        matched_by,
+                        checksum: file.digest.checksum.clone(),
+                    });
+                }
+                None => plan.removed.push(RemovedEntry { id: entry.id.clone(), filename: entry.filename.clone() }),
+            }
+        }
+
+        let renamed_to: HashSet<usize> = renames.values().map(|&(index, _)| index).collect();
+        plan.added = untracked
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !renamed_to.contains(index))
+            .map(|(_, (filename, file))| AddedFile { filename: filename.to_string(), checksum: file.digest.checksum.clone() })
+            .collect();
+
+        Ok(plan)
+    }
+
+    /// Pair entries whose file is gone with untracked files (entry id ->
+    /// index into `untracked`), trying the strongest kind of match first
+    fn match_renames<'a>(&self, missing: &[&'a TimelineEntry], untracked: &[(&str, &ScannedFile)]) -> HashMap<&'a str, (usize, RenameMatch)> {
+        // Only recordings made by Pulse have one
+        let clip_ids: Vec<Option<String>> = untracked
+            .iter()
+            .map(|(filename, _)| {
+                let path = self.folder.join(filename);
+                (EntryKind::from_path(&path) == Some(EntryKind::Video)).then(|| media::embedded_clip_id(&path)).flatten()
+            })
+            .collect();
+
+        let mut renames: HashMap<&str, (usize, RenameMatch)> = HashMap::new();
+        let mut taken = vec![false; untracked.len()];
+        for matched_by in [RenameMatch::ClipId, RenameMatch::FileId, RenameMatch::Checksum, RenameMatch::Content] {
+            for entry in missing {
+                if renames.contains_key(entry.id.as_str()) {
+                    continue;
+                }
+                let candidates = untracked.iter().enumerate().filter(|(index, _)| !taken[*index]);
+                let found = match matched_by {
+                    // A copy of the file carries the same id; the one that's
+                    // still the same file (inode, then checksum) is the original
+                    RenameMatch::ClipId => candidates
+                        .filter(|(index, _)| clip_ids[*index].as_deref() == Some(entry.id.as_str()))
+                        .min_by_key(|(_, (_, file))| {
+                            let same_inode = entry.file_id.is_some() && file.file_id == entry.file_id;
+                            let same_checksum = entry.checksum.as_ref() == Some(&file.digest.checksum);
+                            (!same_inode, !same_checksum)
+                        })
+                        .map(|(index, _)| index),
+                    // Inodes get reused, so a file that carries some other
+                    // clip's id or has another extension isn't this one
+                    RenameMatch::FileId => candidates
+                        .filter(|(index, (filename, file))| {
+                            entry.file_id.is_some()
+                                && file.file_id == entry.file_id
+                                && clip_ids[*index].is_none()
+                                && Path::new(filename).extension() == Path::new(&entry.filename).extension()
+                        })
+                        .map(|(index, _)| index)
+                        .next(),
+                    RenameMatch::Checksum => candidates
+                        .filter(|(_, (_, file))| entry.checksum.as_ref() == Some(&file.digest.checksum))
+                        .map(|(index, _)| index)
+                        .next(),
+                    RenameMatch::Content => entry.content_signature.and_then(|signature| {
+                        candidates
+                            .filter_map(|(index, (_, file))| Some((index, signature.similarity(&file.digest.signature?))))
+                            .filter(|(_, similarity)| *similarity >= SIMILARITY_THRESHOLD)
+                            .max_by(|a, b| a.1.total_cmp(&b.1))
+                            .map(|(index, _)| index)
+                    }),
+                };
+                if let Some(index) = found {
+                    taken[index] = true;
+                    renames.insert(entry.id.as_str(), (index, matched_by));
+                }
+            }
+        }
+        renames
+    }
+
+    /// Apply a (possibly reviewed) reconcile plan
+    ///
+    /// The plan may be stale by now, so each change is checked against the
+    /// current timeline and folder and skipped if it no longer holds: a
+    /// removed file that came back stays, an entry that was already renamed
+    /// or deleted is left alone. Returns the number of entries removed,
+    /// renamed or added.
+    pub fn apply_reconcile(&self, plan: &ReconcilePlan) -> Result<u32, String> {
+        if !self.folder.exists() {
+            return Err("Project folder does not exist".to_string());
+        }
+
+        let mut stale_thumbnails = Vec::new();
+        let changes_count = self.update_timeline(|timeline| {
+            let mut changes_count = 0u32;
+
+            for removal in &plan.removed {
+                if self.folder.join(&removal.filename).exists() {
+                    continue;
+                }
+                let original_count = timeline.entries.len();
+                timeline.entries.retain(|entry| !(entry.id == removal.id && entry.filename == removal.filename));
+                if timeline.entries.len() < original_count {
+                    info!("🗑️  Removed deleted file from timeline: {}", removal.filename);
+                    stale_thumbnails.push(removal.filename.clone());
+                    changes_count += 1;
+                }
+            }
+
+            for rename in &plan.renamed {
+                if !self.folder.join(&rename.to).exists() || timeline.entries.iter().any(|entry| entry.filename == rename.to) {
+                    continue;
+                }
+                if let Some(entry) = timeline.entries.iter_mut().find(|entry| entry.id == rename.id && entry.filename == rename.from) {
+                    info!("📝 Detected rename ({:?}): {} -> {}", rename.matched_by, rename.from, rename.to);
+                    stale_thumbnails.push(entry.filename.clone());
+                    entry.filename = rename.to.clone();
+                    entry.checksum = Some(rename.checksum.clone());
+                    // Regenerated under the new name
+                    entry.thumbnail = None;
+                    entry.filmstrip = None;
+                    changes_count += 1;
+                }
+            }
+
+            for change in &plan.checksum_changed {
+                if let Some(entry) = timeline.entries.iter_mut().find(|entry| entry.id == change.id && entry.filename == change.filename) {
+                    entry.checksum = Some(change.checksum.clone());
+                }
+            }
+
+            for added in &plan.added {
+                if !self.folder.join(&added.filename).exists() || timeline.entries.iter().any(|entry| entry.filename == added.filename) {
+                    continue;
+                }
+                let mut entry = self.new_entry_for_file(&added.filename, &added.checksum);
+                // A copy of a clip that's still in the timeline carries its id
+                if timeline.entries.iter().any(|existing| existing.id == entry.id) {
+                    entry.id = uuid::Uuid::new_v4().to_string();
+                }
+                info!("➕ Added new file to timeline: {}", added.filename);
+                timeline.entries.push(entry);
+                changes_count += 1;
+            }
+
+            self.refresh_identities(timeline);
+
+            // Sort entries by recorded_at timestamp (only when files changed, so a
+            // no-op reconcile doesn't undo the user's manual ordering)
+            if changes_count > 0 {
+                timeline.entries.sort_by(|a, b| a.recorded_at.cmp(&b.recorded_at));
+            }
+
+            timeline.refresh_metadata();
+            Ok(changes_count)
+        })?;
+
+        for filename in &stale_thumbnails {
+            self.remove_thumbnails(filename);
+        }
+
+        if changes_count > 0 {
+            info!("✅ Reconciliation complete: {} changes detected and saved", changes_count);
+        } else {
+            debug!("✅ Reconciliation complete: 0 changes detected (no file write needed)");
+        }
+        Ok(changes_count)
+    }
+
+    /// Record each entry's inode and content signature, so the next
+    /// reconcile can recognize the file if it's renamed or edited
+    ///
+    /// Signatures come from the checksum cache and are only taken if the
+    /// file hasn't changed since it was hashed.
+    fn refresh_identities(&self, timeline: &mut Timeline) {
+        let cache = ChecksumCache::load(&self.folder);
+        for entry in timeline.entries.iter_mut() {
+            let Ok(meta) = fs::metadata(self.folder.join(&entry.filename)) else {
+                continue;
+            };
+            let fingerprint = Fingerprint::of(&meta);
+            let id = file_id(&fingerprint);
+            if id.is_some() && entry.file_id != id {
+                entry.file_id = id;
+            }
+            if let Some(digest) = cache.get(&entry.filename, &fingerprint) {
+                if digest.signature.is_some() && entry.content_signature != digest.signature {
+                    entry.content_signature = digest.signature;
+                }
+            }
+        }
+    }
+
+    /// Soft-delete video clips shorter than `min_duration_ms` (accidental taps)
+    ///
+    /// Like deleting them in the app: the entries are marked deleted and the
+    /// files stay on disk, so nothing is lost if the minimum was set too high.
+    /// Returns the filenames that were newly marked. A minimum of 0 marks nothing.
+    pub fn delete_short_clips(&self, min_duration_ms: u64) -> Result<Vec<String>, String> {
+        if min_duration_ms == 0 {
+            return Ok(Vec::new());
+        }
+
+        let deleted_at = chrono::Utc::now().to_rfc3339();
+        let deleted = self.update_timeline(|timeline| {
+            let mut deleted = Vec::new();
+            for entry in timeline.entries.iter_mut() {
+                if entry.kind == EntryKind::Video && entry.duration_ms < min_duration_ms && !entry.is_deleted() {
+                    entry.deleted = Some(true);
+                    entry.deleted_at = Some(deleted_at.clone());
+                    deleted.push(entry.filename.clone());
+                }
+            }
+            timeline.refresh_metadata();
+            Ok(deleted)
+        })?;
+
+        for filename in &deleted {
+            info!("✂️  Marked clip shorter than {}ms as deleted: {}", min_duration_ms, filename);
+        }
+        Ok(deleted)
+    }
+
+    /// Build a timeline entry for a file found on disk
+    fn new_entry_for_file(&self, filename: &str, checksum: &str) -> TimelineEntry {
+        let file_path = self.folder.join(filename);
+
+        let (created_time, file_size) = match fs::metadata(&file_path) {
+            Ok(meta) => (meta.created().unwrap_or(std::time::SystemTime::now()), meta.len()),
+            Err(_) => (std::time::SystemTime::now(), 0),
+        };
+
+        // Convert system time to RFC3339 string
+        let created_rfc3339 = match created_time.duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => {
+                let datetime = chrono::DateTime::<chrono::Utc>::from_timestamp(duration.as_secs() as i64, 0);
+                datetime.map(|dt| dt.to_rfc3339()).unwrap_or_else(|| chrono::Utc::now().to_rfc3339())
+            }
+            Err(_) => chrono::Utc::now().to_rfc3339(),
+        };
+
+        // Try to detect video properties from filename patterns
+        let (aspect_ratio, width, height) = media::detect_video_properties_from_filename(filename);
+        let kind = EntryKind::from_path(&file_path).unwrap_or_default();
+
+        // Calculate actual video duration (images have none)
+        let duration_ms = if kind == EntryKind::Image {
+            0
+        } else {
+            match media::video_duration_ms(&file_path) {
+                Ok(duration) => {
+                    info!("📊 Calculated actual video duration: {}ms", duration);
+                    duration
+                }
+                Err(e) => {
+                    warn!("⚠️ Failed to calculate video duration, using file size estimate: {}", e);
+                    media::estimate_duration_from_file_size(file_size)
+                }
+            }
+        };
+
+        // Recordings made by Pulse keep their original id
+        let id = media::embedded_clip_id(&file_path).unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        TimelineEntry {
+            id,
+            kind,
+            filename: filename.to_string(),
+            recorded_at: created_rfc3339,
+            duration_ms,
+            aspect_ratio,
+            resolution: Resolution { width, height },
+            mic_enabled: true,
+            notes: Some("Added during timeline reconciliation".to_string()),
+            checksum: Some(checksum.to_string()),
+            ..Default::default() // Thumbnails are generated by the app
+        }
+    }
+
+    /// Delete the thumbnails that belonged to a removed clip
+    pub fn remove_thumbnails(&self, clip_filename: &str) {
+        for filename in [media::poster_filename(clip_filename), media::filmstrip_filename(clip_filename)] {
+            let path = self.folder.join(filename);
+            if path.exists() {
+                if let Err(e) = fs::remove_file(&path) {
+                    warn!("⚠️ Failed to remove thumbnail {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+}
+
+/// Inode from a fingerprint (0 means the platform doesn't have one)
+fn file_id(fingerprint: &Fingerprint) -> Option<u64> {
+    (fingerprint.inode != 0).then_some(fingerprint.inode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameMatch;
+    use crate::Library;
+    use std::fs;
+
+    fn demo_project(dir: &std::path::Path) -> crate::Project {
+        Library::new(dir).create_project("Demo").unwrap()
+    }
+
+    #[test]
+    fn test_reconcile_adds_new_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        fs::write(project.folder().join("recording-1.mp4"), b"video one").unwrap();
+        fs::write(project.folder().join("screenshot-1.png"), b"image one").unwrap();
+        fs::write(project.folder().join("recording-1.thumb.png"), b"poster").unwrap();
+        fs::write(project.folder().join("notes.txt"), b"not media").unwrap();
+
+        assert_eq!(project.reconcile().unwrap(), 2);
+
+        let timeline = project.timeline().unwrap();
+        assert_eq!(timeline.entries.len(), 2);
+        assert!(timeline.entries.iter().all(|e| e.checksum.is_some()));
+        assert_eq!(timeline.metadata.total_videos, 1);
+
+        // Nothing changed on disk: no changes, no write
+        let last_modified = timeline.last_modified;
+        assert_eq!(project.reconcile().unwrap(), 0);
+        assert_eq!(project.timeline().unwrap().last_modified, last_modified);
+    }
+
+    #[test]
+    fn test_reconcile_removes_missing_files_and_thumbnails() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        fs::write(project.folder().join("recording-1.mp4"), b"video one").unwrap();
+        project.reconcile().unwrap();
+
+        fs::write(project.folder().join("recording-1.thumb.png"), b"poster").unwrap();
+        fs::remove_file(project.folder().join("recording-1.mp4")).unwrap();
+
+        assert_eq!(project.reconcile().unwrap(), 1);
+        assert!(project.timeline().unwrap().entries.is_empty());
+        assert!(!project.folder().join("recording-1.thumb.png").exists());
+    }
+
+    #[test]
+    fn test_reconcile_refreshes_changed_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        let path = project.folder().join("recording-1.mp4");
+        fs::write(&path, b"first take").unwrap();
+        project.reconcile().unwrap();
+        let before = project.timeline().unwrap().entries[0].checksum.clone();
+
+        fs::write(&path, b"second take").unwrap();
+        project.reconcile().unwrap();
+        let after = project.timeline().unwrap().entries[0].checksum.clone();
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn test_plan_reconcile_reports_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        fs::write(project.folder().join("recording-1.mp4"), b"video one").unwrap();
+        fs::write(project.folder().join("recording-2.mp4"), b"video two").unwrap();
+        project.reconcile().unwrap();
+        let before = project.timeline().unwrap();
+
+        fs::rename(project.folder().join("recording-1.mp4"), project.folder().join("intro.mp4")).unwrap();
+        fs::remove_file(project.folder().join("recording-2.mp4")).unwrap();
+        fs::write(project.folder().join("recording-3.mp4"), b"video three").unwrap();
+
+        let plan = project.plan_reconcile().unwrap();
+        assert_eq!(plan.total_entries, 2);
+        assert_eq!(plan.renamed.len(), 1);
+        assert_eq!((plan.renamed[0].from.as_str(), plan.renamed[0].to.as_str()), ("recording-1.mp4", "intro.mp4"));
+        assert_eq!(plan.removed.len(), 1);
+        assert_eq!(plan.removed[0].filename, "recording-2.mp4");
+        assert_eq!(plan.added.len(), 1);
+        assert_eq!(plan.added[0].filename, "recording-3.mp4");
+        assert_eq!(project.timeline().unwrap().last_modified, before.last_modified);
+
+        // The renamed entry keeps its id
+        assert_eq!(project.apply_reconcile(&plan).unwrap(), 3);
+        let timeline = project.timeline().unwrap();
+        let renamed = timeline.entries.iter().find(|e| e.filename == "intro.mp4").unwrap();
+        assert_eq!(renamed.id, plan.renamed[0].id);
+        assert!(project.plan_reconcile().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_apply_reconcile_skips_stale_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        fs::write(project.folder().join("recording-1.mp4"), b"video one").unwrap();
+        project.reconcile().unwrap();
+
+        // The drive goes away while the plan is being reviewed, then comes back
+        fs::rename(project.folder().join("recording-1.mp4"), dir.path().join("away.mp4")).unwrap();
+        let plan = project.plan_reconcile().unwrap();
+        assert_eq!(plan.removed.len(), 1);
+        fs::rename(dir.path().join("away.mp4"), project.folder().join("recording-1.mp4")).unwrap();
+
+        assert_eq!(project.apply_reconcile(&plan).unwrap(), 0);
+        assert_eq!(project.timeline().unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_needs_confirmation_for_mass_removal() {
+        use crate::project::{ReconcilePlan, RemovedEntry, DEFAULT_MAX_REMOVAL_PERCENT};
+
+        let plan = |removed: usize, total_entries: usize| ReconcilePlan {
+            removed: (0..removed).map(|i| RemovedEntry { id: i.to_string(), filename: format!("{}.mp4", i) }).collect(),
+            total_entries,
+            ..Default::default()
+        };
+        assert!(plan(10, 10).needs_confirmation(DEFAULT_MAX_REMOVAL_PERCENT));
+        assert!(plan(6, 10).needs_confirmation(DEFAULT_MAX_REMOVAL_PERCENT));
+        assert!(!plan(5, 10).needs_confirmation(DEFAULT_MAX_REMOVAL_PERCENT));
+        // Deleting the only clip of a small project is fine
+        assert!(!plan(1, 1).needs_confirmation(DEFAULT_MAX_REMOVAL_PERCENT));
+    }
+
+    fn noise(len: usize, mut state: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rename_of_edited_clip_keeps_label_and_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        let original = noise(2 * 1024 * 1024, 7);
+        fs::write(project.folder().join("recording-1.mp4"), &original).unwrap();
+        project.reconcile().unwrap();
+        project.update_timeline(|timeline| {
+            timeline.entries[0].label = Some("Intro".to_string());
+            timeline.entries[0].notes = Some("Keep this take".to_string());
+            Ok(())
+        }).unwrap();
+
+        // Trimmed in an editor and saved under a new name
+        fs::write(project.folder().join("intro-final.mp4"), &original[200 * 1024..]).unwrap();
+        fs::remove_file(project.folder().join("recording-1.mp4")).unwrap();
+
+        let plan = project.plan_reconcile().unwrap();
+        assert_eq!(plan.renamed.len(), 1, "{:?}", plan);
+        assert_eq!(plan.renamed[0].matched_by, RenameMatch::Content);
+        assert!(plan.removed.is_empty() && plan.added.is_empty());
+
+        project.apply_reconcile(&plan).unwrap();
+        let entry = project.timeline().unwrap().entries[0].clone();
+        assert_eq!(entry.filename, "intro-final.mp4");
+        assert_eq!(entry.label.as_deref(), Some("Intro"));
+        assert_eq!(entry.notes.as_deref(), Some("Keep this take"));
+    }
+
+    #[test]
+    fn test_rename_of_reencoded_recording_matches_clip_id() {
+        use crate::mp4::tests::mp4_with_comment;
+
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        let partial = project.new_partial_path("mp4");
+        let comment = crate::media::clip_id_comment(crate::media::partial_clip_id(&partial).unwrap());
+        fs::write(&partial, mp4_with_comment(&comment, b"original frames")).unwrap();
+        let published = project.publish_clip(&partial, &crate::FilenameTemplate::default(), None, chrono::Local::now()).unwrap();
+        project.reconcile().unwrap();
+        let id = project.timeline().unwrap().entries[0].id.clone();
+        assert_eq!(Some(&id), published.clip_id.as_ref());
+
+        // Re-encoded: every byte differs, the comment tag is kept
+        fs::remove_file(&published.path).unwrap();
+        fs::write(project.folder().join("small.mp4"), mp4_with_comment(&comment, b"re-encoded frames, smaller")).unwrap();
+        // ...and a copy of it, which gets an id of its own
+        fs::write(project.folder().join("small-copy.mp4"), mp4_with_comment(&comment, b"copy")).unwrap();
+
+        let plan = project.plan_reconcile().unwrap();
+        assert_eq!(plan.renamed.len(), 1);
+        assert_eq!(plan.renamed[0].matched_by, RenameMatch::ClipId);
+        // Both carry the id; the first in name order takes the entry over
+        assert_eq!(plan.renamed[0].to, "small-copy.mp4");
+        project.apply_reconcile(&plan).unwrap();
+
+        let timeline = project.timeline().unwrap();
+        assert_eq!(timeline.entries.len(), 2);
+        assert!(timeline.entries.iter().any(|e| e.id == id));
+        assert!(timeline.entries.iter().any(|e| e.id != id));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_clip_id_match_prefers_original_over_copy() {
+        use crate::mp4::tests::mp4_with_comment;
+
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        let partial = project.new_partial_path("mp4");
+        let comment = crate::media::clip_id_comment(crate::media::partial_clip_id(&partial).unwrap());
+        fs::write(&partial, mp4_with_comment(&comment, b"original frames")).unwrap();
+        let published = project.publish_clip(&partial, &crate::FilenameTemplate::default(), None, chrono::Local::now()).unwrap();
+        project.reconcile().unwrap();
+
+        // Duplicated, then the original renamed: the copy sorts first
+        let original = project.folder().join("zz-original.mp4");
+        fs::copy(&published.path, project.folder().join("aa-copy.mp4")).unwrap();
+        fs::rename(&published.path, &original).unwrap();
+
+        let plan = project.plan_reconcile().unwrap();
+        assert_eq!(plan.renamed.len(), 1);
+        assert_eq!(plan.renamed[0].matched_by, RenameMatch::ClipId);
+        assert_eq!(plan.renamed[0].to, "zz-original.mp4");
+        assert_eq!(plan.added.len(), 1);
+        assert_eq!(plan.added[0].filename, "aa-copy.mp4");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rename_matches_inode_after_edit_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        let path = project.folder().join("recording-1.mp4");
+        fs::write(&path, b"first take").unwrap();
+        project.reconcile().unwrap();
+
+        let renamed = project.folder().join("demo.mp4");
+        fs::rename(&path, &renamed).unwrap();
+        // Rewritten in place: same inode, nothing else in common
+        fs::write(&renamed, b"tags rewritten in place").unwrap();
+
+        let plan = project.plan_reconcile().unwrap();
+        assert_eq!(plan.renamed.len(), 1);
+        assert_eq!(plan.renamed[0].matched_by, RenameMatch::FileId);
+    }
+
+    #[test]
+    fn test_publish_clip_renames_partial_to_a_free_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        let template = crate::FilenameTemplate::default();
+        let now = chrono::Local::now();
+
+        let partial = project.new_partial_path("mp4");
+        assert!(crate::media::is_partial_file(&partial));
+        fs::write(&partial, b"take one").unwrap();
+        // Half-written recordings aren't media as far as reconcile is concerned
+        assert_eq!(project.reconcile().unwrap(), 0);
+
+        // Another tool wrote recording-1 while the clip was being recorded
+        fs::write(project.folder().join("recording-1.mp4"), b"someone else's").unwrap();
+        let published = project.publish_clip(&partial, &template, None, now).unwrap();
+        assert_eq!(published.filename(), "recording-2.mp4");
+        assert_eq!(published.checksum, crate::checksum::file_checksum(&published.path).unwrap());
+        assert!(!partial.exists());
+
+        let partial = project.new_partial_path("mp4");
+        fs::write(&partial, b"take two").unwrap();
+        let labelled = crate::FilenameTemplate::parse("{label}").unwrap();
+        assert_eq!(project.publish_clip(&partial, &labelled, Some("Intro"), now).unwrap().filename(), "Intro.mp4");
+
+        let partial = project.new_partial_path("mp4");
+        fs::write(&partial, b"take three").unwrap();
+        assert_eq!(project.publish_clip(&partial, &labelled, Some("Intro"), now).unwrap().filename(), "Intro-2.mp4");
+
+        // The counter only moved for the numbered clip
+        assert_eq!(crate::naming::next_sequence(project.folder()), 3);
+    }
+
+    #[test]
+    fn test_recover_partial_adds_timeline_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        let partial = project.new_partial_path("mp4");
+        fs::write(&partial, b"cut off").unwrap();
+
+        assert_eq!(project.partial_clips().unwrap(), vec![partial.clone()]);
+        let entry = project.recover_partial(&partial, &crate::FilenameTemplate::default()).unwrap();
+
+        assert_eq!(entry.filename, "recording-1.mp4");
+        assert!(entry.checksum.is_some());
+        assert!(project.partial_clips().unwrap().is_empty());
+        assert_eq!(project.timeline().unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_reconcile_only_hashes_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        fs::write(project.folder().join("recording-1.mp4"), b"video one").unwrap();
+        fs::write(project.folder().join("recording-2.mp4"), b"video two").unwrap();
+
+        let hashed = |project: &crate::Project| {
+            let mut total = 0;
+            project.reconcile_with_progress(|p| total = p.files_total).unwrap();
+            total
+        };
+        assert_eq!(hashed(&project), 2);
+        assert_eq!(hashed(&project), 0);
+
+        fs::write(project.folder().join("recording-2.mp4"), b"video two, re-encoded").unwrap();
+        assert_eq!(hashed(&project), 1);
+        let timeline = project.timeline().unwrap();
+        let entry = timeline.entries.iter().find(|e| e.filename == "recording-2.mp4").unwrap();
+        assert_eq!(entry.checksum, Some(crate::checksum::file_checksum(&project.folder().join("recording-2.mp4")).unwrap()));
+
+        // Published recordings are cached straight away
+        let partial = project.new_partial_path("mp4");
+        fs::write(&partial, b"video three").unwrap();
+        project.publish_clip(&partial, &crate::FilenameTemplate::default(), None, chrono::Local::now()).unwrap();
+        assert_eq!(hashed(&project), 0);
+    }
+
+    #[test]
+    fn test_delete_short_clips() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        for (filename, duration_ms) in [("recording-1.mp4", 200), ("recording-2.mp4", 5000)] {
+            fs::write(project.folder().join(filename), b"video").unwrap();
+            project.add_entry(crate::TimelineEntry {
+                filename: filename.to_string(),
+                duration_ms,
+                ..Default::default()
+            }).unwrap();
+        }
+        fs::write(project.folder().join("recording-1.thumb.png"), b"poster").unwrap();
+
+        assert!(project.delete_short_clips(0).unwrap().is_empty());
+        assert_eq!(project.delete_short_clips(500).unwrap(), ["recording-1.mp4"]);
+        // Already deleted
+        assert!(project.delete_short_clips(500).unwrap().is_empty());
+
+        let timeline = project.timeline().unwrap();
+        assert_eq!(timeline.entries.len(), 2);
+        assert!(timeline.entries[0].is_deleted());
+        assert!(timeline.entries[0].deleted_at.is_some());
+        assert!(!timeline.entries[1].is_deleted());
+        assert_eq!(timeline.metadata.total_videos, 1);
+        // The files are left alone
+        assert!(project.folder().join("recording-1.mp4").exists());
+        assert!(project.folder().join("recording-1.thumb.png").exists());
+    }
+
+    #[test]
+    fn test_verify_reports_problems() {
+        use crate::project::EntryStatus;
+
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        fs::write(project.folder().join("recording-1.mp4"), b"video one").unwrap();
+        fs::write(project.folder().join("recording-2.mp4"), b"video two").unwrap();
+        fs::write(project.folder().join("recording-3.mp4"), b"video three").unwrap();
+        project.reconcile().unwrap();
+        assert!(project.verify().unwrap().is_clean());
+
+        fs::write(project.folder().join("recording-1.mp4"), b"edited").unwrap();
+        fs::remove_file(project.folder().join("recording-2.mp4")).unwrap();
+        fs::write(project.folder().join("recording-4.mp4"), b"video four").unwrap();
+
+        let report = project.verify().unwrap();
+        assert!(!report.is_clean());
+        let status = |name: &str| report.entries.iter().find(|e| e.filename == name).unwrap().status.clone();
+        assert_eq!(status("recording-1.mp4"), EntryStatus::Modified);
+        assert_eq!(status("recording-2.mp4"), EntryStatus::Missing);
+        assert_eq!(status("recording-3.mp4"), EntryStatus::Ok);
+        assert_eq!(report.untracked, ["recording-4.mp4"]);
+    }
+
+    #[test]
+    fn test_add_and_find_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = demo_project(dir.path());
+        let entry = crate::TimelineEntry {
+            id: "abc".to_string(),
+            filename: "recording-1.mp4".to_string(),
+            duration_ms: 1500,
+            ..Default::default()
+        };
+
+        project.add_entry(entry).unwrap();
+        assert_eq!(project.find_entry("abc").unwrap().duration_ms, 1500);
+        assert_eq!(project.timeline().unwrap().metadata.total_duration, 1500);
+        assert!(project.find_entry("missing").is_err());
+    }
+}
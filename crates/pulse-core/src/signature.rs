@@ -0,0 +1,189 @@
+// Content signatures for recognizing a clip after it was edited
+//
+// A SHA-256 changes completely when a single byte does, so it can't tell
+// that a trimmed or re-muxed clip is still the same recording. Here the file
+// is cut into content-defined chunks - boundaries depend on the bytes around
+// them, so an insertion or deletion only changes the chunks it touches - and
+// a MinHash over the chunk hashes estimates the share of chunks two files
+// have in common.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Number of MinHash values kept per file
+pub const SIGNATURE_SIZE: usize = 16;
+
+/// Files with at least this estimated share of common chunks are treated as
+/// the same clip
+pub const SIMILARITY_THRESHOLD: f64 = 0.5;
+
+const MIN_CHUNK: usize = 16 * 1024;
+const MAX_CHUNK: usize = 256 * 1024;
+/// 16 bits: about 64KB between boundaries on average
+const BOUNDARY_MASK: u64 = (1 << 16) - 1;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Random value per byte for the rolling (gear) hash
+const GEAR: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = mix(i as u64 + 1);
+        i += 1;
+    }
+    table
+};
+
+/// splitmix64 finalizer
+const fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// MinHash of a file's content-defined chunks
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ContentSignature([u64; SIGNATURE_SIZE]);
+
+impl ContentSignature {
+    /// Estimated share of chunks the two files have in common (0.0 - 1.0)
+    pub fn similarity(&self, other: &ContentSignature) -> f64 {
+        let same = self.0.iter().zip(other.0.iter()).filter(|(a, b)| a == b).count();
+        same as f64 / SIGNATURE_SIZE as f64
+    }
+
+    pub fn parse(hex: &str) -> Option<Self> {
+        if hex.len() != SIGNATURE_SIZE * 16 || !hex.is_ascii() {
+            return None;
+        }
+        let mut values = [0u64; SIGNATURE_SIZE];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = u64::from_str_radix(&hex[i * 16..(i + 1) * 16], 16).ok()?;
+        }
+        Some(Self(values))
+    }
+}
+
+impl fmt::Display for ContentSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|value| write!(f, "{:016x}", value))
+    }
+}
+
+impl fmt::Debug for ContentSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContentSignature({})", self)
+    }
+}
+
+// Stored as a hex string in timeline.json and the checksum cache
+impl Serialize for ContentSignature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentSignature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Self::parse(&hex).ok_or_else(|| serde::de::Error::custom("invalid content signature"))
+    }
+}
+
+/// Builds a [`ContentSignature`] from a file read in pieces
+pub struct SignatureBuilder {
+    rolling: u64,
+    chunk_hash: u64,
+    chunk_len: usize,
+    chunks: usize,
+    mins: [u64; SIGNATURE_SIZE],
+}
+
+impl SignatureBuilder {
+    pub fn new() -> Self {
+        Self {
+            rolling: 0,
+            chunk_hash: FNV_OFFSET,
+            chunk_len: 0,
+            chunks: 0,
+            mins: [u64::MAX; SIGNATURE_SIZE],
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.rolling = (self.rolling << 1).wrapping_add(GEAR[byte as usize]);
+            self.chunk_hash = (self.chunk_hash ^ byte as u64).wrapping_mul(FNV_PRIME);
+            self.chunk_len += 1;
+            if (self.chunk_len >= MIN_CHUNK && self.rolling & BOUNDARY_MASK == 0) || self.chunk_len >= MAX_CHUNK {
+                self.end_chunk();
+            }
+        }
+    }
+
+    /// The signature, or None for an empty file
+    pub fn finish(mut self) -> Option<ContentSignature> {
+        if self.chunk_len > 0 {
+            self.end_chunk();
+        }
+        (self.chunks > 0).then_some(ContentSignature(self.mins))
+    }
+
+    fn end_chunk(&mut self) {
+        for (i, min) in self.mins.iter_mut().enumerate() {
+            *min = (*min).min(mix(self.chunk_hash ^ GEAR[i]));
+        }
+        self.chunks += 1;
+        self.chunk_hash = FNV_OFFSET;
+        self.chunk_len = 0;
+    }
+}
+
+impl Default for SignatureBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature(data: &[u8]) -> ContentSignature {
+        let mut builder = SignatureBuilder::new();
+        // Chunk boundaries don't depend on how the file is read
+        for piece in data.chunks(8192) {
+            builder.update(piece);
+        }
+        builder.finish().unwrap()
+    }
+
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        (0..len as u64).map(|i| mix(i ^ (seed << 40)) as u8).collect()
+    }
+
+    #[test]
+    fn test_edited_file_stays_similar() {
+        let original = noise(4 * 1024 * 1024, 1);
+        let sig = signature(&original);
+
+        // Trimmed at the start, a tag inserted in the middle
+        let mut edited = original[300 * 1024..].to_vec();
+        edited.splice(1024 * 1024..1024 * 1024, b"edited".iter().copied());
+        assert!(sig.similarity(&signature(&edited)) >= SIMILARITY_THRESHOLD);
+
+        // A different recording shares nothing
+        assert!(sig.similarity(&signature(&noise(4 * 1024 * 1024, 2))) < SIMILARITY_THRESHOLD);
+        assert!(SignatureBuilder::new().finish().is_none());
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let sig = signature(b"a short clip");
+        assert_eq!(ContentSignature::parse(&sig.to_string()), Some(sig));
+        assert_eq!(ContentSignature::parse("abc"), None);
+    }
+}
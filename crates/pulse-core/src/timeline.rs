@@ -0,0 +1,768 @@
+// timeline.json schema
+//
+// This is the single definition of the project timeline format shared by the
+// desktop app and the CLI. The frontend edits the same file (labels, soft
+// deletes, ordering), so every struct keeps fields it doesn't know about in
+// `extra` and writes them back unchanged - a round trip through the backend
+// never loses data.
+//
+// Files carry a `schemaVersion`. Older files are upgraded in place by the
+// migration chain in `load` (after backing up the original); files from a
+// newer version of the app are rejected rather than misread.
+//
+// All access goes through `load`, `update`, `save` and `save_if_unmodified`.
+// They serialize read-modify-write cycles per timeline file and write via a
+// temp file + fsync + rename, so concurrent writers (recording finishing,
+// UI saves, watcher-triggered reconciles) can't lose entries and a crash
+// can't leave a truncated timeline.json behind.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use log::{info, warn};
+use crate::checksum::Fingerprint;
+use crate::media;
+use crate::signature::ContentSignature;
+
+/// Current timeline.json schema version
+///
+/// - 1: no `schemaVersion` field, mixed snake_case/camelCase keys
+/// - 2: `schemaVersion` added, camelCase timestamps/metadata, explicit `kind`
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// What kind of media a timeline entry points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    #[default]
+    Video,
+    Image,
+}
+
+impl EntryKind {
+    /// Classify a file by extension (None if it isn't timeline media)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        if media::is_thumbnail_file(path) {
+            return None;
+        }
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "mp4" | "mov" | "avi" | "mkv" | "webm" | "m4v" => Some(EntryKind::Video),
+            "png" | "jpg" | "jpeg" => Some(EntryKind::Image),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub id: String,
+    #[serde(default)]
+    pub kind: EntryKind, // Entries written before screenshots existed are videos
+    pub filename: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>, // User-editable display name
+    #[serde(rename = "recordedAt", alias = "recorded_at")]
+    pub recorded_at: String,
+    #[serde(rename = "durationMs", alias = "duration_ms")]
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<bool>, // Soft delete flag, set by the frontend
+    #[serde(rename = "deletedAt", alias = "deleted_at", default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
+    #[serde(rename = "aspectRatio", alias = "aspect_ratio")]
+    pub aspect_ratio: String,
+    pub resolution: Resolution,
+    #[serde(rename = "micEnabled", alias = "mic_enabled")]
+    pub mic_enabled: bool,
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>, // SHA256 hash for file integrity and rename detection
+    #[serde(rename = "fileId", alias = "file_id", default, skip_serializing_if = "Option::is_none", with = "u64_string::option")]
+    pub file_id: Option<u64>, // Inode of the file - stays the same when it's renamed or moved
+    #[serde(rename = "contentSignature", alias = "content_signature", default, skip_serializing_if = "Option::is_none")]
+    pub content_signature: Option<ContentSignature>, // Recognizes the clip after edits (see signature.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>, // Poster frame filename, relative to the project folder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filmstrip: Option<String>, // Filmstrip sprite filename, relative to the project folder
+    #[serde(rename = "thumbnailFailed", alias = "thumbnail_failed", default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail_failed: Option<Fingerprint>, // File as it was when no poster frame could be made (retried once it changes)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub markers: Vec<u64>, // Marker offsets in ms from the start of the clip
+    #[serde(flatten)]
+    pub extra: Map<String, Value>, // Fields written by newer versions or the frontend
+}
+
+impl TimelineEntry {
+    /// Check if the entry was soft-deleted in the UI
+    pub fn is_deleted(&self) -> bool {
+        self.deleted == Some(true)
+    }
+}
+
+/// u64 written as a JSON string
+///
+/// The frontend reads timeline.json into JS numbers, which round anything
+/// above 2^53 - inodes and nanosecond timestamps included. Plain numbers
+/// (older files) are still read.
+pub(crate) mod u64_string {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        String(String),
+        Number(u64),
+    }
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        match Repr::deserialize(deserializer)? {
+            Repr::String(s) => s.parse().map_err(de::Error::custom),
+            Repr::Number(n) => Ok(n),
+        }
+    }
+
+    /// Same, for optional fields (use with `default`)
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(value: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => super::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapped(#[serde(with = "super")] u64);
+
+            Ok(Option::<Wrapped>::deserialize(deserializer)?.map(|Wrapped(n)| n))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timeline {
+    #[serde(rename = "schemaVersion", default = "legacy_schema_version")]
+    pub schema_version: u32,
+    #[serde(rename = "projectName", alias = "project_name")]
+    pub project_name: String,
+    #[serde(rename = "createdAt", alias = "created_at")]
+    pub created_at: String,
+    #[serde(rename = "lastModified", alias = "last_modified")]
+    pub last_modified: String,
+    pub entries: Vec<TimelineEntry>,
+    pub metadata: TimelineMetadata,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Timeline {
+    /// Create an empty timeline for a project
+    pub fn new(project_name: &str) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            project_name: project_name.to_string(),
+            created_at: now.clone(),
+            last_modified: now,
+            entries: Vec::new(),
+            metadata: TimelineMetadata::default(),
+            extra: Map::new(),
+        }
+    }
+
+    /// Recompute video count and total duration from the entries
+    /// (soft-deleted entries don't count)
+    pub fn refresh_metadata(&mut self) {
+        let videos = self.entries.iter().filter(|e| e.kind == EntryKind::Video && !e.is_deleted());
+        self.metadata.total_videos = videos.clone().count() as u32;
+        self.metadata.total_duration = videos.map(|e| e.duration_ms).sum();
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimelineMetadata {
+    #[serde(rename = "totalVideos", alias = "total_videos")]
+    pub total_videos: u32,
+    #[serde(rename = "totalDuration", alias = "total_duration")]
+    pub total_duration: u64,
+    #[serde(rename = "defaultAspectRatio", alias = "default_aspect_ratio")]
+    pub default_aspect_ratio: Option<String>,
+    pub tags: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// One lock per timeline.json path
+static LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+
+/// Run `f` while holding the lock for a timeline file
+pub(crate) fn with_lock<T>(path: &Path, f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    let lock = {
+        let mut locks = LOCKS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        locks.entry(path.to_path_buf()).or_default().clone()
+    };
+    // A panic while holding the lock can't leave a half-written file behind
+    // (writes are atomic), so a poisoned lock is safe to reuse
+    let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+    f()
+}
+
+/// Read a timeline.json, migrating it to the current schema if needed
+///
+/// Older files are backed up to `timeline.v<N>.bak.json` and rewritten in
+/// place, so migrations run once per file.
+pub fn load(path: &Path) -> Result<Timeline, String> {
+    with_lock(path, || load_unlocked(path))
+}
+
+/// Read, modify and write a timeline as one locked operation
+///
+/// Creates an empty timeline if the file doesn't exist. The file is only
+/// written (with a fresh `lastModified`) if `f` actually changed something.
+pub fn update<T>(
+    path: &Path,
+    project_name: &str,
+    f: impl FnOnce(&mut Timeline) -> Result<T, String>,
+) -> Result<T, String> {
+    with_lock(path, || {
+        let original = if path.exists() { Some(load_unlocked(path)?) } else { None };
+        let mut timeline = original.clone().unwrap_or_else(|| Timeline::new(project_name));
+
+        let result = f(&mut timeline)?;
+
+        if original.as_ref() != Some(&timeline) {
+            timeline.last_modified = chrono::Utc::now().to_rfc3339();
+            write_atomic(path, &timeline)?;
+        }
+        Ok(result)
+    })
+}
+
+/// Write a timeline.json as-is (for new projects)
+pub fn save(path: &Path, timeline: &Timeline) -> Result<(), String> {
+    with_lock(path, || write_atomic(path, timeline))
+}
+
+/// Replace a timeline edited elsewhere (the frontend), unless the file has
+/// been modified since that copy was loaded
+///
+/// The caller's `lastModified` must match the file's; on success the saved
+/// timeline, with its new `lastModified`, is returned.
+pub fn save_if_unmodified(path: &Path, mut timeline: Timeline) -> Result<Timeline, String> {
+    with_lock(path, || {
+        if path.exists() {
+            let current = load_unlocked(path)?;
+            if current.last_modified != timeline.last_modified {
+                warn!("⚠️ Rejected stale timeline save for {:?} (loaded {}, file is {})",
+                      path, timeline.last_modified, current.last_modified);
+                return Err(format!(
+                    "Timeline was modified at {} after it was loaded. Reload it and try again",
+                    current.last_modified
+                ));
+            }
+        }
+
+        timeline.last_modified = chrono::Utc::now().to_rfc3339();
+        write_atomic(path, &timeline)?;
+        Ok(timeline)
+    })
+}
+
+fn load_unlocked(path: &Path) -> Result<Timeline, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read timeline: {}", e))?;
+    let value: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse timeline: {}", e))?;
+
+    let (value, from_version) = migrate(value)?;
+
+    let timeline: Timeline = serde_json::from_value(value)
+        .map_err(|e| format!("Failed to parse timeline: {}", e))?;
+
+    if from_version < SCHEMA_VERSION {
+        let backup_path = path.with_file_name(format!("timeline.v{}.bak.json", from_version));
+        fs::write(&backup_path, &content)
+            .map_err(|e| format!("Failed to back up timeline before migration: {}", e))?;
+        write_atomic(path, &timeline)?;
+        info!("⬆️ Migrated {:?} from schema v{} to v{} (backup: {:?})",
+              path, from_version, SCHEMA_VERSION, backup_path);
+    }
+
+    Ok(timeline)
+}
+
+/// Write to a temp file in the same folder, fsync it, then rename over the
+/// target so readers see either the old or the new file, never a partial one
+fn write_atomic(path: &Path, timeline: &Timeline) -> Result<(), String> {
+    let mut timeline = timeline.clone();
+    timeline.schema_version = SCHEMA_VERSION;
+
+    let json = serde_json::to_string_pretty(&timeline)
+        .map_err(|e| format!("Failed to serialize timeline: {}", e))?;
+
+    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("timeline.json");
+    let temp_path = path.with_file_name(format!(".{}.tmp", filename));
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("Failed to write timeline.json: {}", e));
+    }
+
+    // Persist the rename itself
+    if let Some(parent) = path.parent() {
+        sync_dir(parent);
+    }
+    Ok(())
+}
+
+/// Persist renames in a folder (best effort, and only possible on Unix)
+pub(crate) fn sync_dir(folder: &Path) {
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(folder) {
+        let _ = dir.sync_all();
+    }
+    #[cfg(not(unix))]
+    let _ = folder;
+}
+
+/// Upgrade a parsed timeline.json to the current schema
+///
+/// Returns the upgraded document and the version it started at.
+fn migrate(mut value: Value) -> Result<(Value, u32), String> {
+    let version = match value.get("schemaVersion") {
+        None => 1,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| format!("Invalid schemaVersion: {}", v))?,
+    };
+
+    if version < 1 {
+        return Err(format!("Invalid schemaVersion: {}", version));
+    }
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "timeline.json uses schema v{}, but this version of Pulse only supports up to v{}. Please update the app",
+            version, SCHEMA_VERSION
+        ));
+    }
+    if !value.is_object() {
+        return Err("Failed to parse timeline: expected a JSON object".to_string());
+    }
+
+    for from in version..SCHEMA_VERSION {
+        match from {
+            1 => migrate_v1_to_v2(&mut value),
+            _ => return Err(format!("No timeline migration from schema v{}", from)),
+        }
+    }
+
+    Ok((value, version))
+}
+
+/// v1 -> v2: canonical camelCase keys, explicit entry kind, schemaVersion
+fn migrate_v1_to_v2(value: &mut Value) {
+    fn rename_keys(object: &mut Value, renames: &[(&str, &str)]) {
+        let Some(map) = object.as_object_mut() else { return };
+        for (old, new) in renames {
+            if let Some(v) = map.remove(*old) {
+                // Never clobber a value already written under the new name
+                map.entry(*new).or_insert(v);
+            }
+        }
+    }
+
+    rename_keys(value, &[
+        ("project_name", "projectName"),
+        ("created_at", "createdAt"),
+        ("last_modified", "lastModified"),
+    ]);
+    if let Some(metadata) = value.get_mut("metadata") {
+        rename_keys(metadata, &[
+            ("total_videos", "totalVideos"),
+            ("total_duration", "totalDuration"),
+            ("default_aspect_ratio", "defaultAspectRatio"),
+        ]);
+    }
+    if let Some(entries) = value.get_mut("entries").and_then(Value::as_array_mut) {
+        for entry in entries {
+            rename_keys(entry, &[
+                ("recorded_at", "recordedAt"),
+                ("duration_ms", "durationMs"),
+                ("deleted_at", "deletedAt"),
+                ("aspect_ratio", "aspectRatio"),
+                ("mic_enabled", "micEnabled"),
+            ]);
+            if let Some(map) = entry.as_object_mut() {
+                map.entry("kind").or_insert_with(|| Value::from("video"));
+            }
+        }
+    }
+
+    value["schemaVersion"] = Value::from(2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// An entry as the frontend writes it after labelling and deleting a clip
+    fn frontend_entry() -> Value {
+        json!({
+            "id": "0b6f6c1e-1111-4c1e-9a43-000000000001",
+            "kind": "video",
+            "filename": "recording-1.mp4",
+            "label": "Intro",
+            "recordedAt": "2025-01-01T10:00:00+00:00",
+            "durationMs": 4200,
+            "deleted": true,
+            "deletedAt": "2025-01-02T09:00:00.000Z",
+            "aspectRatio": "16:9",
+            "resolution": { "width": 1920, "height": 1080 },
+            "micEnabled": true,
+            "notes": null,
+            "checksum": "abc123",
+            "thumbnail": "recording-1.thumb.png",
+            "color": "red"
+        })
+    }
+
+    fn frontend_timeline() -> Value {
+        json!({
+            "schemaVersion": SCHEMA_VERSION,
+            "projectName": "Demo",
+            "createdAt": "2025-01-01T09:00:00+00:00",
+            "lastModified": "2025-01-02T09:00:00+00:00",
+            "entries": [frontend_entry()],
+            "metadata": {
+                "totalVideos": 0,
+                "totalDuration": 0,
+                "defaultAspectRatio": "16:9",
+                "tags": ["demo"],
+                "coverEntry": "0b6f6c1e-1111-4c1e-9a43-000000000001"
+            },
+            "order": ["0b6f6c1e-1111-4c1e-9a43-000000000001"]
+        })
+    }
+
+    #[test]
+    fn test_entry_round_trip_keeps_all_fields() {
+        let original = frontend_entry();
+        let entry: TimelineEntry = serde_json::from_value(original.clone()).unwrap();
+
+        assert_eq!(entry.label.as_deref(), Some("Intro"));
+        assert!(entry.is_deleted());
+        assert_eq!(entry.deleted_at.as_deref(), Some("2025-01-02T09:00:00.000Z"));
+        assert_eq!(entry.thumbnail.as_deref(), Some("recording-1.thumb.png"));
+        assert_eq!(entry.extra.get("color"), Some(&json!("red")));
+
+        assert_eq!(serde_json::to_value(&entry).unwrap(), original);
+    }
+
+    #[test]
+    fn test_camel_case_entry_round_trip() {
+        let entry: TimelineEntry = serde_json::from_value(frontend_entry()).unwrap();
+        assert_eq!(entry.aspect_ratio, "16:9");
+        assert!(entry.mic_enabled);
+        assert!(!entry.extra.contains_key("aspectRatio"));
+        assert!(!entry.extra.contains_key("micEnabled"));
+
+        let value = serde_json::to_value(&entry).unwrap();
+        assert_eq!(value["aspectRatio"], json!("16:9"));
+        assert_eq!(value["micEnabled"], json!(true));
+        assert!(value.get("aspect_ratio").is_none());
+        assert!(value.get("mic_enabled").is_none());
+        assert_eq!(serde_json::from_value::<TimelineEntry>(value).unwrap(), entry);
+    }
+
+    /// What the frontend hands back after `JSON.parse` + `JSON.stringify`:
+    /// every number went through an f64
+    fn through_js(value: Value) -> Value {
+        match value {
+            Value::Number(n) => {
+                let f = n.as_f64().unwrap();
+                if f.fract() == 0.0 && f >= 0.0 { json!(f as u64) } else { json!(f) }
+            }
+            Value::Array(items) => Value::Array(items.into_iter().map(through_js).collect()),
+            Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, through_js(v))).collect()),
+            other => other,
+        }
+    }
+
+    #[test]
+    fn test_thumbnail_failed_survives_js_numbers() {
+        let mut entry: TimelineEntry = serde_json::from_value(frontend_entry()).unwrap();
+        entry.thumbnail_failed = Some(Fingerprint {
+            size: 123_456,
+            modified_ns: 1_734_000_000_123_456_789,
+            inode: 18_014_398_509_481_985,
+        });
+
+        let value = through_js(serde_json::to_value(&entry).unwrap());
+        let back: TimelineEntry = serde_json::from_value(value).unwrap();
+        assert_eq!(back.thumbnail_failed, entry.thumbnail_failed);
+    }
+
+    #[test]
+    fn test_file_id_survives_js_numbers() {
+        let mut entry: TimelineEntry = serde_json::from_value(frontend_entry()).unwrap();
+        entry.file_id = Some(18_014_398_509_481_985);
+
+        let value = through_js(serde_json::to_value(&entry).unwrap());
+        assert_eq!(value["fileId"], json!("18014398509481985"));
+        let back: TimelineEntry = serde_json::from_value(value).unwrap();
+        assert_eq!(back.file_id, entry.file_id);
+
+        let mut legacy = frontend_entry();
+        legacy["file_id"] = json!(42);
+        assert_eq!(serde_json::from_value::<TimelineEntry>(legacy).unwrap().file_id, Some(42));
+    }
+
+    #[test]
+    fn test_thumbnail_failed_reads_numbers() {
+        let mut value = frontend_entry();
+        value["thumbnailFailed"] = json!({ "size": 1, "modifiedNs": 2, "inode": 3 });
+        let entry: TimelineEntry = serde_json::from_value(value).unwrap();
+        assert_eq!(entry.thumbnail_failed, Some(Fingerprint { size: 1, modified_ns: 2, inode: 3 }));
+    }
+
+    #[test]
+    fn test_timeline_round_trip_keeps_unknown_fields() {
+        let original = frontend_timeline();
+        let timeline: Timeline = serde_json::from_value(original.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&timeline).unwrap(), original);
+    }
+
+    #[test]
+    fn test_legacy_entry_defaults() {
+        let entry: TimelineEntry = serde_json::from_value(json!({
+            "id": "legacy",
+            "filename": "recording-1.mp4",
+            "recorded_at": "2024-06-01T10:00:00+00:00",
+            "duration_ms": 1000,
+            "aspect_ratio": "none",
+            "resolution": { "width": 1920, "height": 1080 },
+            "mic_enabled": false,
+            "notes": null
+        }))
+        .unwrap();
+
+        assert_eq!(entry.kind, EntryKind::Video);
+        assert_eq!(entry.duration_ms, 1000);
+        assert_eq!(entry.aspect_ratio, "none");
+        assert!(!entry.is_deleted());
+        assert!(entry.extra.is_empty());
+    }
+
+    #[test]
+    fn test_refresh_metadata_skips_deleted_and_images() {
+        let mut timeline: Timeline = serde_json::from_value(frontend_timeline()).unwrap();
+        let mut kept = timeline.entries[0].clone();
+        kept.deleted = None;
+        kept.duration_ms = 1500;
+        let image = TimelineEntry { kind: EntryKind::Image, duration_ms: 0, ..kept.clone() };
+        timeline.entries.extend([kept, image]);
+
+        timeline.refresh_metadata();
+        assert_eq!(timeline.metadata.total_videos, 1);
+        assert_eq!(timeline.metadata.total_duration, 1500);
+    }
+
+    #[test]
+    fn test_migrate_v1_timeline() {
+        let v1 = json!({
+            "project_name": "Old",
+            "created_at": "2024-06-01T09:00:00+00:00",
+            "lastModified": "2024-06-01T10:00:00+00:00",
+            "entries": [{
+                "id": "legacy",
+                "filename": "recording-1.mp4",
+                "recorded_at": "2024-06-01T10:00:00+00:00",
+                "duration_ms": 1000,
+                "aspect_ratio": "none",
+                "resolution": { "width": 1920, "height": 1080 },
+                "mic_enabled": false,
+                "notes": null,
+                "label": "kept"
+            }],
+            "metadata": { "total_videos": 1, "total_duration": 1000, "default_aspect_ratio": null, "tags": null }
+        });
+
+        let (migrated, from) = migrate(v1).unwrap();
+        assert_eq!(from, 1);
+        assert_eq!(migrated["schemaVersion"], json!(SCHEMA_VERSION));
+        assert_eq!(migrated["projectName"], json!("Old"));
+        assert_eq!(migrated["entries"][0]["recordedAt"], json!("2024-06-01T10:00:00+00:00"));
+        assert_eq!(migrated["entries"][0]["kind"], json!("video"));
+        assert_eq!(migrated["entries"][0]["aspectRatio"], json!("none"));
+        assert_eq!(migrated["entries"][0]["micEnabled"], json!(false));
+        assert_eq!(migrated["metadata"]["totalVideos"], json!(1));
+
+        let timeline: Timeline = serde_json::from_value(migrated).unwrap();
+        assert_eq!(timeline.entries[0].label.as_deref(), Some("kept"));
+        assert!(timeline.extra.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_current_is_noop() {
+        let (migrated, from) = migrate(frontend_timeline()).unwrap();
+        assert_eq!(from, SCHEMA_VERSION);
+        assert_eq!(migrated, frontend_timeline());
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_schema() {
+        let mut newer = frontend_timeline();
+        newer["schemaVersion"] = json!(SCHEMA_VERSION + 1);
+        let err = migrate(newer).unwrap_err();
+        assert!(err.contains("only supports up to"), "{}", err);
+
+        assert!(migrate(json!({ "schemaVersion": "two" })).is_err());
+    }
+
+    #[test]
+    fn test_migrate_rejects_schema_zero() {
+        let mut zero = frontend_timeline();
+        zero["schemaVersion"] = json!(0);
+        let err = migrate(zero).unwrap_err();
+        assert!(err.contains("Invalid schemaVersion"), "{}", err);
+    }
+
+    fn entry(id: &str) -> TimelineEntry {
+        TimelineEntry {
+            id: id.to_string(),
+            filename: format!("{}.mp4", id),
+            duration_ms: 1000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_load_migrates_and_backs_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("timeline.json");
+        let v1 = json!({
+            "project_name": "Old",
+            "created_at": "2024-06-01T09:00:00+00:00",
+            "last_modified": "2024-06-01T10:00:00+00:00",
+            "entries": [],
+            "metadata": { "total_videos": 0, "total_duration": 0, "default_aspect_ratio": null, "tags": null }
+        });
+        fs::write(&path, v1.to_string()).unwrap();
+
+        let timeline = load(&path).unwrap();
+        assert_eq!(timeline.project_name, "Old");
+
+        let on_disk: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(on_disk["schemaVersion"], json!(SCHEMA_VERSION));
+        let backup: Value = serde_json::from_str(&fs::read_to_string(dir.path().join("timeline.v1.bak.json")).unwrap()).unwrap();
+        assert_eq!(backup, v1);
+        assert!(!dir.path().join(".timeline.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_update_only_writes_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("timeline.json");
+
+        update(&path, "Demo", |t| {
+            t.entries.push(entry("a"));
+            Ok(())
+        })
+        .unwrap();
+        let first = load(&path).unwrap();
+        assert_eq!(first.entries.len(), 1);
+
+        update(&path, "Demo", |_| Ok(())).unwrap();
+        assert_eq!(load(&path).unwrap().last_modified, first.last_modified);
+    }
+
+    #[test]
+    fn test_concurrent_updates_keep_every_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("timeline.json");
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    update(&path, "Demo", |t| {
+                        t.entries.push(entry(&format!("clip-{}", i)));
+                        Ok(())
+                    })
+                    .unwrap()
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(load(&path).unwrap().entries.len(), 8);
+    }
+
+    #[test]
+    fn test_save_if_unmodified_rejects_stale_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("timeline.json");
+        save(&path, &Timeline::new("Demo")).unwrap();
+
+        // The UI loads the timeline, then a recording is added behind its back
+        let mut stale = load(&path).unwrap();
+        update(&path, "Demo", |t| {
+            t.entries.push(entry("recorded"));
+            Ok(())
+        })
+        .unwrap();
+
+        stale.entries.push(entry("from-ui"));
+        assert!(save_if_unmodified(&path, stale).is_err());
+        assert_eq!(load(&path).unwrap().entries[0].id, "recorded");
+
+        // A fresh copy saves, and can be saved again with the returned timestamp
+        let mut fresh = load(&path).unwrap();
+        fresh.entries[0].label = Some("Renamed".to_string());
+        let saved = save_if_unmodified(&path, fresh).unwrap();
+        assert!(save_if_unmodified(&path, saved).is_ok());
+    }
+
+    #[test]
+    fn test_media_file_classification() {
+        assert_eq!(EntryKind::from_path(Path::new("recording-1.mp4")), Some(EntryKind::Video));
+        assert_eq!(EntryKind::from_path(Path::new("screenshot-1.PNG")), Some(EntryKind::Image));
+        assert_eq!(EntryKind::from_path(Path::new("recording-1.thumb.png")), None);
+        assert_eq!(EntryKind::from_path(Path::new("timeline.json")), None);
+    }
+}
@@ -0,0 +1,491 @@
+// Animated GIF / WebP export of recorded clips
+//
+// Frames are decoded from the finished MP4 (see `FrameExtractor`), scaled
+// down and re-encoded. Screen recordings are mostly static, so both encoders
+// only store the rectangle that changed since the previous frame, and
+// identical consecutive frames are merged into one longer frame.
+
+use crate::{FrameExtractor, RgbaImage};
+use color_quant::NeuQuant;
+use log::{debug, info};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Most pixels (width x height x frames) we are willing to hold in memory
+/// while exporting: 100M pixels is ~400 MB of RGBA
+const MAX_TOTAL_PIXELS: u64 = 100_000_000;
+
+/// Maximum attempts at shrinking an animation to fit its size budget
+const MAX_BUDGET_ATTEMPTS: usize = 8;
+
+/// Never shrink below this width while trying to fit the size budget;
+/// drop frames instead
+const MIN_BUDGET_WIDTH: u32 = 240;
+
+/// Never reduce the GIF palette below this many colors
+const MIN_GIF_COLORS: u16 = 32;
+
+/// Pixels fed to the color quantizer when building the GIF palette
+const PALETTE_SAMPLE_PIXELS: usize = 256 * 1024;
+
+/// Output format for animated exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationFormat {
+    Gif,
+    WebP,
+}
+
+impl AnimationFormat {
+    /// File extension (without the dot)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "gif",
+            AnimationFormat::WebP => "webp",
+        }
+    }
+}
+
+/// Options for exporting a clip as an animation
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AnimationOptions {
+    /// Output format (default: GIF)
+    pub format: AnimationFormat,
+
+    /// Trim start in seconds (default: 0)
+    pub start_secs: f64,
+
+    /// Trim end in seconds (None = end of clip)
+    pub end_secs: Option<f64>,
+
+    /// Frames per second (1-50, default: 10)
+    pub fps: u32,
+
+    /// Maximum output width; height follows the aspect ratio (default: 640)
+    pub max_width: u32,
+
+    /// Palette size for GIF output (8-256, default: 256)
+    pub max_colors: u16,
+
+    /// Target file size in bytes. The export is retried at lower
+    /// resolution, frame rate and color count until it fits (None = no limit)
+    pub max_bytes: Option<u64>,
+
+    /// Loop forever instead of playing once (default: true)
+    pub loop_forever: bool,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            format: AnimationFormat::Gif,
+            start_secs: 0.0,
+            end_secs: None,
+            fps: 10,
+            max_width: 640,
+            max_colors: 256,
+            max_bytes: None,
+            loop_forever: true,
+        }
+    }
+}
+
+/// Result of a successful animation export
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AnimationExport {
+    pub path: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pub frame_count: usize,
+    pub fps: f64,
+    pub bytes: u64,
+}
+
+/// Export a section of a video file as an animated GIF or WebP
+pub fn export_animation(
+    input: &Path,
+    output: &Path,
+    options: &AnimationOptions,
+) -> Result<AnimationExport, String> {
+    let fps = options.fps.clamp(1, 50);
+    let max_width = options.max_width.max(1);
+
+    let mut extractor = FrameExtractor::open(input, Some((max_width, 16384)))?;
+    let duration = extractor.duration();
+
+    let start = options.start_secs.max(0.0);
+    let end = options.end_secs.unwrap_or(duration).min(duration);
+    if end <= start {
+        return Err(format!(
+            "Invalid trim range: {:.2}s-{:.2}s (clip is {:.2}s long)",
+            start, end, duration
+        ));
+    }
+
+    let frame_count = (((end - start) * fps as f64).ceil() as usize).max(1);
+    let frame_at = |extractor: &mut FrameExtractor, i: usize| -> Result<RgbaImage, String> {
+        let frame = extractor.frame_at(start + i as f64 / fps as f64)?;
+        Ok(if frame.width > max_width { frame.fit_width(max_width) } else { frame })
+    };
+
+    // Every frame is held decoded, so the limit is on pixels, not frames
+    let first = frame_at(&mut extractor, 0)?;
+    let total_pixels = first.width as u64 * first.height as u64 * frame_count as u64;
+    if total_pixels > MAX_TOTAL_PIXELS {
+        return Err(format!(
+            "Animation too large: {} frames at {}x{} (max {} pixels in total). Trim the clip or lower the frame rate or size",
+            frame_count, first.width, first.height, MAX_TOTAL_PIXELS
+        ));
+    }
+
+    info!("🎞️ Extracting {} frames from {} ({:.2}s-{:.2}s @ {}fps)",
+          frame_count, input.display(), start, end, fps);
+
+    let mut frames = Vec::with_capacity(frame_count);
+    frames.push(first);
+    for i in 1..frame_count {
+        frames.push(frame_at(&mut extractor, i)?);
+    }
+
+    let frame_ms = 1000.0 / fps as f64;
+    let source_width = frames[0].width;
+    let mut width = source_width;
+    let mut step = 1;
+    let mut colors = options.max_colors;
+
+    for attempt in 1..=MAX_BUDGET_ATTEMPTS {
+        // The first attempt encodes the decoded frames as they are
+        let attempt_frames: Cow<[RgbaImage]> = if width == source_width && step == 1 {
+            Cow::Borrowed(&frames[..])
+        } else {
+            frames.iter().step_by(step).map(|frame| frame.fit_width(width)).collect()
+        };
+        let attempt_options = AnimationOptions { max_colors: colors, ..options.clone() };
+        let bytes = encode_animation(&attempt_frames, frame_ms * step as f64, &attempt_options)?;
+
+        let budget = match options.max_bytes {
+            Some(budget) if bytes.len() as u64 > budget => budget,
+            _ => {
+                std::fs::write(output, &bytes)
+                    .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+                info!("✅ Exported {} ({} bytes, {} frames)", output.display(), bytes.len(), attempt_frames.len());
+                return Ok(AnimationExport {
+                    path: output.to_path_buf(),
+                    width: attempt_frames[0].width,
+                    height: attempt_frames[0].height,
+                    frame_count: attempt_frames.len(),
+                    fps: fps as f64 / step as f64,
+                    bytes: bytes.len() as u64,
+                });
+            }
+        };
+
+        debug!("📉 Attempt {}: {} bytes exceeds budget of {} bytes at {}px, every {} frame(s), {} colors",
+               attempt, bytes.len(), budget, width, step, colors);
+
+        // Pixel count scales roughly with file size, so shrink both sides by
+        // the square root of the overshoot (bounded so we converge steadily)
+        let factor = ((budget as f64 / bytes.len() as f64).sqrt() * 0.95).clamp(0.5, 0.9);
+        let next_width = (width as f64 * factor) as u32;
+        if next_width < MIN_BUDGET_WIDTH.min(source_width) {
+            step += 1;
+        } else {
+            width = next_width;
+        }
+        if options.format == AnimationFormat::Gif {
+            colors = (colors / 2).max(MIN_GIF_COLORS);
+        }
+    }
+
+    Err(format!(
+        "Could not fit animation within {} bytes. Try a shorter section or a smaller size",
+        options.max_bytes.unwrap_or_default()
+    ))
+}
+
+/// Encode frames as an animation
+///
+/// All frames must have the same dimensions. `frame_ms` is the display time
+/// of each frame in milliseconds.
+pub fn encode_animation(
+    frames: &[RgbaImage],
+    frame_ms: f64,
+    options: &AnimationOptions,
+) -> Result<Vec<u8>, String> {
+    let first = frames.first().ok_or("No frames to encode")?;
+    if frames.iter().any(|f| f.width != first.width || f.height != first.height) {
+        return Err("All animation frames must have the same size".to_string());
+    }
+
+    match options.format {
+        AnimationFormat::Gif => encode_gif(frames, frame_ms, options.max_colors, options.loop_forever),
+        AnimationFormat::WebP => encode_webp(frames, frame_ms, options.loop_forever),
+    }
+}
+
+/// Changed area of a frame relative to the previous one
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+/// Bounding box of pixels for which `same(index)` is false
+fn changed_bounds(width: u32, height: u32, same: impl Fn(usize) -> bool) -> Option<Rect> {
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (u32::MAX, u32::MAX, 0, 0);
+    for y in 0..height {
+        for x in 0..width {
+            if !same((y * width + x) as usize) {
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+    }
+
+    (min_x != u32::MAX).then(|| Rect {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x + 1,
+        height: max_y - min_y + 1,
+    })
+}
+
+/// Display time of frame `index..index + frames` in `unit_ms` units, computed
+/// from absolute timestamps so rounding doesn't drift over long animations
+fn frame_delay(index: usize, frames: usize, frame_ms: f64, unit_ms: f64) -> u32 {
+    let start = (index as f64 * frame_ms / unit_ms).round() as u32;
+    let end = ((index + frames) as f64 * frame_ms / unit_ms).round() as u32;
+    (end - start).max(1)
+}
+
+fn encode_gif(frames: &[RgbaImage], frame_ms: f64, max_colors: u16, loop_forever: bool) -> Result<Vec<u8>, String> {
+    let width = u16::try_from(frames[0].width).map_err(|_| "Animation too wide for GIF")?;
+    let height = u16::try_from(frames[0].height).map_err(|_| "Animation too tall for GIF")?;
+
+    // One global palette for the whole animation; the last entry is reserved
+    // for "unchanged" (transparent) pixels
+    let colors = max_colors.clamp(8, 256) as usize;
+    let quantizer = NeuQuant::new(10, colors - 1, &palette_sample(frames));
+    let mut palette = quantizer.color_map_rgb();
+    let transparent = (palette.len() / 3) as u8;
+    palette.extend_from_slice(&[0, 0, 0]);
+
+    let mut output = Vec::new();
+    {
+        let mut encoder = gif::Encoder::new(&mut output, width, height, &palette)
+            .map_err(|e| format!("Failed to start GIF: {}", e))?;
+        if loop_forever {
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(|e| format!("Failed to write GIF loop extension: {}", e))?;
+        }
+
+        // Screen content has few distinct colors, so cache palette lookups
+        let mut lookup: HashMap<[u8; 3], u8> = HashMap::new();
+        let mut previous: Option<Vec<u8>> = None;
+        // Frame waiting to be written, with its first index and frame count
+        let mut pending: Option<(gif::Frame<'static>, usize, usize)> = None;
+
+        for (index, frame) in frames.iter().enumerate() {
+            let indices: Vec<u8> = frame
+                .data
+                .chunks_exact(4)
+                .map(|px| {
+                    let rgb = [px[0], px[1], px[2]];
+                    *lookup
+                        .entry(rgb)
+                        .or_insert_with(|| quantizer.index_of(&[px[0], px[1], px[2], 255]) as u8)
+                })
+                .collect();
+
+            let rect = match &previous {
+                None => Some(Rect { x: 0, y: 0, width: frames[0].width, height: frames[0].height }),
+                Some(prev) => changed_bounds(frame.width, frame.height, |i| prev[i] == indices[i]),
+            };
+
+            let Some(rect) = rect else {
+                // Identical to the previous frame: show that one longer
+                if let Some((_, _, count)) = pending.as_mut() {
+                    *count += 1;
+                }
+                continue;
+            };
+
+            let mut buffer = Vec::with_capacity((rect.width * rect.height) as usize);
+            for y in rect.y..rect.y + rect.height {
+                for x in rect.x..rect.x + rect.width {
+                    let i = (y * frame.width + x) as usize;
+                    let unchanged = previous.as_ref().is_some_and(|prev| prev[i] == indices[i]);
+                    buffer.push(if unchanged { transparent } else { indices[i] });
+                }
+            }
+
+            let gif_frame = gif::Frame {
+                left: rect.x as u16,
+                top: rect.y as u16,
+                width: rect.width as u16,
+                height: rect.height as u16,
+                transparent: Some(transparent),
+                dispose: gif::DisposalMethod::Keep,
+                buffer: buffer.into(),
+                ..Default::default()
+            };
+
+            if let Some(done) = pending.replace((gif_frame, index, 1)) {
+                write_gif_frame(&mut encoder, done, frame_ms)?;
+            }
+            previous = Some(indices);
+        }
+
+        if let Some(done) = pending {
+            write_gif_frame(&mut encoder, done, frame_ms)?;
+        }
+    }
+
+    Ok(output)
+}
+
+fn write_gif_frame<W: std::io::Write>(
+    encoder: &mut gif::Encoder<W>,
+    (mut frame, index, count): (gif::Frame<'static>, usize, usize),
+    frame_ms: f64,
+) -> Result<(), String> {
+    // GIF delays are in hundredths of a second
+    frame.delay = frame_delay(index, count, frame_ms, 10.0).min(u16::MAX as u32) as u16;
+    encoder
+        .write_frame(&frame)
+        .map_err(|e| format!("Failed to write GIF frame: {}", e))
+}
+
+/// Evenly spaced opaque pixels from all frames, for palette training
+fn palette_sample(frames: &[RgbaImage]) -> Vec<u8> {
+    let total: usize = frames.iter().map(|f| f.data.len() / 4).sum();
+    let stride = (total / PALETTE_SAMPLE_PIXELS).max(1);
+
+    let mut sample = Vec::with_capacity((total / stride + 1) * 4);
+    for px in frames.iter().flat_map(|f| f.data.chunks_exact(4)).step_by(stride) {
+        sample.extend_from_slice(&[px[0], px[1], px[2], 255]);
+    }
+    sample
+}
+
+fn encode_webp(frames: &[RgbaImage], frame_ms: f64, loop_forever: bool) -> Result<Vec<u8>, String> {
+    let (width, height) = (frames[0].width, frames[0].height);
+    if width > 16383 || height > 16383 {
+        return Err("Animation too large for WebP".to_string());
+    }
+
+    let mut previous: Option<&RgbaImage> = None;
+    let mut pending: Option<(Rect, Vec<u8>, usize, usize)> = None;
+    let mut anmf_chunks = Vec::new();
+
+    for (index, frame) in frames.iter().enumerate() {
+        let rect = match previous {
+            None => Some(Rect { x: 0, y: 0, width, height }),
+            Some(prev) => changed_bounds(width, height, |i| {
+                prev.data[i * 4..i * 4 + 4] == frame.data[i * 4..i * 4 + 4]
+            }),
+        };
+
+        let Some(mut rect) = rect else {
+            if let Some((_, _, _, count)) = pending.as_mut() {
+                *count += 1;
+            }
+            continue;
+        };
+
+        // Frame offsets are stored divided by two
+        if rect.x % 2 == 1 {
+            rect.x -= 1;
+            rect.width += 1;
+        }
+        if rect.y % 2 == 1 {
+            rect.y -= 1;
+            rect.height += 1;
+        }
+
+        // Unchanged pixels become fully transparent and are alpha-blended
+        // over the previous canvas
+        let mut data = Vec::with_capacity((rect.width * rect.height * 4) as usize);
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                let i = ((y * width + x) * 4) as usize;
+                let px = &frame.data[i..i + 4];
+                match previous {
+                    Some(prev) if prev.data[i..i + 4] == *px => data.extend_from_slice(&[0, 0, 0, 0]),
+                    _ => data.extend_from_slice(px),
+                }
+            }
+        }
+
+        if let Some((rect, data, start, count)) = pending.replace((rect, data, index, 1)) {
+            anmf_chunks.push(webp_anmf(rect, &data, frame_delay(start, count, frame_ms, 1.0), start > 0)?);
+        }
+        previous = Some(frame);
+    }
+
+    if let Some((rect, data, start, count)) = pending {
+        anmf_chunks.push(webp_anmf(rect, &data, frame_delay(start, count, frame_ms, 1.0), start > 0)?);
+    }
+
+    let mut vp8x = Vec::with_capacity(10);
+    vp8x.push((1 << 1) | (1 << 4)); // animation + alpha
+    vp8x.extend_from_slice(&[0; 3]);
+    vp8x.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+    vp8x.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+
+    let mut anim = Vec::with_capacity(6);
+    anim.extend_from_slice(&[255, 255, 255, 255]); // background (BGRA)
+    anim.extend_from_slice(&(if loop_forever { 0u16 } else { 1u16 }).to_le_bytes());
+
+    let mut body = b"WEBP".to_vec();
+    write_riff_chunk(&mut body, b"VP8X", &vp8x);
+    write_riff_chunk(&mut body, b"ANIM", &anim);
+    for chunk in &anmf_chunks {
+        write_riff_chunk(&mut body, b"ANMF", chunk);
+    }
+
+    let mut output = Vec::with_capacity(body.len() + 8);
+    output.extend_from_slice(b"RIFF");
+    output.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    output.extend_from_slice(&body);
+    Ok(output)
+}
+
+/// Build the payload of an ANMF (animation frame) chunk
+///
+/// `blend` alpha-blends the frame over the canvas; the first frame replaces
+/// the background outright.
+fn webp_anmf(rect: Rect, data: &[u8], duration_ms: u32, blend: bool) -> Result<Vec<u8>, String> {
+    // Encode the frame as a still lossless WebP and lift out its VP8L chunk
+    let mut still = Vec::new();
+    image_webp::WebPEncoder::new(&mut still)
+        .encode(data, rect.width, rect.height, image_webp::ColorType::Rgba8)
+        .map_err(|e| format!("Failed to encode WebP frame: {}", e))?;
+    let bitstream = still.get(12..).ok_or("Unexpected WebP encoder output")?;
+
+    let mut payload = Vec::with_capacity(16 + bitstream.len());
+    payload.extend_from_slice(&(rect.x / 2).to_le_bytes()[..3]);
+    payload.extend_from_slice(&(rect.y / 2).to_le_bytes()[..3]);
+    payload.extend_from_slice(&(rect.width - 1).to_le_bytes()[..3]);
+    payload.extend_from_slice(&(rect.height - 1).to_le_bytes()[..3]);
+    payload.extend_from_slice(&duration_ms.min(0xFF_FFFF).to_le_bytes()[..3]);
+    payload.push(if blend { 0 } else { 1 << 1 }); // blending method, no disposal
+    payload.extend_from_slice(bitstream);
+    Ok(payload)
+}
+
+fn write_riff_chunk(output: &mut Vec<u8>, fourcc: &[u8; 4], payload: &[u8]) {
+    output.extend_from_slice(fourcc);
+    output.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    output.extend_from_slice(payload);
+    if payload.len() % 2 == 1 {
+        output.push(0);
+    }
+}
@@ -0,0 +1,111 @@
+// Async-native recorder API
+//
+// The native recorders block: creating one initializes ScreenCaptureKit
+// (2-3 seconds) and start/stop wait on completion handlers. `AsyncRecorder`
+// owns a `Recorder` on a dedicated worker thread and hands results back over
+// oneshot channels, so its futures are `Send` and never block the executor
+// they are polled on. No particular async runtime is required.
+
+use crate::{Recorder, RecordingConfig, RecordingState};
+use futures_channel::oneshot;
+use log::debug;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+/// Requests sent from `AsyncRecorder` to its worker thread
+enum Command {
+    Start(oneshot::Sender<Result<(), String>>),
+    Stop(oneshot::Sender<Result<(PathBuf, f64), String>>),
+    State(oneshot::Sender<RecordingState>),
+    Duration(oneshot::Sender<f64>),
+}
+
+/// Screen recorder with an async API
+///
+/// All blocking work (initialization, start, stop/finalize) runs on a worker
+/// thread owned by this recorder. Dropping the `AsyncRecorder` shuts the worker
+/// down and releases the native recorder on that thread.
+pub struct AsyncRecorder {
+    commands: mpsc::Sender<Command>,
+}
+
+impl AsyncRecorder {
+    /// Create a recorder, running the slow native initialization off the executor
+    pub async fn new(config: RecordingConfig) -> Result<Self, String> {
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (commands, command_rx) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("screen-capture-recorder".to_string())
+            .spawn(move || {
+                let recorder = match Recorder::new(config) {
+                    Ok(recorder) => {
+                        let _ = ready_tx.send(Ok(()));
+                        recorder
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+                run_worker(recorder, command_rx);
+            })
+            .map_err(|e| format!("Failed to spawn recorder thread: {}", e))?;
+
+        ready_rx
+            .await
+            .map_err(|_| "Recorder thread exited during initialization".to_string())??;
+
+        Ok(Self { commands })
+    }
+
+    /// Start recording
+    pub async fn start(&mut self) -> Result<(), String> {
+        self.request(Command::Start).await?
+    }
+
+    /// Stop recording and finalize the video file
+    pub async fn stop(&mut self) -> Result<(PathBuf, f64), String> {
+        self.request(Command::Stop).await?
+    }
+
+    /// Get current recording state
+    pub async fn state(&self) -> Result<RecordingState, String> {
+        self.request(Command::State).await
+    }
+
+    /// Get recording duration in seconds
+    pub async fn duration(&self) -> Result<f64, String> {
+        self.request(Command::Duration).await
+    }
+
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> Command) -> Result<T, String> {
+        let (tx, rx) = oneshot::channel();
+        self.commands
+            .send(command(tx))
+            .map_err(|_| "Recorder thread has exited".to_string())?;
+        rx.await.map_err(|_| "Recorder thread has exited".to_string())
+    }
+}
+
+/// Serve requests until the owning `AsyncRecorder` is dropped
+fn run_worker(mut recorder: Recorder, commands: mpsc::Receiver<Command>) {
+    while let Ok(command) = commands.recv() {
+        match command {
+            Command::Start(reply) => {
+                let _ = reply.send(recorder.start());
+            }
+            Command::Stop(reply) => {
+                let _ = reply.send(recorder.stop());
+            }
+            Command::State(reply) => {
+                let _ = reply.send(recorder.state());
+            }
+            Command::Duration(reply) => {
+                let _ = reply.send(recorder.duration());
+            }
+        }
+    }
+    debug!("🧵 Recorder worker thread exiting");
+}
@@ -0,0 +1,68 @@
+// Frame extraction from recorded videos (export, thumbnails)
+
+use crate::RgbaImage;
+use std::path::Path;
+
+#[cfg(target_os = "macos")]
+use crate::macos::bridge;
+
+/// Decodes individual frames from an existing video file
+pub struct FrameExtractor {
+    #[cfg(target_os = "macos")]
+    native: bridge::FrameExtractor,
+    duration: f64,
+}
+
+impl FrameExtractor {
+    /// Open a video file
+    ///
+    /// Frames are scaled down to fit within `max_size` (width, height) when
+    /// given, which is much cheaper than decoding at full size and resizing.
+    pub fn open(path: &Path, max_size: Option<(u32, u32)>) -> Result<Self, String> {
+        if !path.exists() {
+            return Err(format!("Video not found: {}", path.display()));
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let (max_width, max_height) = max_size.unwrap_or((0, 0));
+            let native = bridge::FrameExtractor::new(&path.to_string_lossy(), max_width, max_height)?;
+            let duration = native.duration();
+            Ok(Self { native, duration })
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            // TODO: Implement for Windows
+            let _ = max_size;
+            Err("Frame extraction not yet implemented for Windows".to_string())
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            let _ = max_size;
+            Err("Frame extraction not supported on this platform".to_string())
+        }
+    }
+
+    /// Video duration in seconds
+    pub fn duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Decode the frame shown at `seconds` (clamped to the video's duration)
+    pub fn frame_at(&mut self, seconds: f64) -> Result<RgbaImage, String> {
+        let seconds = seconds.clamp(0.0, self.duration.max(0.0));
+
+        #[cfg(target_os = "macos")]
+        {
+            self.native.frame_at(seconds)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = seconds;
+            Err("Frame extraction not supported on this platform".to_string())
+        }
+    }
+}
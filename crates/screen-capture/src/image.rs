@@ -0,0 +1,106 @@
+// In-memory RGBA images (screenshots and decoded frames)
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// An 8-bit RGBA image, 4 bytes per pixel, rows tightly packed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Create an image from raw RGBA bytes
+    pub fn new(width: u32, height: u32, data: Vec<u8>) -> Result<Self, String> {
+        let expected = width as usize * height as usize * 4;
+        if data.len() != expected {
+            return Err(format!(
+                "Invalid RGBA buffer: expected {} bytes for {}x{}, got {}",
+                expected, width, height, data.len()
+            ));
+        }
+        Ok(Self { width, height, data })
+    }
+
+    /// Scale the image to the given size, averaging source pixels
+    ///
+    /// Intended for downscaling (thumbnails, animation frames); upscaling
+    /// falls back to nearest-neighbour.
+    pub fn resized(&self, width: u32, height: u32) -> RgbaImage {
+        let width = width.max(1);
+        let height = height.max(1);
+        if width == self.width && height == self.height {
+            return self.clone();
+        }
+
+        let src_w = self.width as usize;
+        let src_h = self.height as usize;
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+
+        for y in 0..height as usize {
+            let y0 = y * src_h / height as usize;
+            let y1 = ((y + 1) * src_h / height as usize).max(y0 + 1).min(src_h);
+            for x in 0..width as usize {
+                let x0 = x * src_w / width as usize;
+                let x1 = ((x + 1) * src_w / width as usize).max(x0 + 1).min(src_w);
+
+                let mut sum = [0u32; 4];
+                for sy in y0..y1 {
+                    let row = &self.data[(sy * src_w + x0) * 4..(sy * src_w + x1) * 4];
+                    for px in row.chunks_exact(4) {
+                        for (acc, &channel) in sum.iter_mut().zip(px) {
+                            *acc += channel as u32;
+                        }
+                    }
+                }
+
+                let count = ((y1 - y0) * (x1 - x0)) as u32;
+                data.extend(sum.iter().map(|&acc| ((acc + count / 2) / count) as u8));
+            }
+        }
+
+        RgbaImage { width, height, data }
+    }
+
+    /// Scale down to fit within `max_width`, keeping the aspect ratio
+    pub fn fit_width(&self, max_width: u32) -> RgbaImage {
+        if self.width <= max_width {
+            return self.clone();
+        }
+        let height = (self.height as u64 * max_width as u64 / self.width as u64).max(1) as u32;
+        self.resized(max_width, height)
+    }
+
+    /// Encode the image as PNG
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        self.write_png(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Save the image as a PNG file
+    pub fn save_png(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        self.write_png(BufWriter::new(file))
+    }
+
+    fn write_png<W: std::io::Write>(&self, writer: W) -> Result<(), String> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("Failed to write PNG header: {}", e))?;
+        writer
+            .write_image_data(&self.data)
+            .map_err(|e| format!("Failed to write PNG data: {}", e))?;
+        writer
+            .finish()
+            .map_err(|e| format!("Failed to finish PNG: {}", e))
+    }
+}
@@ -11,6 +11,8 @@
 //! - Hardware-accelerated encoding
 //! - Proper Retina/HiDPI handling
 //! - Low memory footprint (streaming)
+//! - Optional variable frame rate output for mostly-static screens
+//! - Animated GIF / WebP export of recorded clips
 //! 
 //! ## Example
 //! ```no_run
@@ -28,9 +30,27 @@
 //! // ... record for some time ...
 //! recorder.stop()?;
 //! ```
+//!
+//! ## Async
+//! [`AsyncRecorder`] runs the blocking native calls on a dedicated thread, so
+//! its futures are `Send` and safe to await on any executor:
+//! ```no_run
+//! # async fn example() -> Result<(), String> {
+//! use screen_capture::{AsyncRecorder, RecordingConfig};
+//!
+//! let mut recorder = AsyncRecorder::new(RecordingConfig::default()).await?;
+//! recorder.start().await?;
+//! let (path, duration) = recorder.stop().await?;
+//! # Ok(())
+//! # }
+//! ```
 
 use std::path::PathBuf;
 
+mod animation;
+mod async_recorder;
+mod frames;
+mod image;
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(target_os = "windows")]
@@ -41,6 +61,14 @@ use macos::NativeRecorder;
 #[cfg(target_os = "windows")]
 use windows::NativeRecorder;
 
+pub use animation::{encode_animation, export_animation, AnimationExport, AnimationFormat, AnimationOptions};
+pub use async_recorder::AsyncRecorder;
+pub use frames::FrameExtractor;
+pub use image::RgbaImage;
+
+/// Default maximum seconds between keyframes
+pub const DEFAULT_KEYFRAME_INTERVAL_SECS: u32 = 2;
+
 /// Configuration for screen recording
 #[derive(Debug, Clone)]
 pub struct RecordingConfig {
@@ -67,6 +95,19 @@ pub struct RecordingConfig {
     
     /// Microphone device ID (None = default microphone)
     pub microphone_device_id: Option<String>,
+    
+    /// Skip frames that are unchanged from the previous one, producing
+    /// variable-frame-rate output (default: false)
+    pub variable_frame_rate: bool,
+    
+    /// Maximum seconds between keyframes (default: 2). With variable frame
+    /// rate enabled, static stretches repeat the last frame at this cadence
+    /// so seeking stays accurate.
+    pub keyframe_interval_secs: u32,
+    
+    /// Text for the file's comment tag (default: None). Pulse stores the
+    /// clip id here so a recording can be recognized after it's renamed.
+    pub comment: Option<String>,
 }
 
 impl Default for RecordingConfig {
@@ -80,6 +121,9 @@ impl Default for RecordingConfig {
             region: None,
             capture_microphone: false,
             microphone_device_id: None,
+            variable_frame_rate: false,
+            keyframe_interval_secs: DEFAULT_KEYFRAME_INTERVAL_SECS,
+            comment: None,
         }
     }
 }
@@ -136,7 +180,36 @@ pub fn get_audio_devices() -> Result<Vec<AudioDevice>, String> {
     }
 }
 
+/// Capture a single frame of a display as an RGBA image
+///
+/// Uses the same display selection and region semantics as [`RecordingConfig`]:
+/// `display_id` of `None` means the primary display and `region` of `None`
+/// means the full display. Save the result with [`RgbaImage::save_png`].
+pub fn capture_screenshot(display_id: Option<u32>, region: Option<CaptureRegion>) -> Result<RgbaImage, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let region = region.map(|r| (r.x, r.y, r.width, r.height));
+        macos::bridge::capture_screenshot(display_id.unwrap_or(0), region)
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        // TODO: Implement for Windows
+        let _ = (display_id, region);
+        Err("Screenshot capture not yet implemented for Windows".to_string())
+    }
+    
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (display_id, region);
+        Err("Screenshot capture not supported on this platform".to_string())
+    }
+}
+
 /// Cross-platform screen recorder
+///
+/// `Recorder` is `Send`, so it can be moved between threads, but its methods
+/// block. Use [`AsyncRecorder`] from async code.
 pub struct Recorder {
     native: NativeRecorder,
     // Stored for potential future use (e.g., querying config after creation)
@@ -201,4 +274,120 @@ mod tests {
         assert_eq!(config.quality, 80);
         assert!(config.capture_cursor);
     }
+
+    #[test]
+    fn test_recorders_are_send() {
+        fn assert_send<T: Send>() {}
+        fn assert_send_future<F: std::future::Future + Send>(_future: F) {}
+
+        assert_send::<Recorder>();
+        assert_send::<AsyncRecorder>();
+
+        // Futures are only constructed here, never polled
+        assert_send_future(AsyncRecorder::new(RecordingConfig::default()));
+    }
+
+    #[test]
+    fn test_rgba_image_png_roundtrip() {
+        let image = RgbaImage::new(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 128]).unwrap();
+        let png = image.to_png().unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(png));
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(&buf[..info.buffer_size()], image.data.as_slice());
+    }
+
+    #[test]
+    fn test_rgba_image_rejects_wrong_size() {
+        assert!(RgbaImage::new(2, 2, vec![0; 4]).is_err());
+    }
+
+    fn solid_frame(width: u32, height: u32, rgba: [u8; 4]) -> RgbaImage {
+        RgbaImage::new(width, height, rgba.repeat((width * height) as usize)).unwrap()
+    }
+
+    /// Three frames where the second repeats the first and the third
+    /// changes a small area
+    fn sample_frames() -> Vec<RgbaImage> {
+        let first = solid_frame(16, 8, [255, 255, 255, 255]);
+        let mut third = first.clone();
+        for y in 2..4 {
+            for x in 5..9 {
+                let i = ((y * 16 + x) * 4) as usize;
+                third.data[i..i + 4].copy_from_slice(&[0, 0, 0, 255]);
+            }
+        }
+        vec![first.clone(), first, third]
+    }
+
+    #[test]
+    fn test_rgba_image_resized() {
+        let mut image = solid_frame(4, 2, [0, 0, 0, 255]);
+        image.data[..4].copy_from_slice(&[255, 255, 255, 255]);
+
+        let half = image.resized(2, 1);
+        assert_eq!((half.width, half.height), (2, 1));
+        // Top-left 2x2 block averages one white pixel with three black ones
+        assert_eq!(&half.data[..4], &[64, 64, 64, 255]);
+        assert_eq!(&half.data[4..], &[0, 0, 0, 255]);
+
+        let fitted = solid_frame(1920, 1080, [1, 2, 3, 255]).fit_width(640);
+        assert_eq!((fitted.width, fitted.height), (640, 360));
+    }
+
+    #[test]
+    fn test_encode_gif_merges_identical_frames() {
+        let options = AnimationOptions { format: AnimationFormat::Gif, ..Default::default() };
+        let gif = encode_animation(&sample_frames(), 100.0, &options).unwrap();
+
+        let mut decode_options = gif::DecodeOptions::new();
+        decode_options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = decode_options.read_info(std::io::Cursor::new(gif)).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (16, 8));
+
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.left, frame.top, frame.width, frame.height));
+        }
+        // The repeated frame is folded into the first one's delay and the
+        // last frame only covers the changed rectangle
+        assert_eq!(frames, vec![(20, 0, 0, 16, 8), (10, 5, 2, 4, 2)]);
+    }
+
+    #[test]
+    fn test_encode_webp_roundtrip() {
+        let source = sample_frames();
+        let options = AnimationOptions { format: AnimationFormat::WebP, ..Default::default() };
+        let webp = encode_animation(&source, 100.0, &options).unwrap();
+
+        let mut decoder = image_webp::WebPDecoder::new(std::io::Cursor::new(webp)).unwrap();
+        assert!(decoder.is_animated());
+        assert_eq!(decoder.dimensions(), (16, 8));
+        assert_eq!(decoder.num_frames(), 2);
+
+        let mut buf = vec![0; decoder.output_buffer_size().unwrap()];
+        assert_eq!(decoder.read_frame(&mut buf).unwrap(), 200);
+        assert_eq!(buf, source[0].data);
+        assert_eq!(decoder.read_frame(&mut buf).unwrap(), 100);
+        // Blending the partial frame over the canvas may be off by one
+        assert!(buf.iter().zip(&source[2].data).all(|(a, b)| a.abs_diff(*b) <= 1));
+    }
+
+    #[test]
+    fn test_encode_animation_rejects_mismatched_frames() {
+        let frames = vec![solid_frame(4, 4, [0; 4]), solid_frame(8, 4, [0; 4])];
+        assert!(encode_animation(&frames, 100.0, &AnimationOptions::default()).is_err());
+        assert!(encode_animation(&[], 100.0, &AnimationOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_config_default_frame_rate_mode() {
+        let config = RecordingConfig::default();
+        assert!(!config.variable_frame_rate);
+        assert_eq!(config.keyframe_interval_secs, 2);
+    }
 }
@@ -29,6 +29,9 @@ typedef void (*SCRecorderCallback)(int32_t event, void* user_data);
 // Create a new recorder
 // Returns NULL on failure
 // audio_device_id: optional device unique ID (NULL for auto-select)
+// variable_frame_rate: drop unchanged frames instead of writing them
+// keyframe_interval_secs: maximum seconds between keyframes
+// comment: optional text for the file's comment tag (NULL for none)
 SCRecorder* sc_recorder_create(
     const char* output_path,
     uint32_t width,
@@ -37,7 +40,10 @@ SCRecorder* sc_recorder_create(
     uint32_t quality,
     uint32_t display_id,
     bool capture_audio,
-    const char* audio_device_id
+    const char* audio_device_id,
+    bool variable_frame_rate,
+    uint32_t keyframe_interval_secs,
+    const char* comment
 );
 
 // Start recording
@@ -64,6 +70,51 @@ void sc_recorder_set_callback(
 // Get last error message (NULL if no error)
 const char* sc_recorder_last_error(SCRecorder* recorder);
 
+// Single-frame screenshot (RGBA, 4 bytes per pixel, tightly packed)
+// On failure `data` is NULL and `error` holds a message
+typedef struct {
+    uint8_t* data;
+    uint32_t width;
+    uint32_t height;
+    char* error;
+} SCImage;
+
+// Capture a screenshot of a display, optionally limited to a region
+// display_id: 0 = primary display
+SCImage* sc_capture_screenshot(
+    uint32_t display_id,
+    bool has_region,
+    uint32_t x,
+    uint32_t y,
+    uint32_t width,
+    uint32_t height
+);
+
+// Free an image returned by sc_capture_screenshot
+void sc_free_image(SCImage* image);
+
+// Opaque frame extractor handle (decodes frames from an existing video file)
+typedef struct SCFrameExtractor SCFrameExtractor;
+
+// Open a video for frame extraction
+// max_width/max_height: scale frames to fit (0 = no limit)
+// Returns NULL on failure
+SCFrameExtractor* sc_frame_extractor_create(
+    const char* path,
+    uint32_t max_width,
+    uint32_t max_height
+);
+
+// Get video duration in seconds
+double sc_frame_extractor_duration(SCFrameExtractor* extractor);
+
+// Decode the frame at the given time (seconds)
+// Returns an image; check `error` on failure
+SCImage* sc_frame_extractor_frame_at(SCFrameExtractor* extractor, double seconds);
+
+// Free the extractor
+void sc_frame_extractor_free(SCFrameExtractor* extractor);
+
 // Rust logging bridge functions
 // These allow Objective-C code to log through Rust's log system
 void rust_log_info(const char* msg);
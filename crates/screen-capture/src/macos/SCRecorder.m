@@ -34,7 +34,10 @@
 } while(0)
 
 API_AVAILABLE(macos(12.3))
-@interface SCRecorderImpl : NSObject <SCStreamOutput, SCStreamDelegate, AVCaptureAudioDataOutputSampleBufferDelegate>
+@interface SCRecorderImpl : NSObject <SCStreamOutput, SCStreamDelegate, AVCaptureAudioDataOutputSampleBufferDelegate> {
+    // Last frame written in variable frame rate mode, repeated to keep keyframe cadence (retained manually)
+    CVPixelBufferRef _lastPixelBuffer;
+}
 
 @property (nonatomic, strong) SCStream *stream;
 @property (nonatomic, strong) SCContentFilter *filter;
@@ -57,6 +60,11 @@ API_AVAILABLE(macos(12.3))
 @property (nonatomic, assign) uint32_t width;
 @property (nonatomic, assign) uint32_t height;
 @property (nonatomic, assign) NSTimeInterval finalDuration;
+@property (nonatomic, assign) BOOL variableFrameRate;
+@property (nonatomic, assign) uint32_t keyframeIntervalSecs;
+@property (nonatomic, assign) CMTime lastWrittenFrameTime;  // Original (host clock) time of last written frame
+@property (nonatomic, assign) uint64_t framesReceived;
+@property (nonatomic, assign) uint64_t framesWritten;
 
 - (instancetype)initWithConfig:(const char*)path
                          width:(uint32_t)w
@@ -65,7 +73,10 @@ API_AVAILABLE(macos(12.3))
                        quality:(uint32_t)q
                      displayID:(uint32_t)displayID
                   captureAudio:(BOOL)captureAudio
-                audioDeviceID:(const char*)audioDeviceID;
+                audioDeviceID:(const char*)audioDeviceID
+             variableFrameRate:(BOOL)variableFrameRate
+          keyframeIntervalSecs:(uint32_t)keyframeIntervalSecs
+                       comment:(const char*)comment;
 - (int32_t)start;
 - (int32_t)stop;
 - (double)duration;
@@ -81,7 +92,10 @@ API_AVAILABLE(macos(12.3))
                        quality:(uint32_t)q
                      displayID:(uint32_t)displayID
                   captureAudio:(BOOL)captureAudio
-                audioDeviceID:(const char*)audioDeviceID {
+                audioDeviceID:(const char*)audioDeviceID
+             variableFrameRate:(BOOL)variableFrameRate
+          keyframeIntervalSecs:(uint32_t)keyframeIntervalSecs
+                       comment:(const char*)comment {
     self = [super init];
     if (self) {
         _outputPath = [NSString stringWithUTF8String:path];
@@ -97,6 +111,12 @@ API_AVAILABLE(macos(12.3))
         _firstAudioTime = kCMTimeZero;
         _lastError = nil;
         _finalDuration = 0.0;
+        _variableFrameRate = variableFrameRate;
+        _keyframeIntervalSecs = keyframeIntervalSecs > 0 ? keyframeIntervalSecs : 2;
+        _lastPixelBuffer = NULL;
+        _lastWrittenFrameTime = kCMTimeInvalid;
+        _framesReceived = 0;
+        _framesWritten = 0;
         
         // Initialize asset writer
         NSError *error = nil;
@@ -109,6 +129,16 @@ API_AVAILABLE(macos(12.3))
             return nil;
         }
         
+        // Written as the MP4 comment tag (moov/udta/meta/ilst/©cmt), which
+        // survives renames, copies and most re-encodes
+        if (comment) {
+            AVMutableMetadataItem *commentItem = [AVMutableMetadataItem metadataItem];
+            commentItem.keySpace = AVMetadataKeySpaceiTunes;
+            commentItem.key = AVMetadataiTunesMetadataKeyUserComment;
+            commentItem.value = [NSString stringWithUTF8String:comment];
+            _assetWriter.metadata = @[commentItem];
+        }
+        
         // Configure video settings for H.264
         NSDictionary *videoSettings = @{
             AVVideoCodecKey: AVVideoCodecTypeH264,
@@ -117,7 +147,9 @@ API_AVAILABLE(macos(12.3))
             AVVideoCompressionPropertiesKey: @{
                 AVVideoAverageBitRateKey: @(w * h * 3 * f / 4), // Reasonable bitrate
                 AVVideoProfileLevelKey: AVVideoProfileLevelH264HighAutoLevel,
-                AVVideoMaxKeyFrameIntervalKey: @(f * 2), // Keyframe every 2 seconds
+                AVVideoMaxKeyFrameIntervalKey: @(f * _keyframeIntervalSecs),
+                // Frame-count intervals stretch when static frames are dropped, so also cap by time
+                AVVideoMaxKeyFrameIntervalDurationKey: @(_keyframeIntervalSecs),
             }
         };
         
@@ -219,10 +251,21 @@ API_AVAILABLE(macos(12.3))
             LOG_INFO(@"🎤 Pre-initializing audio capture...");
             [self setupAudioCapture];
         }
+        
+        if (_variableFrameRate) {
+            LOG_INFO(@"🎞️  Variable frame rate enabled (keyframe at least every %us)", _keyframeIntervalSecs);
+        }
     }
     return self;
 }
 
+- (void)dealloc {
+    if (_lastPixelBuffer) {
+        CVPixelBufferRelease(_lastPixelBuffer);
+        _lastPixelBuffer = NULL;
+    }
+}
+
 - (int32_t)start {
     if (_isRecording) {
         _lastError = @"Already recording";
@@ -250,6 +293,9 @@ API_AVAILABLE(macos(12.3))
     _firstFrameTime = kCMTimeZero;
     _hasFirstAudio = NO;
     _firstAudioTime = kCMTimeZero;
+    _lastWrittenFrameTime = kCMTimeInvalid;
+    _framesReceived = 0;
+    _framesWritten = 0;
     
     // CRITICAL: Set recording flag BEFORE starting capture so audio frames aren't dropped
     _isRecording = YES;
@@ -293,6 +339,12 @@ API_AVAILABLE(macos(12.3))
             result = -1;
         }
         
+        // Extend the last static stretch up to the stop time before finishing
+        if (weakSelf.variableFrameRate) {
+            [weakSelf flushTrailingFrame];
+            LOG_INFO(@"🎞️  Variable frame rate: wrote %llu of %llu frames", weakSelf.framesWritten, weakSelf.framesReceived);
+        }
+        
         // Finish writing
         [weakSelf.videoInput markAsFinished];
         if (weakSelf.audioInput) {
@@ -448,8 +500,137 @@ API_AVAILABLE(macos(12.3))
     }
 }
 
+// Whether ScreenCaptureKit reports that a frame carries no new content
+- (BOOL)isUnchangedFrame:(CMSampleBufferRef)sampleBuffer {
+    CFArrayRef attachmentsArray = CMSampleBufferGetSampleAttachmentsArray(sampleBuffer, NO);
+    if (!attachmentsArray || CFArrayGetCount(attachmentsArray) == 0) {
+        return NO;
+    }
+    
+    NSDictionary *attachments = (__bridge NSDictionary *)CFArrayGetValueAtIndex(attachmentsArray, 0);
+    
+    // Idle/blank/suspended frames have no new pixels (and usually no image buffer)
+    NSNumber *status = attachments[SCStreamFrameInfoStatus];
+    if (status && status.integerValue != SCFrameStatusComplete) {
+        return YES;
+    }
+    
+    // A complete frame with no dirty rects is identical to the previous one
+    NSArray *dirtyRects = attachments[SCStreamFrameInfoDirtyRects];
+    if (dirtyRects && dirtyRects.count == 0) {
+        return YES;
+    }
+    
+    return NO;
+}
+
+// Append a pixel buffer as a new video sample at the given (zero-based) time
+- (BOOL)appendPixelBuffer:(CVPixelBufferRef)pixelBuffer atTime:(CMTime)adjustedTime {
+    CMVideoFormatDescriptionRef format = NULL;
+    OSStatus status = CMVideoFormatDescriptionCreateForImageBuffer(kCFAllocatorDefault, pixelBuffer, &format);
+    if (status != noErr || format == NULL) {
+        LOG_ERROR(@"❌ Failed to create format description for repeated frame (status: %d)", status);
+        return NO;
+    }
+    
+    CMSampleTimingInfo timingInfo;
+    timingInfo.presentationTimeStamp = adjustedTime;
+    timingInfo.decodeTimeStamp = kCMTimeInvalid;
+    timingInfo.duration = CMTimeMake(1, _fps);
+    
+    CMSampleBufferRef sample = NULL;
+    status = CMSampleBufferCreateReadyWithImageBuffer(kCFAllocatorDefault, pixelBuffer, format, &timingInfo, &sample);
+    CFRelease(format);
+    
+    if (status != noErr || sample == NULL) {
+        LOG_ERROR(@"❌ Failed to create repeated frame sample buffer (status: %d)", status);
+        return NO;
+    }
+    
+    BOOL success = [_videoInput appendSampleBuffer:sample];
+    CFRelease(sample);
+    return success;
+}
+
+// Variable frame rate: write changed frames, and repeat the last one only when
+// the keyframe interval has elapsed so static screens cost almost nothing
+- (void)handleVariableRateFrame:(CMSampleBufferRef)sampleBuffer {
+    @synchronized (self) {
+        _framesReceived++;
+        
+        CMTime originalTime = CMSampleBufferGetPresentationTimeStamp(sampleBuffer);
+        CVPixelBufferRef pixelBuffer = CMSampleBufferGetImageBuffer(sampleBuffer);
+        BOOL unchanged = pixelBuffer == NULL || [self isUnchangedFrame:sampleBuffer];
+        
+        if (unchanged) {
+            // Nothing written yet, or too soon since the last written frame
+            if (_lastPixelBuffer == NULL || !CMTIME_IS_VALID(_lastWrittenFrameTime)) {
+                return;
+            }
+            CMTime sinceLast = CMTimeSubtract(originalTime, _lastWrittenFrameTime);
+            if (CMTimeGetSeconds(sinceLast) < (Float64)_keyframeIntervalSecs) {
+                return;
+            }
+            
+            if (_videoInput.readyForMoreMediaData &&
+                [self appendPixelBuffer:_lastPixelBuffer atTime:CMTimeSubtract(originalTime, _firstFrameTime)]) {
+                _lastWrittenFrameTime = originalTime;
+                _framesWritten++;
+            }
+            return;
+        }
+        
+        if (!_videoInput.readyForMoreMediaData) {
+            return;
+        }
+        
+        if (!_hasFirstFrame) {
+            _firstFrameTime = originalTime;
+            _hasFirstFrame = YES;
+        }
+        
+        if ([self appendPixelBuffer:pixelBuffer atTime:CMTimeSubtract(originalTime, _firstFrameTime)]) {
+            _lastWrittenFrameTime = originalTime;
+            _framesWritten++;
+            
+            // Keep the latest frame so static stretches can repeat it
+            if (_lastPixelBuffer) {
+                CVPixelBufferRelease(_lastPixelBuffer);
+            }
+            _lastPixelBuffer = CVPixelBufferRetain(pixelBuffer);
+        }
+    }
+}
+
+// Repeat the last frame at the current time so the trailing static stretch keeps its length
+- (void)flushTrailingFrame {
+    @synchronized (self) {
+        if (_lastPixelBuffer == NULL || !CMTIME_IS_VALID(_lastWrittenFrameTime)) {
+            return;
+        }
+        
+        CMTime now = CMClockGetTime(CMClockGetHostTimeClock());
+        if (CMTimeCompare(now, _lastWrittenFrameTime) <= 0) {
+            return;
+        }
+        
+        if (_videoInput.readyForMoreMediaData &&
+            [self appendPixelBuffer:_lastPixelBuffer atTime:CMTimeSubtract(now, _firstFrameTime)]) {
+            _framesWritten++;
+        }
+        
+        CVPixelBufferRelease(_lastPixelBuffer);
+        _lastPixelBuffer = NULL;
+    }
+}
+
 // SCStreamOutput delegate method
 - (void)stream:(SCStream *)stream didOutputSampleBuffer:(CMSampleBufferRef)sampleBuffer ofType:(SCStreamOutputType)type {
+    if (type == SCStreamOutputTypeScreen && _isRecording && _variableFrameRate) {
+        [self handleVariableRateFrame:sampleBuffer];
+        return;
+    }
+    
     if (type == SCStreamOutputTypeScreen && _isRecording) {
         if (_videoInput.readyForMoreMediaData) {
             // Get the original presentation timestamp
@@ -504,7 +685,10 @@ SCRecorder* sc_recorder_create(
     uint32_t quality,
     uint32_t display_id,
     bool capture_audio,
-    const char* audio_device_id
+    const char* audio_device_id,
+    bool variable_frame_rate,
+    uint32_t keyframe_interval_secs,
+    const char* comment
 ) {
     @autoreleasepool {
         SCRecorderImpl *impl = [[SCRecorderImpl alloc] initWithConfig:output_path
@@ -514,7 +698,10 @@ SCRecorder* sc_recorder_create(
                                                                quality:quality
                                                              displayID:display_id
                                                           captureAudio:capture_audio
-                                                         audioDeviceID:audio_device_id];
+                                                         audioDeviceID:audio_device_id
+                                                     variableFrameRate:variable_frame_rate
+                                                  keyframeIntervalSecs:keyframe_interval_secs
+                                                               comment:comment];
         if (!impl) {
             return NULL;
         }
@@ -587,6 +774,206 @@ const char* sc_recorder_last_error(SCRecorder* recorder) {
     }
 }
 
+// Screenshot functions
+
+static SCImage* sc_image_with_error(NSString *message) {
+    SCImage *image = (SCImage*)calloc(1, sizeof(SCImage));
+    image->error = strdup([message UTF8String]);
+    return image;
+}
+
+// Render a CGImage into a tightly packed RGBA buffer
+static SCImage* sc_image_from_cgimage(CGImageRef cgImage) {
+    size_t width = CGImageGetWidth(cgImage);
+    size_t height = CGImageGetHeight(cgImage);
+    if (width == 0 || height == 0) {
+        return sc_image_with_error(@"Captured image is empty");
+    }
+    
+    uint8_t *data = (uint8_t*)calloc(width * height * 4, 1);
+    if (!data) {
+        return sc_image_with_error(@"Failed to allocate image buffer");
+    }
+    
+    CGColorSpaceRef colorSpace = CGColorSpaceCreateWithName(kCGColorSpaceSRGB);
+    CGContextRef context = CGBitmapContextCreate(data, width, height, 8, width * 4, colorSpace,
+                                                 kCGImageAlphaPremultipliedLast | kCGBitmapByteOrder32Big);
+    CGColorSpaceRelease(colorSpace);
+    if (!context) {
+        free(data);
+        return sc_image_with_error(@"Failed to create bitmap context");
+    }
+    
+    CGContextDrawImage(context, CGRectMake(0, 0, width, height), cgImage);
+    CGContextRelease(context);
+    
+    SCImage *image = (SCImage*)calloc(1, sizeof(SCImage));
+    image->data = data;
+    image->width = (uint32_t)width;
+    image->height = (uint32_t)height;
+    return image;
+}
+
+SCImage* sc_capture_screenshot(
+    uint32_t display_id,
+    bool has_region,
+    uint32_t x,
+    uint32_t y,
+    uint32_t width,
+    uint32_t height
+) {
+    @autoreleasepool {
+        if (@available(macOS 14.0, *)) {
+            dispatch_semaphore_t semaphore = dispatch_semaphore_create(0);
+            __block SCImage *result = NULL;
+            
+            [SCShareableContent getShareableContentWithCompletionHandler:^(SCShareableContent *content, NSError *error) {
+                if (error) {
+                    result = sc_image_with_error([NSString stringWithFormat:@"Failed to get shareable content: %@", error.localizedDescription]);
+                    dispatch_semaphore_signal(semaphore);
+                    return;
+                }
+                
+                // Same display selection as the recorder: 0 = primary display
+                SCDisplay *display = content.displays.firstObject;
+                if (display_id != 0) {
+                    for (SCDisplay *candidate in content.displays) {
+                        if (candidate.displayID == display_id) {
+                            display = candidate;
+                            break;
+                        }
+                    }
+                }
+                if (!display) {
+                    result = sc_image_with_error(@"No displays found");
+                    dispatch_semaphore_signal(semaphore);
+                    return;
+                }
+                
+                SCContentFilter *filter = [[SCContentFilter alloc] initWithDisplay:display excludingWindows:@[]];
+                SCStreamConfiguration *config = [[SCStreamConfiguration alloc] init];
+                config.pixelFormat = kCVPixelFormatType_32BGRA;
+                config.showsCursor = NO;
+                
+                if (has_region) {
+                    config.sourceRect = CGRectMake(x, y, width, height);
+                    config.width = width;
+                    config.height = height;
+                } else {
+                    // Capture at the display's native pixel size (Retina aware)
+                    CGDisplayModeRef mode = CGDisplayCopyDisplayMode(display.displayID);
+                    config.width = mode ? CGDisplayModeGetPixelWidth(mode) : display.width;
+                    config.height = mode ? CGDisplayModeGetPixelHeight(mode) : display.height;
+                    if (mode) {
+                        CGDisplayModeRelease(mode);
+                    }
+                }
+                
+                [SCScreenshotManager captureImageWithFilter:filter
+                                              configuration:config
+                                          completionHandler:^(CGImageRef image, NSError *captureError) {
+                    if (captureError || !image) {
+                        result = sc_image_with_error([NSString stringWithFormat:@"Failed to capture screenshot: %@",
+                                                      captureError ? captureError.localizedDescription : @"no image"]);
+                    } else {
+                        result = sc_image_from_cgimage(image);
+                    }
+                    dispatch_semaphore_signal(semaphore);
+                }];
+            }];
+            
+            dispatch_semaphore_wait(semaphore, DISPATCH_TIME_FOREVER);
+            if (result && result->data) {
+                LOG_DEBUG(@"📸 Screenshot captured: %ux%u", result->width, result->height);
+            }
+            return result;
+        } else {
+            return sc_image_with_error(@"Screenshots require macOS 14.0 or newer");
+        }
+    }
+}
+
+void sc_free_image(SCImage* image) {
+    if (!image) return;
+    free(image->data);
+    free(image->error);
+    free(image);
+}
+
+// Frame extraction functions
+
+struct SCFrameExtractor {
+    void *generator; // Retained AVAssetImageGenerator
+    double duration;
+};
+
+SCFrameExtractor* sc_frame_extractor_create(
+    const char* path,
+    uint32_t max_width,
+    uint32_t max_height
+) {
+    @autoreleasepool {
+        if (!path) return NULL;
+        
+        NSURL *url = [NSURL fileURLWithPath:[NSString stringWithUTF8String:path]];
+        AVURLAsset *asset = [AVURLAsset URLAssetWithURL:url options:@{AVURLAssetPreferPreciseDurationAndTimingKey: @YES}];
+        if ([asset tracksWithMediaType:AVMediaTypeVideo].count == 0) {
+            LOG_WARN(@"⚠️ No video track found in %s", path);
+            return NULL;
+        }
+        
+        AVAssetImageGenerator *generator = [[AVAssetImageGenerator alloc] initWithAsset:asset];
+        generator.appliesPreferredTrackTransform = YES;
+        // Exact frames - export and poster frames must match the requested time
+        generator.requestedTimeToleranceBefore = kCMTimeZero;
+        generator.requestedTimeToleranceAfter = kCMTimeZero;
+        if (max_width > 0 || max_height > 0) {
+            generator.maximumSize = CGSizeMake(max_width, max_height);
+        }
+        
+        SCFrameExtractor *extractor = (SCFrameExtractor*)malloc(sizeof(SCFrameExtractor));
+        extractor->generator = (__bridge_retained void*)generator;
+        extractor->duration = CMTimeGetSeconds(asset.duration);
+        return extractor;
+    }
+}
+
+double sc_frame_extractor_duration(SCFrameExtractor* extractor) {
+    if (!extractor) return 0.0;
+    return extractor->duration;
+}
+
+SCImage* sc_frame_extractor_frame_at(SCFrameExtractor* extractor, double seconds) {
+    @autoreleasepool {
+        if (!extractor) {
+            return sc_image_with_error(@"Frame extractor not initialized");
+        }
+        
+        AVAssetImageGenerator *generator = (__bridge AVAssetImageGenerator*)(extractor->generator);
+        NSError *error = nil;
+        CMTime time = CMTimeMakeWithSeconds(seconds, 600);
+        CGImageRef cgImage = [generator copyCGImageAtTime:time actualTime:NULL error:&error];
+        if (!cgImage) {
+            return sc_image_with_error([NSString stringWithFormat:@"Failed to decode frame at %.3fs: %@",
+                                        seconds, error ? error.localizedDescription : @"unknown error"]);
+        }
+        
+        SCImage *image = sc_image_from_cgimage(cgImage);
+        CGImageRelease(cgImage);
+        return image;
+    }
+}
+
+void sc_frame_extractor_free(SCFrameExtractor* extractor) {
+    @autoreleasepool {
+        if (extractor) {
+            AVAssetImageGenerator *generator = (__bridge_transfer AVAssetImageGenerator*)(extractor->generator);
+            (void)generator; // Just to release it
+            free(extractor);
+        }
+    }
+}
+
 // Audio device management functions
 
 typedef struct {
@@ -6,6 +6,7 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
+use crate::RgbaImage;
 use std::ffi::{c_void, CString};
 use std::os::raw::c_char;
 use std::ptr;
@@ -16,6 +17,11 @@ pub struct SCRecorder {
     _private: [u8; 0],
 }
 
+#[repr(C)]
+pub struct SCFrameExtractor {
+    _private: [u8; 0],
+}
+
 // Callback type for recording events (reserved for future event callback system)
 #[allow(dead_code)]
 pub type SCRecorderCallback = extern "C" fn(event: i32, user_data: *mut c_void);
@@ -34,6 +40,9 @@ extern "C" {
     // Create a new recorder
     // Returns NULL on failure
     // audio_device_id: optional device unique ID (NULL for auto-select)
+    // variable_frame_rate: drop unchanged frames instead of writing them
+    // keyframe_interval_secs: maximum seconds between keyframes
+    // comment: optional text for the file's comment tag (NULL for none)
     pub fn sc_recorder_create(
         output_path: *const c_char,
         width: u32,
@@ -43,6 +52,9 @@ extern "C" {
         display_id: u32,
         capture_audio: bool,
         audio_device_id: *const c_char,
+        variable_frame_rate: bool,
+        keyframe_interval_secs: u32,
+        comment: *const c_char,
     ) -> *mut SCRecorder;
     
     // Start recording
@@ -73,6 +85,72 @@ extern "C" {
     // Audio device management
     pub fn sc_get_audio_devices() -> *mut AudioDeviceList;
     pub fn sc_free_audio_device_list(list: *mut AudioDeviceList);
+    
+    // Screenshot capture
+    // Returns NULL only on allocation failure; check `error` otherwise
+    pub fn sc_capture_screenshot(
+        display_id: u32,
+        has_region: bool,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> *mut SCImage;
+    pub fn sc_free_image(image: *mut SCImage);
+    
+    // Frame extraction from existing videos
+    // Returns NULL on failure
+    pub fn sc_frame_extractor_create(
+        path: *const c_char,
+        max_width: u32,
+        max_height: u32,
+    ) -> *mut SCFrameExtractor;
+    pub fn sc_frame_extractor_duration(extractor: *mut SCFrameExtractor) -> f64;
+    pub fn sc_frame_extractor_frame_at(extractor: *mut SCFrameExtractor, seconds: f64) -> *mut SCImage;
+    pub fn sc_frame_extractor_free(extractor: *mut SCFrameExtractor);
+}
+
+// RGBA image returned by the native side
+#[repr(C)]
+pub struct SCImage {
+    pub data: *mut u8,
+    pub width: u32,
+    pub height: u32,
+    pub error: *mut c_char,
+}
+
+/// Copy a native image into an owned `RgbaImage` and free it
+///
+/// # Safety
+/// `image` must be NULL or a pointer returned by the native side that has not been freed.
+unsafe fn take_image(image: *mut SCImage) -> Result<RgbaImage, String> {
+    if image.is_null() {
+        return Err("Failed to allocate image".to_string());
+    }
+    
+    let native = &*image;
+    let result = if native.data.is_null() {
+        if native.error.is_null() {
+            Err("Unknown error".to_string())
+        } else {
+            Err(std::ffi::CStr::from_ptr(native.error).to_string_lossy().to_string())
+        }
+    } else {
+        let len = native.width as usize * native.height as usize * 4;
+        let data = std::slice::from_raw_parts(native.data, len).to_vec();
+        RgbaImage::new(native.width, native.height, data)
+    };
+    
+    sc_free_image(image);
+    result
+}
+
+pub fn capture_screenshot(display_id: u32, region: Option<(u32, u32, u32, u32)>) -> Result<RgbaImage, String> {
+    let (x, y, width, height) = region.unwrap_or((0, 0, 0, 0));
+    unsafe {
+        let image = sc_capture_screenshot(display_id, region.is_some(), x, y, width, height);
+        take_image(image)
+    }
 }
 
 // Audio device structures
@@ -141,6 +219,43 @@ pub fn get_audio_devices() -> Result<Vec<AudioDevice>, String> {
     }
 }
 
+// Safe Rust wrapper for frame extraction
+pub struct FrameExtractor {
+    extractor: *mut SCFrameExtractor,
+}
+
+unsafe impl Send for FrameExtractor {}
+
+impl FrameExtractor {
+    pub fn new(path: &str, max_width: u32, max_height: u32) -> Result<Self, String> {
+        let path_cstr = CString::new(path)
+            .map_err(|e| format!("Invalid path: {}", e))?;
+        
+        let extractor = unsafe { sc_frame_extractor_create(path_cstr.as_ptr(), max_width, max_height) };
+        if extractor.is_null() {
+            return Err(format!("Failed to open video: {}", path));
+        }
+        
+        Ok(Self { extractor })
+    }
+    
+    pub fn duration(&self) -> f64 {
+        unsafe { sc_frame_extractor_duration(self.extractor) }
+    }
+    
+    pub fn frame_at(&mut self, seconds: f64) -> Result<RgbaImage, String> {
+        unsafe { take_image(sc_frame_extractor_frame_at(self.extractor, seconds)) }
+    }
+}
+
+impl Drop for FrameExtractor {
+    fn drop(&mut self) {
+        if !self.extractor.is_null() {
+            unsafe { sc_frame_extractor_free(self.extractor) };
+        }
+    }
+}
+
 // Safe Rust wrapper
 pub struct ScreenCaptureRecorder {
     recorder: *mut SCRecorder,
@@ -150,6 +265,7 @@ unsafe impl Send for ScreenCaptureRecorder {}
 unsafe impl Sync for ScreenCaptureRecorder {}
 
 impl ScreenCaptureRecorder {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         output_path: &str,
         width: u32,
@@ -159,6 +275,9 @@ impl ScreenCaptureRecorder {
         display_id: u32,
         capture_audio: bool,
         audio_device_id: Option<&str>,
+        variable_frame_rate: bool,
+        keyframe_interval_secs: u32,
+        comment: Option<&str>,
     ) -> Result<Self, String> {
         let path_cstr = CString::new(output_path)
             .map_err(|e| format!("Invalid path: {}", e))?;
@@ -172,6 +291,12 @@ impl ScreenCaptureRecorder {
             .map(|cs| cs.as_ptr())
             .unwrap_or(ptr::null());
         
+        let comment_cstr = comment.and_then(|c| CString::new(c).ok());
+        let comment_ptr = comment_cstr
+            .as_ref()
+            .map(|cs| cs.as_ptr())
+            .unwrap_or(ptr::null());
+        
         let recorder = unsafe {
             sc_recorder_create(
                 path_cstr.as_ptr(),
@@ -182,6 +307,9 @@ impl ScreenCaptureRecorder {
                 display_id,
                 capture_audio,
                 device_id_ptr,
+                variable_frame_rate,
+                keyframe_interval_secs,
+                comment_ptr,
             )
         };
         
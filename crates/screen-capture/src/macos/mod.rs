@@ -96,6 +96,9 @@ impl NativeRecorder {
             display_id,
             config.capture_microphone,
             config.microphone_device_id.as_deref(),
+            config.variable_frame_rate,
+            config.keyframe_interval_secs.max(1),
+            config.comment.as_deref(),
         )?;
         
         Ok(Self {
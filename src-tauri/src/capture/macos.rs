@@ -1,32 +1,32 @@
 // macOS-specific capture implementation using native ScreenCaptureKit
 
 use std::path::PathBuf;
-use std::time::Instant;
-use screen_capture::{Recorder, RecordingConfig};
+use std::time::{Duration, Instant};
+use screen_capture::{AsyncRecorder, RecordingConfig};
+use pulse_core::media;
+use super::CaptureOptions;
 use log::{debug, info};
 
 pub struct ScreenCapturer {
-    output_folder: PathBuf,
-    // Field tracked internally but not read externally (state managed via atomic bools in commands.rs)
+    output_path: PathBuf, // Named by the caller (see Project::new_partial_path)
+    // Field tracked internally but not read externally (lifecycle managed by RecordingController)
     #[allow(dead_code)]
     is_recording: bool,
     start_time: Option<Instant>,
-    recorder: Option<Recorder>,
-    mic_enabled: bool,
-    audio_device_id: Option<String>,
+    recorder: Option<AsyncRecorder>,
+    options: CaptureOptions,
     pre_initialized: bool,
     prepared_output_path: Option<PathBuf>,
 }
 
 impl ScreenCapturer {
-    pub fn new(output_folder: PathBuf, mic_enabled: bool, audio_device_id: Option<String>) -> Self {
+    pub fn new(output_path: PathBuf, options: CaptureOptions) -> Self {
         Self {
-            output_folder,
+            output_path,
             is_recording: false,
             start_time: None,
             recorder: None,
-            mic_enabled,
-            audio_device_id,
+            options,
             pre_initialized: false,
             prepared_output_path: None,
         }
@@ -37,8 +37,7 @@ impl ScreenCapturer {
         info!("🚀 Pre-initializing ScreenCaptureKit (this takes 2-3 seconds)...");
         let init_start = Instant::now();
         
-        // Get next sequential recording path and store it
-        let output_path = self.get_next_output_path();
+        let output_path = self.output_path.clone();
         debug!("📝 Prepared output path: {:?}", output_path);
         
         // Convert region tuple to CaptureRegion if provided
@@ -52,14 +51,18 @@ impl ScreenCapturer {
             fps: 30,
             quality: 80,
             capture_cursor: true,
-            capture_microphone: self.mic_enabled,
-            microphone_device_id: self.audio_device_id.clone(),
+            capture_microphone: self.options.mic_enabled,
+            microphone_device_id: self.options.audio_device_id.clone(),
             display_id: Some(0),
             region: capture_region,
+            variable_frame_rate: self.options.variable_frame_rate,
+            keyframe_interval_secs: self.options.keyframe_interval_secs,
+            // Lets reconcile recognize the clip after it's renamed or re-encoded
+            comment: media::partial_clip_id(&output_path).map(media::clip_id_comment),
         };
 
-        // Create recorder (this is the slow part - initializes ScreenCaptureKit)
-        let recorder = Recorder::new(config)
+        // Create recorder (this is the slow part - initializes ScreenCaptureKit on the recorder's own thread)
+        let recorder = AsyncRecorder::new(config).await
             .map_err(|e| format!("Failed to create recorder: {}", e))?;
 
         self.recorder = Some(recorder);
@@ -104,7 +107,7 @@ impl ScreenCapturer {
 
         // Start recording (should be instant if pre-initialized)
         if let Some(ref mut recorder) = self.recorder {
-            recorder.start()
+            recorder.start().await
                 .map_err(|e| format!("Failed to start recording: {}", e))?;
 
             let startup_duration = start_time.elapsed();
@@ -137,7 +140,7 @@ impl ScreenCapturer {
 
         // Stop the recorder
         if let Some(mut recorder) = self.recorder.take() {
-            let (_, recorded_duration) = recorder.stop()
+            let (_, recorded_duration) = recorder.stop().await
                 .map_err(|e| format!("Failed to stop recording: {}", e))?;
             
             info!("📊 Recording complete: {:.2}s", recorded_duration);
@@ -153,51 +156,25 @@ impl ScreenCapturer {
         }
     }
 
-    /// Get the next sequential recording path (recording-1.mp4, recording-2.mp4, etc.)
-    fn get_next_output_path(&self) -> PathBuf {
-        let mut n = 1;
-        loop {
-            let path = self.output_folder.join(format!("recording-{}.mp4", n));
-            if !path.exists() {
-                return path;
-            }
-            n += 1;
-        }
-    }
-
-    /// Get the most recently created recording path
-    /// Helper method for potential future file management features
-    #[allow(dead_code)]
-    fn get_last_created_path(&self) -> PathBuf {
-        // Find the highest numbered recording that exists
-        let mut n = 1;
-        let mut last_path = self.output_folder.join(format!("recording-{}.mp4", n));
-        
-        loop {
-            let path = self.output_folder.join(format!("recording-{}.mp4", n));
-            if !path.exists() {
-                break;
-            }
-            last_path = path;
-            n += 1;
-        }
-        
-        last_path
-    }
-
     /// Check if currently recording
-    /// Accessor method for recording state (state tracked internally via atomic bool in commands.rs)
+    /// Accessor method for recording state (lifecycle tracked by RecordingController)
     #[allow(dead_code)]
     pub fn is_recording(&self) -> bool {
         self.is_recording
     }
 
+    /// Time since recording started (None if not recording)
+    /// Used to place markers without waiting on the recorder
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.start_time.filter(|_| self.is_recording).map(|start| start.elapsed())
+    }
+
     /// Get recording duration (if recording)
     /// Utility method for potential future real-time duration display in UI
     #[allow(dead_code)]
-    pub fn duration(&self) -> f64 {
+    pub async fn duration(&self) -> f64 {
         if let Some(recorder) = &self.recorder {
-            recorder.duration()
+            recorder.duration().await.unwrap_or(0.0)
         } else {
             0.0
         }
@@ -1,5 +1,14 @@
 // Capture module - platform-specific screen capture implementations
 
+/// User settings a capturer records with (see `pre_initialize_capturer`)
+#[derive(Debug, Clone)]
+pub struct CaptureOptions {
+    pub mic_enabled: bool,
+    pub audio_device_id: Option<String>, // None = auto-select
+    pub variable_frame_rate: bool, // Skip unchanged frames
+    pub keyframe_interval_secs: u32, // Most seconds between keyframes
+}
+
 #[cfg(target_os = "macos")]
 pub mod macos;
 
@@ -2,6 +2,7 @@
 
 use std::path::PathBuf;
 use log::debug;
+use super::CaptureOptions;
 
 pub struct ScreenCapturer {
     output_path: PathBuf,
@@ -10,11 +11,11 @@ pub struct ScreenCapturer {
 }
 
 impl ScreenCapturer {
-    pub fn new(output_path: PathBuf, mic_enabled: bool) -> Self {
+    pub fn new(output_path: PathBuf, options: CaptureOptions) -> Self {
         Self {
             output_path,
             is_recording: false,
-            mic_enabled,
+            mic_enabled: options.mic_enabled,
         }
     }
 
@@ -1,469 +1,211 @@
-use tauri::{State, AppHandle, Manager, Emitter};
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{State, AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use crate::state::{AppState, PreInitStatus};
+use crate::recorder::{self, SavedClip};
+use crate::recording::{RecordingEvent, RecordingState};
 use crate::events;
+use crate::fs_watcher;
+use crate::hotkey::bindings::{self, Bindings, HotkeyAction, HotkeyBinding};
+use crate::hotkey::trigger::{TriggerAction, TriggerMode};
+use crate::settings::{self, Settings};
+use crate::thumbnails;
+use pulse_core::{checksum, media, EntryKind, FilenameTemplate, Library, Project, ProjectSummary, ReconcilePlan, RecoverableClip, Resolution, Timeline, TimelineEntry};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::collections::HashMap;
-use sha2::{Sha256, Digest};
-use std::io::Read;
+use tokio::sync::oneshot;
 use log::{debug, info, warn, error};
 
-#[cfg(target_os = "macos")]
-use crate::capture::macos::ScreenCapturer;
+/// Minimum time between `checksum-progress` events
+const CHECKSUM_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
 
-#[cfg(target_os = "windows")]
-use crate::capture::windows::ScreenCapturer;
+/// Register every configured hotkey during app initialization
+///
+/// A binding that can't be registered (usually because another app already
+/// owns it) is logged and skipped so the rest still work; the user can pick
+/// different keys in settings.
+pub fn setup_global_shortcut(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let bindings = app.state::<AppState>().hotkeys.lock()
+        .map_err(|e| format!("Failed to lock hotkeys: {}", e))?
+        .clone();
 
-// Performance thresholds (in milliseconds)
-/// Expected maximum time from hotkey press to recording start (includes all overhead)
-/// This includes AVAssetWriter initialization, ScreenCaptureKit activation, and thread overhead
-pub const HOTKEY_TO_RECORDING_THRESHOLD_MS: u128 = 250;
+    for action in HotkeyAction::ALL {
+        if let Err(e) = register_hotkey(app, action, bindings.get(action)) {
+            warn!("⚠️  {}", e);
+        }
+    }
+    Ok(())
+}
 
-// Global state to track if we're currently recording
-static IS_RECORDING: AtomicBool = AtomicBool::new(false);
+/// Register one hotkey with the OS and route its events to the action
+fn register_hotkey(app: &AppHandle, action: HotkeyAction, accelerator: &str) -> Result<(), String> {
+    let shortcut = bindings::parse(accelerator)?;
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| handle_hotkey(app, action, event.state))
+        .map_err(|e| format!(
+            "Couldn't register {} for {} - it may be in use by another app ({})",
+            accelerator, action.label(), e
+        ))?;
+
+    info!("✅ Global shortcut registered: {} ({})", accelerator, action.label());
+    Ok(())
+}
 
-// Global state to track if capturer is initializing (prevents recording during re-init)
-static IS_INITIALIZING: AtomicBool = AtomicBool::new(false);
+fn unregister_hotkey(app: &AppHandle, accelerator: &str) {
+    let result = bindings::parse(accelerator)
+        .and_then(|shortcut| app.global_shortcut().unregister(shortcut).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        // Expected if the binding failed to register in the first place
+        debug!("Hotkey {} was not registered: {}", accelerator, e);
+    }
+}
 
-// Global state to track if recording has actually started (vs just initiated)
-static RECORDING_ACTIVE: AtomicBool = AtomicBool::new(false);
+fn handle_hotkey(app: &AppHandle, action: HotkeyAction, key_state: ShortcutState) {
+    debug!("Hotkey event: action={:?} state={:?}", action, key_state);
 
-/// Setup global shortcut during app initialization
-pub fn setup_global_shortcut(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    // Register Cmd+Shift+R (macOS) or Ctrl+Shift+R (Windows)
-    #[cfg(target_os = "macos")]
-    let shortcut = "CmdOrCtrl+Shift+R";
+    match (action, key_state) {
+        (HotkeyAction::Record, _) => on_record_key(app, key_state),
+        (HotkeyAction::Marker, ShortcutState::Pressed) => recorder::add_marker(app),
+        (HotkeyAction::Cancel, ShortcutState::Pressed) => {
+            recorder::cancel_current_recording(app);
+        }
+        (HotkeyAction::Screenshot, ShortcutState::Pressed) => {
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = capture_screenshot_to_project(&app_clone).await {
+                    error!("❌ Failed to capture screenshot: {}", e);
+                    let _ = events::emit_error(&app_clone, "SCREENSHOT_ERROR", &e);
+                }
+            });
+        }
+        // Only the record key cares about release
+        _ => {}
+    }
+}
+
+/// Feed a record key event through the trigger state machine (hold/toggle/double-tap)
+fn on_record_key(app: &AppHandle, key_state: ShortcutState) {
+    let state = app.state::<AppState>();
+    let action = {
+        let Ok(mut trigger) = state.trigger.lock() else {
+            error!("❌ Failed to lock trigger state");
+            return;
+        };
+        // Recording ended some other way (failed start, cancel) - start from scratch
+        let recording = state.recording.lock().map(|c| c.wants_recording()).unwrap_or(false);
+        if !recording {
+            trigger.reset();
+        }
+        let now = Instant::now();
+        match key_state {
+            ShortcutState::Pressed => trigger.on_press(now),
+            ShortcutState::Released => trigger.on_release(now),
+        }
+    };
+    apply_trigger_action(app, action);
+}
+
+fn apply_trigger_action(app: &AppHandle, action: TriggerAction) {
+    match action {
+        TriggerAction::Start => recorder::begin_recording(app, None),
+        TriggerAction::Stop => recorder::finish_recording(app),
+        TriggerAction::Lock => {
+            info!("🔒 Recording locked - tap the record key again to stop");
+            let _ = events::emit_recording_locked(app);
+        }
+        TriggerAction::StopAfter(delay) => {
+            // Single tap so far - stop unless a second tap locks the recording first
+            let app = app.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                let action = app.state::<AppState>().trigger.lock()
+                    .map(|mut trigger| trigger.on_timeout(Instant::now()))
+                    .unwrap_or(TriggerAction::None);
+                apply_trigger_action(&app, action);
+            });
+        }
+        TriggerAction::None => {}
+    }
+}
+
+/// Capture a screenshot into the current project and add it to the timeline
+async fn capture_screenshot_to_project(app: &AppHandle) -> Result<PathBuf, String> {
+    let state = app.state::<AppState>();
     
-    #[cfg(target_os = "windows")]
-    let shortcut = "Ctrl+Shift+R";
+    let (output_folder, current_project, capture_region) = {
+        let folder = state.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?;
+        let project = state.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))?;
+        let region = state.capture_region.lock().map_err(|e| format!("Failed to lock capture_region: {}", e))?;
+        (folder.clone(), project.clone(), *region)
+    };
     
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    let shortcut = "Ctrl+Shift+R";
+    let Some(project_name) = current_project else {
+        warn!("⚠️  No project selected - requesting project name");
+        let _ = events::emit_project_required(app);
+        return Err("No current project set".to_string());
+    };
     
-    let shortcut: Shortcut = shortcut.parse()?;
-    let _app_handle = app.clone();
+    let project = Library::new(output_folder).ensure_project(&project_name)?;
     
-    app.global_shortcut().on_shortcut(shortcut, move |app, _shortcut, event| {
-        debug!("Hotkey event: state={:?}", event.state);
-        
-        match event.state {
-            ShortcutState::Pressed => {
-                // Key pressed - start recording
-                if !IS_RECORDING.swap(true, Ordering::SeqCst) {
-                    // Check if capturer is still initializing
-                    if IS_INITIALIZING.load(Ordering::SeqCst) {
-                        warn!("⏳ Capturer still initializing from previous recording - please wait");
-                        IS_RECORDING.store(false, Ordering::SeqCst);
-                        return;
-                    }
-                    
-                    let press_time = std::time::Instant::now();
-                    info!("🎬 Starting recording at {:?}...", press_time);
-                    
-                    // Check if we have a current project before starting
-                    let state = app.state::<AppState>();
-                    let current_project = {
-                        let project = state.current_project.lock().unwrap();
-                        project.clone()
-                    };
-                    
-                    // If no project is selected, emit event and stop recording
-                    if current_project.is_none() {
-                        warn!("⚠️  No project selected - requesting project name");
-                        let _ = events::emit_project_required(app);
-                        IS_RECORDING.store(false, Ordering::SeqCst);
-                        return;
-                    }
-                    
-                    // Pause filesystem watcher during recording
-                    {
-                        let control = state.watcher_control.lock().unwrap();
-                        if let Some(watcher) = control.as_ref() {
-                            watcher.pause();
-                        }
-                    }
-                    
-                    let _ = events::emit_status(app, "recording");
-                    
-                    // Check if we already have a pre-initialized capturer
-                    let capturer_ready = {
-                        let cap = state.capturer.lock().unwrap();
-                        cap.is_some()
-                    };
-                    
-                    if capturer_ready {
-                        // Fast path: capturer already initialized
-                        info!("⚡ Using pre-initialized capturer (fast path)");
-                        let app_clone = app.clone();
-                        std::thread::spawn(move || {
-                            let state = app_clone.state::<AppState>();
-                            
-                            // Get capture region from state
-                            let capture_region = {
-                                let region = state.capture_region.lock().unwrap();
-                                *region
-                            };
-                            
-                            // Take capturer from state
-                            let capturer_option = {
-                                let mut cap = state.capturer.lock().unwrap();
-                                cap.take()
-                            };
-                            
-                            if let Some(mut capturer) = capturer_option {
-                                let runtime = tokio::runtime::Runtime::new().unwrap();
-                                match runtime.block_on(capturer.start_recording(capture_region)) {
-                                    Ok(_) => {
-                                        let elapsed = press_time.elapsed();
-                                        info!("✅ Screen capture started in {:?}", elapsed);
-                                        
-                                        // Update last activity
-                                        if let Ok(mut activity) = state.last_activity.lock() {
-                                            *activity = Instant::now();
-                                        }
-                                        
-                                        // Mark recording as actually active now
-                                        RECORDING_ACTIVE.store(true, Ordering::SeqCst);
-                                        
-                                        if elapsed.as_millis() > HOTKEY_TO_RECORDING_THRESHOLD_MS {
-                                            error!("⚠️  SLOW START DETECTED: {:?} from key press to recording started", elapsed);
-                                            error!("💔 We sincerely apologize - you may have lost the first {:?} of your recording.", elapsed);
-                                            error!("🔧 This should not happen with pre-initialization. Please report this issue.");
-                                        }
-                                        
-                                        // Store capturer back in state
-                                        let mut cap = state.capturer.lock().unwrap();
-                                        *cap = Some(capturer);
-                                    }
-                                    Err(e) => {
-                                        error!("❌ Failed to start recording: {}", e);
-                                        let _ = events::emit_error(&app_clone, "CAPTURE_ERROR", &e);
-                                        IS_RECORDING.store(false, Ordering::SeqCst);
-                                        RECORDING_ACTIVE.store(false, Ordering::SeqCst); // Clear active flag on error
-                                        let _ = events::emit_status(&app_clone, "idle");
-                                        
-                                        // Resume filesystem watcher on error
-                                        {
-                                            let control = state.watcher_control.lock().unwrap();
-                                            if let Some(watcher) = control.as_ref() {
-                                                watcher.resume();
-                                            }
-                                        }
-                                    }
-                                }
-                            } else {
-                                error!("❌ Capturer was expected but not found!");
-                                let _ = events::emit_error(&app_clone, "CAPTURE_ERROR", "Capturer initialization failed");
-                                IS_RECORDING.store(false, Ordering::SeqCst);
-                                RECORDING_ACTIVE.store(false, Ordering::SeqCst); // Clear active flag on error
-                                let _ = events::emit_status(&app_clone, "idle");
-                                
-                                // Resume filesystem watcher
-                                {
-                                    let control = state.watcher_control.lock().unwrap();
-                                    if let Some(watcher) = control.as_ref() {
-                                        watcher.resume();
-                                    }
-                                }
-                            }
-                        });
-                    } else {
-                        // Auto-initialize path: capturer was sleeping, wake it up automatically
-                        info!("💤 Capturer was sleeping - auto-initializing for recording...");
-                        warn!("⏳ Auto-initialization will add ~2-3 seconds to recording start time");
-                        
-                        // Emit status change to show we're waking up
-                        let _ = events::emit_pre_init_status(app, "Initializing");
-                        
-                        let app_clone = app.clone();
-                        std::thread::spawn(move || {
-                            let state = app_clone.state::<AppState>();
-                            
-                            // Get output folder and current project
-                            let (base_output_folder, current_project) = {
-                                let folder = state.output_folder.lock().unwrap();
-                                let project = state.current_project.lock().unwrap();
-                                (folder.clone(), project.clone())
-                            };
-                            
-                            // At this point we know current_project is Some(), so unwrap is safe
-                            let project_name = current_project.unwrap();
-                            let output_folder = base_output_folder.join(&project_name);
-                            
-                            // Create output folder if it doesn't exist
-                            if let Err(e) = std::fs::create_dir_all(&output_folder) {
-                                error!("Failed to create output folder: {}", e);
-                                let _ = events::emit_error(&app_clone, "FOLDER_ERROR", &format!("Failed to create output folder: {}", e));
-                                IS_RECORDING.store(false, Ordering::SeqCst);
-                                let _ = events::emit_status(&app_clone, "idle");
-                                
-                                // Resume filesystem watcher on error
-                                {
-                                    let control = state.watcher_control.lock().unwrap();
-                                    if let Some(watcher) = control.as_ref() {
-                                        watcher.resume();
-                                    }
-                                }
-                                return;
-                            }
-                            
-                            // Get mic_enabled from state
-                            let mic_enabled = {
-                                let mic = state.mic_enabled.lock().unwrap();
-                                *mic
-                            };
-                            
-                            // Get selected audio device from state
-                            let audio_device_id = {
-                                let device = state.selected_audio_device.lock().unwrap();
-                                device.clone()
-                            };
-                            
-                            // Get capture region from state
-                            let capture_region = {
-                                let region = state.capture_region.lock().unwrap();
-                                *region
-                            };
-                            
-                            // Create capturer
-                            let mut capturer = ScreenCapturer::new(output_folder, mic_enabled, audio_device_id);
-                            
-                            // Pre-initialize capturer before starting recording (blocking call)
-                            let runtime = tokio::runtime::Runtime::new().unwrap();
-                            match runtime.block_on(capturer.pre_initialize(capture_region)) {
-                                Ok(()) => {
-                                    // Now start recording
-                                    match runtime.block_on(capturer.start_recording(capture_region)) {
-                                        Ok(_) => {
-                                            let elapsed = press_time.elapsed();
-                                            info!("✅ Screen capture started in {:?}", elapsed);
-                                            info!("⏰ Auto-initialization took {:?} - recording started successfully", elapsed);
-                                            
-                                            // Mark recording as active
-                                            RECORDING_ACTIVE.store(true, Ordering::SeqCst);
-                                            
-                                            // Store capturer in state
-                                            let mut cap = state.capturer.lock().unwrap();
-                                            *cap = Some(capturer);
-                                        }
-                                        Err(e) => {
-                                            error!("❌ Failed to start recording after pre-initialization: {}", e);
-                                            let _ = events::emit_error(&app_clone, "CAPTURE_ERROR", &e);
-                                            IS_RECORDING.store(false, Ordering::SeqCst);
-                                            RECORDING_ACTIVE.store(false, Ordering::SeqCst);
-                                            let _ = events::emit_status(&app_clone, "idle");
-                                            
-                                            // Resume filesystem watcher on error
-                                            {
-                                                let control = state.watcher_control.lock().unwrap();
-                                                if let Some(watcher) = control.as_ref() {
-                                                    watcher.resume();
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    error!("❌ Failed to pre-initialize capturer for auto-wake recording: {}", e);
-                                    let _ = events::emit_error(&app_clone, "CAPTURE_ERROR", &e);
-                                    IS_RECORDING.store(false, Ordering::SeqCst);
-                                    RECORDING_ACTIVE.store(false, Ordering::SeqCst);
-                                    let _ = events::emit_status(&app_clone, "idle");
-                                    
-                                    // Resume filesystem watcher on error
-                                    {
-                                        let control = state.watcher_control.lock().unwrap();
-                                        if let Some(watcher) = control.as_ref() {
-                                            watcher.resume();
-                                        }
-                                    }
-                                }
-                            }
-                        });
-                    }
-                } else {
-                    warn!("⚠️  Already recording, ignoring press");
-                }
-            }
-            ShortcutState::Released => {
-                // Key released - stop recording
-                if IS_RECORDING.swap(false, Ordering::SeqCst) {
-                    // Check if recording has actually started
-                    if !RECORDING_ACTIVE.load(Ordering::SeqCst) {
-                        warn!("⏳ Recording initiated but not yet started - waiting for it to begin...");
-                        // Wait a bit for recording to actually start (max 200ms)
-                        std::thread::sleep(std::time::Duration::from_millis(50));
-                        
-                        // Check again
-                        if !RECORDING_ACTIVE.load(Ordering::SeqCst) {
-                            warn!("⚠️  Recording never started, canceling...");
-                            let _ = events::emit_status(app, "idle");
-                            return;
-                        }
-                    }
-                    
-                    info!("⏹️  Stopping recording...");
-                    
-                    // Immediately transition to idle to allow rapid re-recording
-                    let _ = events::emit_status(app, "idle");
-                    
-                    // Stop actual screen capture in background thread
-                    let app_clone = app.clone();
-                    std::thread::spawn(move || {
-                        let state = app_clone.state::<AppState>();
-                        
-                        // Get capturer from state (take ownership to release lock immediately)
-                        let capturer_option = {
-                            let mut cap = state.capturer.lock().unwrap();
-                            cap.take()
-                        }; // Lock is released here
-                        
-                        if let Some(mut capturer) = capturer_option {
-                            let runtime = tokio::runtime::Runtime::new().unwrap();
-                            match runtime.block_on(capturer.stop_recording()) {
-                                Ok((path, duration_seconds)) => {
-                                    // Clear recording active flag
-                                    RECORDING_ACTIVE.store(false, Ordering::SeqCst);
-                                    
-                                    // Convert duration to milliseconds
-                                    let duration_ms = (duration_seconds * 1000.0) as u64;
-                                    
-                                    info!("✅ Recording saved to: {:?}, duration: {:.2}s", path, duration_seconds);
-                                    
-                                    // Increment clip count
-                                    {
-                                        let mut count = state.clip_count.lock().unwrap();
-                                        *count += 1;
-                                    }
-                                    
-                                    // Add timeline entry if we have a current project
-                                    let current_project = {
-                                        let project = state.current_project.lock().unwrap();
-                                        project.clone()
-                                    };
-                                    
-                                    if let Some(_project_name) = current_project {
-                                        let filename = path.file_name()
-                                            .and_then(|n| n.to_str())
-                                            .unwrap_or("recording.mp4")
-                                            .to_string();
-                                        
-                                        // Use actual duration from recording
-                                        let aspect_ratio = "none".to_string(); // Will be updated with actual aspect ratio
-                                        let width = 1920; // Will be updated with actual width
-                                        let height = 1080; // Will be updated with actual height
-                                        
-                                        // Add timeline entry using the runtime we have  
-                                        let app_handle_clone = app_clone.clone();
-                                        let _ = runtime.block_on(async {
-                                            if let Err(e) = add_timeline_entry(
-                                                filename,
-                                                duration_ms, // Now using actual duration
-                                                aspect_ratio,
-                                                width,
-                                                height,
-                                                app_handle_clone.state::<AppState>()
-                                            ).await {
-                                                error!("Failed to add timeline entry: {}", e);
-                                            }
-                                        });
-                                    }
-                                    
-                                    // Emit clip saved event with actual duration
-                                    let _ = events::emit_clip_saved(&app_clone, events::ClipSavedEvent {
-                                        path: path.to_string_lossy().to_string(),
-                                        duration_ms, // Now using actual duration
-                                    });
-                                    
-                                    // Re-initialize capturer for next recording (in background)
-                                    {
-                                        let current_project = state.current_project.lock().unwrap().clone();
-                                        if let Some(_project_name) = current_project {
-                                            info!("🔄 Re-initializing capturer for next recording...");
-                                            
-                                            // Set initializing flag to prevent recording during re-init
-                                            IS_INITIALIZING.store(true, Ordering::SeqCst);
-                                            
-                                            let output_folder = state.output_folder.lock().unwrap().clone();
-                                            let mic_enabled = state.mic_enabled.lock().unwrap().clone();
-                                            let audio_device_id = state.selected_audio_device.lock().unwrap().clone();
-                                            let capture_region = state.capture_region.lock().unwrap().clone();
-                                            let project_folder = output_folder.join(_project_name);
-                                            
-                                            // Create new capturer
-                                            let mut new_capturer = ScreenCapturer::new(project_folder, mic_enabled, audio_device_id);
-                                            
-                                            // Pre-initialize in background using Tauri's async runtime
-                                            let app_for_spawn = app_clone.clone();
-                                            tauri::async_runtime::spawn(async move {
-                                                match new_capturer.pre_initialize(capture_region).await {
-                                                    Ok(_) => {
-                                                        info!("✅ Capturer re-initialized for next recording");
-                                                        // Store back in state
-                                                        let state = app_for_spawn.state::<AppState>();
-                                                        let mut cap = state.capturer.lock().unwrap();
-                                                        *cap = Some(new_capturer);
-                                                        
-                                                        // Clear initializing flag - ready to record again
-                                                        IS_INITIALIZING.store(false, Ordering::SeqCst);
-                                                    }
-                                                    Err(e) => {
-                                                        warn!("⚠️  Failed to re-initialize capturer: {}", e);
-                                                        // Clear initializing flag even on error
-                                                        IS_INITIALIZING.store(false, Ordering::SeqCst);
-                                                    }
-                                                }
-                                            });
-                                        }
-                                    }
-                                    
-                                    // Resume filesystem watcher AFTER clip is fully processed and event emitted
-                                    {
-                                        let control = state.watcher_control.lock().unwrap();
-                                        if let Some(watcher) = control.as_ref() {
-                                            watcher.resume();
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    error!("❌ Failed to stop recording: {}", e);
-                                    let _ = events::emit_error(&app_clone, "SAVE_ERROR", &e);
-                                    
-                                    // Clear recording active flag even on error
-                                    RECORDING_ACTIVE.store(false, Ordering::SeqCst);
-                                    
-                                    // Resume filesystem watcher even on error
-                                    {
-                                        let control = state.watcher_control.lock().unwrap();
-                                        if let Some(watcher) = control.as_ref() {
-                                            watcher.resume();
-                                        }
-                                    }
-                                }
-                            }
-                        } else {
-                            warn!("⚠️  No active capturer to stop");
-                            RECORDING_ACTIVE.store(false, Ordering::SeqCst); // Clear flag even if no capturer
-                        }
-                    });
-                } else {
-                    warn!("⚠️  Not recording, ignoring release");
-                }
-            }
-        }
-    })?;
+    // Capture and encode off the async executor - ScreenCaptureKit blocks until the frame arrives
+    let path = media::next_sequential_path(project.folder(), "screenshot", "png");
+    let save_path = path.clone();
+    let region = capture_region.map(|(x, y, width, height)| screen_capture::CaptureRegion { x, y, width, height });
+    let (width, height) = tauri::async_runtime::spawn_blocking(move || {
+        let image = screen_capture::capture_screenshot(Some(0), region)?;
+        image.save_png(&save_path)?;
+        Ok::<_, String>((image.width, image.height))
+    })
+    .await
+    .map_err(|e| format!("Screenshot task failed: {}", e))??;
     
-    info!("✅ Global shortcut registered: {}", shortcut);
-    Ok(())
+    info!("📸 Screenshot saved to: {:?} ({}x{})", path, width, height);
+    
+    let filename = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("screenshot.png")
+        .to_string();
+    
+    let entry = TimelineEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind: EntryKind::Image,
+        filename: filename.clone(),
+        recorded_at: chrono::Utc::now().to_rfc3339(),
+        duration_ms: 0,
+        aspect_ratio: "none".to_string(),
+        resolution: Resolution { width, height },
+        mic_enabled: false,
+        notes: None,
+        checksum: checksum::file_checksum(&path).ok(),
+        thumbnail: Some(filename.clone()), // Screenshots are their own thumbnail
+        ..Default::default()
+    };
+    project.add_entry(entry)?;
+    
+    let _ = events::emit_screenshot_saved(app, events::ScreenshotSavedEvent {
+        path: path.to_string_lossy().to_string(),
+        width,
+        height,
+    });
+    
+    Ok(path)
+}
+
+/// Take a screenshot into the current project (same as the screenshot hotkey)
+#[tauri::command]
+pub async fn take_screenshot(app: AppHandle) -> Result<String, String> {
+    let path = capture_screenshot_to_project(&app).await?;
+    Ok(path.to_string_lossy().to_string())
 }
 
 /// Set the output folder for recordings
+///
+/// The filesystem watcher moves to the new folder, a Default project is
+/// created if it has none, and its projects are reconciled in the background
+/// (each reported with a `project-reconciled` event).
 #[tauri::command]
-pub fn set_output_folder(path: String, state: State<AppState>) -> Result<(), String> {
+pub fn set_output_folder(path: String, app: AppHandle, state: State<AppState>) -> Result<(), String> {
     let path_buf = PathBuf::from(path);
     
     // Create directory if it doesn't exist
@@ -472,28 +214,148 @@ pub fn set_output_folder(path: String, state: State<AppState>) -> Result<(), Str
             .map_err(|e| format!("Failed to create directory: {}", e))?;
     }
     
-    let mut folder = state.output_folder.lock()
-        .map_err(|e| format!("Failed to lock output_folder: {}", e))?;
-    *folder = path_buf;
+    {
+        let mut folder = state.output_folder.lock()
+            .map_err(|e| format!("Failed to lock output_folder: {}", e))?;
+        if *folder == path_buf {
+            return Ok(());
+        }
+        *folder = path_buf.clone();
+    }
+    info!("📁 Output folder set to {:?}", path_buf);
+    ensure_default_project(&path_buf);
+
+    // The current project may not exist in the new library
+    let library = Library::new(&path_buf);
+    let current_exists = state.current_project.lock()
+        .map_err(|e| format!("Failed to lock current project: {}", e))?
+        .as_ref()
+        .map(|name| library.project(name).is_ok());
+    if current_exists == Some(false) {
+        info!("🎯 Current project isn't in the new output folder, switching to Default");
+        *state.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))? = Some("Default".to_string());
+    }
+    state.save_settings();
+
+    // Interrupted recordings are listed per library
+    let clips = library.recoverable_clips().unwrap_or_else(|e| {
+        warn!("⚠️  Failed to look for interrupted recordings: {}", e);
+        Vec::new()
+    });
+    *state.recoverable_clips.lock().map_err(|e| format!("Failed to lock recoverable clips: {}", e))? = clips;
+
+    if let Err(e) = watch_output_folder(&app, &state, path_buf) {
+        warn!("⚠️  {}", e);
+    }
+
+    // The pre-initialized capturer records into the old folder
+    recorder::reinitialize_capturer_if_needed(&app);
+
+    let projects = library.projects()?;
+    tauri::async_runtime::spawn(async move {
+        for project in projects {
+            match reconcile_project(&app, &project.name, false).await {
+                Ok(changes) => {
+                    let _ = events::emit_project_reconciled(&app, events::ProjectReconciledEvent { project: project.name, changes });
+                }
+                Err(e) => warn!("⚠️  Reconcile of {} failed: {}", project.name, e),
+            }
+        }
+    });
     
     Ok(())
 }
 
+/// Create the Default project in an output folder that doesn't have one
+pub fn ensure_default_project(output_folder: &Path) {
+    if output_folder.exists() && !output_folder.join("Default").exists() {
+        info!("📁 Creating default project");
+        match Library::new(output_folder).ensure_project("Default") {
+            Ok(_) => info!("✅ Default project created with timeline.json"),
+            Err(e) => warn!("⚠️  Failed to create default project: {}", e),
+        }
+    }
+}
+
+/// Watch the output folder, moving an existing watcher there
+///
+/// If the new folder can't be watched, an existing watcher stays on the old one.
+pub fn watch_output_folder(app: &AppHandle, state: &AppState, output_folder: PathBuf) -> Result<(), String> {
+    let mut control = state.watcher_control.lock().map_err(|e| format!("Failed to lock watcher control: {}", e))?;
+    let result = match control.as_mut() {
+        Some(watcher) if watcher.folder() == output_folder => Ok(()),
+        Some(watcher) => watcher.retarget(app.clone(), output_folder),
+        None => fs_watcher::watch_output_folder(app.clone(), output_folder).map(|watcher| *control = Some(watcher)),
+    };
+    result.map_err(|e| format!("Failed to start filesystem watcher: {}", e))
+}
+
 /// Set microphone enabled state
 #[tauri::command]
-pub async fn set_mic_enabled(enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn set_mic_enabled(enabled: bool, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
     {
         let mut mic = state.mic_enabled.lock()
             .map_err(|e| format!("Failed to lock mic_enabled: {}", e))?;
         *mic = enabled;
     }
+    state.save_settings();
     
     // Re-initialize capturer with new mic setting if we have a project selected
-    reinitialize_capturer_if_needed(state).await?;
+    recorder::reinitialize_capturer_if_needed(&app);
     
     Ok(())
 }
 
+/// Whether recordings skip unchanged frames (variable frame rate)
+#[tauri::command]
+pub async fn get_variable_frame_rate(state: State<'_, AppState>) -> Result<bool, String> {
+    let enabled = state.variable_frame_rate.lock().map_err(|e| format!("Failed to lock variable_frame_rate: {}", e))?;
+    Ok(*enabled)
+}
+
+/// Record with a variable frame rate (smaller files for mostly static screens) or a constant one
+#[tauri::command]
+pub async fn set_variable_frame_rate(enabled: bool, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let mut variable_frame_rate = state.variable_frame_rate.lock().map_err(|e| format!("Failed to lock variable_frame_rate: {}", e))?;
+        *variable_frame_rate = enabled;
+    }
+    state.save_settings();
+    info!("🎞️  Variable frame rate {}", if enabled { "enabled" } else { "disabled" });
+
+    // The armed capturer was configured with the old frame rate mode
+    recorder::reinitialize_capturer_if_needed(&app);
+    Ok(())
+}
+
+/// Most seconds between keyframes in new recordings
+#[tauri::command]
+pub async fn get_keyframe_interval_secs(state: State<'_, AppState>) -> Result<u32, String> {
+    let secs = state.keyframe_interval_secs.lock().map_err(|e| format!("Failed to lock keyframe_interval_secs: {}", e))?;
+    Ok(*secs)
+}
+
+/// Set how often recordings get a keyframe (shorter = more precise seeking, larger files)
+#[tauri::command]
+pub async fn set_keyframe_interval_secs(secs: u32, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    if !(settings::MIN_KEYFRAME_INTERVAL_SECS..=settings::MAX_KEYFRAME_INTERVAL_SECS).contains(&secs) {
+        return Err(format!(
+            "Keyframe interval must be between {} and {} seconds",
+            settings::MIN_KEYFRAME_INTERVAL_SECS, settings::MAX_KEYFRAME_INTERVAL_SECS
+        ));
+    }
+    {
+        let mut keyframe_interval_secs = state.keyframe_interval_secs.lock().map_err(|e| format!("Failed to lock keyframe_interval_secs: {}", e))?;
+        *keyframe_interval_secs = secs;
+    }
+    state.save_settings();
+    info!("🎞️  Keyframe every {}s at most", secs);
+
+    // The armed capturer was configured with the old interval
+    recorder::reinitialize_capturer_if_needed(&app);
+    Ok(())
+}
+
 /// Get available audio input devices
 #[tauri::command]
 pub async fn get_audio_devices() -> Result<Vec<screen_capture::AudioDevice>, String> {
@@ -502,72 +364,18 @@ pub async fn get_audio_devices() -> Result<Vec<screen_capture::AudioDevice>, Str
 
 /// Set selected audio device
 #[tauri::command]
-pub async fn set_audio_device(device_id: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn set_audio_device(device_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
     {
         let mut device = state.selected_audio_device.lock()
             .map_err(|e| format!("Failed to lock selected_audio_device: {}", e))?;
         *device = Some(device_id.clone());
     }
+    state.save_settings();
     
     info!("🎤 Audio device changed to: {}", device_id);
     
     // Re-initialize capturer with new device if we have a project selected
-    reinitialize_capturer_if_needed(state).await?;
-    
-    Ok(())
-}
-
-/// Helper function to re-initialize capturer when settings change
-async fn reinitialize_capturer_if_needed(state: State<'_, AppState>) -> Result<(), String> {
-    let current_project = {
-        let project = state.current_project.lock()
-            .map_err(|e| format!("Failed to lock current_project: {}", e))?;
-        project.clone()
-    };
-    
-    if let Some(project_name) = current_project {
-        info!("🔄 Re-initializing capturer due to settings change...");
-        
-        let output_folder = {
-            let folder = state.output_folder.lock()
-                .map_err(|e| format!("Failed to lock output_folder: {}", e))?;
-            folder.clone()
-        };
-        
-        let output_path = output_folder.join(&project_name);
-        
-        let mic_enabled = {
-            let mic = state.mic_enabled.lock()
-                .map_err(|e| format!("Failed to lock mic_enabled: {}", e))?;
-            *mic
-        };
-        
-        let audio_device_id = {
-            let device = state.selected_audio_device.lock()
-                .map_err(|e| format!("Failed to lock selected_audio_device: {}", e))?;
-            device.clone()
-        };
-        
-        let capture_region = {
-            let region = state.capture_region.lock()
-                .map_err(|e| format!("Failed to lock capture_region: {}", e))?;
-            *region
-        };
-        
-        // Create and pre-initialize new capturer
-        let mut capturer = ScreenCapturer::new(output_path, mic_enabled, audio_device_id);
-        capturer.pre_initialize(capture_region).await
-            .map_err(|e| format!("Failed to re-initialize recorder: {}", e))?;
-        
-        info!("✅ Capturer re-initialized");
-        
-        // Store in state
-        {
-            let mut cap = state.capturer.lock()
-                .map_err(|e| format!("Failed to lock capturer: {}", e))?;
-            *cap = Some(capturer);
-        }
-    }
+    recorder::reinitialize_capturer_if_needed(&app);
     
     Ok(())
 }
@@ -588,6 +396,18 @@ pub async fn authorize_capture() -> Result<String, String> {
     }
 }
 
+/// Get the persisted settings (as loaded at startup plus any changes since)
+#[tauri::command]
+pub fn get_settings(state: State<AppState>) -> Result<Settings, String> {
+    let mut settings = state.settings()?;
+    // The frontend always wants the effective folder, even when it's the default
+    if settings.output_folder.is_none() {
+        settings.output_folder = Some(state.output_folder.lock()
+            .map_err(|e| format!("Failed to lock output_folder: {}", e))?.clone());
+    }
+    Ok(settings)
+}
+
 /// Get current output folder
 #[tauri::command]
 pub fn get_output_folder(state: State<AppState>) -> Result<String, String> {
@@ -615,20 +435,188 @@ pub async fn init_hotkey() -> Result<String, String> {
     }
 }
 
-/// Start recording manually (for testing without hotkey)
+/// Get the current hotkey for every action
+#[tauri::command]
+pub fn get_hotkeys(state: State<AppState>) -> Result<Vec<HotkeyBinding>, String> {
+    let bindings = state.hotkeys.lock()
+        .map_err(|e| format!("Failed to lock hotkeys: {}", e))?;
+    Ok(bindings.to_list())
+}
+
+/// Rebind one action's hotkey
+///
+/// Fails without changing anything if the accelerator is invalid, already
+/// used by another action, or can't be registered with the OS.
 #[tauri::command]
-pub async fn start_recording(_state: State<'_, AppState>) -> Result<(), String> {
-    debug!("Manual start recording triggered");
-    // TODO: Implement actual recording start
+pub fn set_hotkey(
+    action: HotkeyAction,
+    accelerator: String,
+    app: AppHandle,
+    state: State<AppState>,
+) -> Result<Vec<HotkeyBinding>, String> {
+    rebind(&app, &state, |bindings| bindings.set(action, &accelerator))
+}
+
+/// Restore one action's default hotkey, or all of them if no action is given
+#[tauri::command]
+pub fn reset_hotkeys(
+    action: Option<HotkeyAction>,
+    app: AppHandle,
+    state: State<AppState>,
+) -> Result<Vec<HotkeyBinding>, String> {
+    rebind(&app, &state, |bindings| {
+        match action {
+            Some(action) => bindings.set(action, action.default_accelerator()),
+            None => {
+                *bindings = Bindings::default();
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Get how the record hotkey starts and stops recordings
+#[tauri::command]
+pub fn get_trigger_mode(state: State<AppState>) -> Result<TriggerMode, String> {
+    let trigger = state.trigger.lock()
+        .map_err(|e| format!("Failed to lock trigger: {}", e))?;
+    Ok(trigger.mode())
+}
+
+/// Set how the record hotkey starts and stops recordings
+/// (a recording in progress finishes the way it was started)
+#[tauri::command]
+pub fn set_trigger_mode(mode: TriggerMode, app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    {
+        let mut trigger = state.trigger.lock()
+            .map_err(|e| format!("Failed to lock trigger: {}", e))?;
+        trigger.set_mode(mode);
+    }
+    info!("🎚️ Trigger mode set to {:?}", mode);
+    state.save_settings();
+    let _ = events::emit_trigger_mode(&app, mode);
     Ok(())
 }
 
-/// Stop recording manually (for testing without hotkey)
+/// Apply a change to the bindings and re-register whatever it touched
+///
+/// Registration happens before the new bindings are stored; if the OS
+/// refuses a hotkey, the old ones are put back and the error is returned.
+fn rebind(
+    app: &AppHandle,
+    state: &AppState,
+    change: impl FnOnce(&mut Bindings) -> Result<(), String>,
+) -> Result<Vec<HotkeyBinding>, String> {
+    let mut hotkeys = state.hotkeys.lock()
+        .map_err(|e| format!("Failed to lock hotkeys: {}", e))?;
+
+    let mut updated = hotkeys.clone();
+    change(&mut updated)?;
+
+    let changed: Vec<HotkeyAction> = HotkeyAction::ALL
+        .into_iter()
+        .filter(|action| hotkeys.get(*action) != updated.get(*action))
+        .collect();
+
+    // Unregister everything first so actions can swap keys
+    for action in &changed {
+        unregister_hotkey(app, hotkeys.get(*action));
+    }
+
+    let mut registered = Vec::new();
+    for action in &changed {
+        match register_hotkey(app, *action, updated.get(*action)) {
+            Ok(()) => registered.push(*action),
+            Err(e) => {
+                for action in &registered {
+                    unregister_hotkey(app, updated.get(*action));
+                }
+                for action in &changed {
+                    if let Err(e) = register_hotkey(app, *action, hotkeys.get(*action)) {
+                        warn!("⚠️  {}", e);
+                    }
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    *hotkeys = updated;
+    let list = hotkeys.to_list();
+    drop(hotkeys); // save_settings locks it again
+
+    state.save_settings();
+    Ok(list)
+}
+
+/// Start recording manually (UI button, automation) - same pipeline as the record hotkey
+///
+/// The optional `label` becomes the clip's timeline label and fills the
+/// `{label}` token of the filename template.
 #[tauri::command]
-pub async fn stop_recording(_state: State<'_, AppState>) -> Result<String, String> {
-    debug!("Manual stop recording triggered");
-    // TODO: Implement actual recording stop
-    Ok("Recording stopped".to_string())
+pub async fn start_recording(label: Option<String>, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    info!("🎬 Manual start recording requested");
+    {
+        let project = state.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))?;
+        if project.is_none() {
+            return Err("No project selected".to_string());
+        }
+        let controller = state.recording.lock().map_err(|e| format!("Failed to lock recording controller: {}", e))?;
+        if controller.wants_recording() {
+            return Err("Already recording".to_string());
+        }
+    }
+
+    let label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+    recorder::begin_recording(&app, label);
+    Ok(())
+}
+
+/// Stop recording manually and wait for the clip to be saved
+///
+/// Returns the clip's path and the timeline entry it was added as.
+#[tauri::command]
+pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Result<SavedClip, String> {
+    info!("⏹️  Manual stop recording requested");
+    let saved = {
+        let controller = state.recording.lock().map_err(|e| format!("Failed to lock recording controller: {}", e))?;
+        if !controller.wants_recording() {
+            return Err("Not recording".to_string());
+        }
+
+        // Capture is running (or starting), so stopping it will produce a clip.
+        // Registered while the controller is locked so the clip can't slip past us.
+        if matches!(controller.state(), RecordingState::Starting | RecordingState::Recording) {
+            let (tx, rx) = oneshot::channel();
+            state.clip_waiters.lock().map_err(|e| format!("Failed to lock clip waiters: {}", e))?.push(tx);
+            Some(rx)
+        } else {
+            None
+        }
+    };
+
+    // Don't leave the record key thinking it still owns a recording
+    if let Ok(mut trigger) = state.trigger.lock() {
+        trigger.reset();
+    }
+    recorder::finish_recording(&app);
+
+    match saved {
+        Some(rx) => rx.await.map_err(|_| "Recording ended without being saved".to_string())?,
+        // The start was still queued behind initialization; dropping it leaves nothing to save
+        None => Err("Recording stopped before it started".to_string()),
+    }
+}
+
+/// Cancel the current recording: the recorder is stopped and the clip deleted
+/// instead of being added to the timeline
+#[tauri::command]
+pub async fn cancel_recording(app: AppHandle) -> Result<(), String> {
+    if recorder::cancel_current_recording(&app) {
+        Ok(())
+    } else {
+        Err("Not recording".to_string())
+    }
 }
 
 /// Performance settings structure
@@ -642,7 +630,7 @@ pub struct PerformanceSettings {
 #[tauri::command]
 pub async fn get_performance_settings() -> Result<PerformanceSettings, String> {
     Ok(PerformanceSettings {
-        hotkey_to_recording_threshold_ms: HOTKEY_TO_RECORDING_THRESHOLD_MS,
+        hotkey_to_recording_threshold_ms: recorder::HOTKEY_TO_RECORDING_THRESHOLD_MS,
     })
 }
 
@@ -738,6 +726,7 @@ pub async fn open_file(path: String) -> Result<(), String> {
 /// Set capture region for recording
 #[tauri::command]
 pub async fn set_capture_region(
+    app: AppHandle,
     state: State<'_, AppState>,
     x: u32,
     y: u32,
@@ -749,24 +738,26 @@ pub async fn set_capture_region(
         *region = Some((x, y, width, height));
         info!("📏 Capture region set: {}x{} at ({}, {})", width, height, x, y);
     }
+    state.save_settings();
     
     // Re-initialize capturer with new region
-    reinitialize_capturer_if_needed(state).await?;
+    recorder::reinitialize_capturer_if_needed(&app);
     
     Ok(())
 }
 
 /// Clear capture region (return to full screen)
 #[tauri::command]
-pub async fn clear_capture_region(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn clear_capture_region(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
     {
         let mut region = state.capture_region.lock().unwrap();
         *region = None;
         info!("🖥️ Capture region cleared - using full screen");
     }
+    state.save_settings();
     
     // Re-initialize capturer for full screen
-    reinitialize_capturer_if_needed(state).await?;
+    recorder::reinitialize_capturer_if_needed(&app);
     
     Ok(())
 }
@@ -829,162 +820,12 @@ pub async fn close_region_selector(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-// Project management structures
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Project {
-    pub name: String,
-    pub created_at: String,
-    pub video_count: u32,
-    pub last_modified: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TimelineEntry {
-    pub id: String,
-    pub filename: String,
-    #[serde(rename = "recordedAt", alias = "recorded_at")]
-    pub recorded_at: String,
-    #[serde(rename = "durationMs", alias = "duration_ms")]
-    pub duration_ms: u64,
-    pub aspect_ratio: String,
-    pub resolution: Resolution,
-    pub mic_enabled: bool,
-    pub notes: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub checksum: Option<String>, // SHA256 hash for file integrity and rename detection
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Resolution {
-    pub width: u32,
-    pub height: u32,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ProjectTimeline {
-    #[serde(rename = "projectName", alias = "project_name")]
-    pub project_name: String,
-    #[serde(rename = "createdAt", alias = "created_at")]
-    pub created_at: String,
-    #[serde(rename = "lastModified", alias = "last_modified")]
-    pub last_modified: String,
-    pub entries: Vec<TimelineEntry>,
-    pub metadata: TimelineMetadata,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TimelineMetadata {
-    #[serde(rename = "totalVideos", alias = "total_videos")]
-    pub total_videos: u32,
-    #[serde(rename = "totalDuration", alias = "total_duration")]
-    pub total_duration: u64,
-    #[serde(rename = "defaultAspectRatio", alias = "default_aspect_ratio")]
-    pub default_aspect_ratio: Option<String>,
-    pub tags: Option<Vec<String>>,
-}
-
 /// Get all available projects in the output folder
 #[tauri::command]
-pub async fn get_projects(state: State<'_, AppState>) -> Result<Vec<Project>, String> {
-    use log::{info, debug};
-    
+pub async fn get_projects(state: State<'_, AppState>) -> Result<Vec<ProjectSummary>, String> {
     info!("🔍 Starting get_projects");
-    
-    let output_folder = {
-        let folder = state.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?;
-        folder.clone()
-    };
 
-    debug!("📁 Output folder: {:?}", output_folder);
-
-    let mut projects = Vec::new();
-
-    if !output_folder.exists() {
-        info!("📁 Output folder doesn't exist, returning empty list");
-        return Ok(projects);
-    }
-
-    let entries = fs::read_dir(&output_folder).map_err(|e| format!("Failed to read output folder: {}", e))?;
-    
-    debug!("📂 Reading directory entries...");
-    let mut dir_count = 0;
-
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            dir_count += 1;
-            let project_name = path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("Unknown")
-                .to_string();
-
-            debug!("📁 Processing project directory: {}", project_name);
-
-            // Check for timeline.json file
-            let timeline_path = path.join("timeline.json");
-            
-            if timeline_path.exists() {
-                debug!("📄 Found timeline.json for {}", project_name);
-                // Read timeline to get project info
-                match fs::read_to_string(&timeline_path) {
-                    Ok(content) => {
-                        debug!("📖 Read timeline content ({} bytes) for {}", content.len(), project_name);
-                        match serde_json::from_str::<ProjectTimeline>(&content) {
-                            Ok(timeline) => {
-                                debug!("✅ Parsed timeline for {} ({} videos)", project_name, timeline.metadata.total_videos);
-                                projects.push(Project {
-                                    name: project_name,
-                                    created_at: timeline.created_at,
-                                    video_count: timeline.metadata.total_videos,
-                                    last_modified: timeline.last_modified,
-                                });
-                            }
-                            Err(e) => {
-                                debug!("❌ Failed to parse timeline JSON for {}: {}", project_name, e);
-                                // Invalid timeline, create default project entry
-                                let now = chrono::Utc::now().to_rfc3339();
-                                projects.push(Project {
-                                    name: project_name,
-                                    created_at: now.clone(),
-                                    video_count: 0,
-                                    last_modified: now,
-                                });
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        debug!("❌ Failed to read timeline file for {}: {}", project_name, e);
-                        // Can't read timeline, create default project entry
-                        let now = chrono::Utc::now().to_rfc3339();
-                        projects.push(Project {
-                            name: project_name,
-                            created_at: now.clone(),
-                            video_count: 0,
-                            last_modified: now,
-                        });
-                    }
-                }
-            } else {
-                debug!("📄 No timeline.json found for {}, creating default entry", project_name);
-                // No timeline file, create default project entry
-                let now = chrono::Utc::now().to_rfc3339();
-                projects.push(Project {
-                    name: project_name,
-                    created_at: now.clone(),
-                    video_count: 0,
-                    last_modified: now,
-                });
-            }
-        }
-    }
-
-    debug!("📋 Found {} project directories total", dir_count);
-    
-    // Sort projects by last modified date (newest first)
-    debug!("🔄 Sorting {} projects by last modified date", projects.len());
-    projects.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+    let projects = state.library()?.projects()?;
 
     info!("✅ Finished get_projects: {} projects returned", projects.len());
     Ok(projects)
@@ -993,58 +834,14 @@ pub async fn get_projects(state: State<'_, AppState>) -> Result<Vec<Project>, St
 /// Create a new project
 #[tauri::command]
 pub async fn create_project(project_name: String, state: State<'_, AppState>) -> Result<(), String> {
-    if project_name.trim().is_empty() {
-        return Err("Project name cannot be empty".to_string());
-    }
-
-    let project_name = project_name.trim().to_string();
-
-    // Validate project name (no special characters that would cause filesystem issues)
-    if project_name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
-        return Err("Project name contains invalid characters".to_string());
-    }
-
-    let output_folder = {
-        let folder = state.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?;
-        folder.clone()
-    };
-
-    let project_folder = output_folder.join(&project_name);
-
-    if project_folder.exists() {
-        return Err("Project already exists".to_string());
-    }
-
-    // Create project folder
-    fs::create_dir_all(&project_folder).map_err(|e| format!("Failed to create project folder: {}", e))?;
-
-    // Create initial timeline.json
-    let now = chrono::Utc::now().to_rfc3339();
-    let timeline = ProjectTimeline {
-        project_name: project_name.clone(),
-        created_at: now.clone(),
-        last_modified: now,
-        entries: Vec::new(),
-        metadata: TimelineMetadata {
-            total_videos: 0,
-            total_duration: 0,
-            default_aspect_ratio: None,
-            tags: None,
-        },
-    };
-
-    let timeline_path = project_folder.join("timeline.json");
-    let timeline_json = serde_json::to_string_pretty(&timeline)
-        .map_err(|e| format!("Failed to serialize timeline: {}", e))?;
-
-    fs::write(&timeline_path, timeline_json)
-        .map_err(|e| format!("Failed to write timeline.json: {}", e))?;
+    let project = state.library()?.create_project(&project_name)?;
 
     // Set as current project
     {
         let mut current = state.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))?;
-        *current = Some(project_name);
+        *current = Some(project.name().to_string());
     }
+    state.save_settings();
 
     Ok(())
 }
@@ -1074,101 +871,18 @@ pub async fn set_current_project(project_name: String, app: AppHandle, state: St
         let mut current = state.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))?;
         *current = Some(project_name.clone());
     }
+    state.save_settings();
     
     let project_set_time = start_time.elapsed();
     debug!("📝 Project state updated in {:.1}ms", project_set_time.as_millis() as f32);
     
-    // Pre-initialize capturer for instant recording startup
-    info!("⚡ Pre-initializing capturer for project: {}", project_name);
-    
-    // Set initializing flag
-    IS_INITIALIZING.store(true, Ordering::SeqCst);
-    
-    let output_path = output_folder.join(&project_name);
-    
-    let mic_enabled = {
-        let mic = state.mic_enabled.lock().map_err(|e| format!("Failed to lock mic_enabled: {}", e))?;
-        *mic
-    };
-    
-    let audio_device_id = {
-        let device = state.selected_audio_device.lock().map_err(|e| format!("Failed to lock selected_audio_device: {}", e))?;
-        device.clone()
-    };
-    
-    let capture_region = {
-        let region = state.capture_region.lock().map_err(|e| format!("Failed to lock capture_region: {}", e))?;
-        *region
-    };
-    
-    let capturer_create_time = start_time.elapsed();
-    debug!("📱 Creating capturer after {:.1}ms", capturer_create_time.as_millis() as f32);
-    
-    let capturer_init_start = start_time.elapsed();
-    debug!("⚡ Starting capturer pre-initialization in background after {:.1}ms (this may take 2-3 seconds)", capturer_init_start.as_millis() as f32);
-    
-    // Update status to initializing
-    {
-        if let Ok(mut status) = state.pre_init_status.lock() {
-            *status = PreInitStatus::Initializing;
-            let _ = events::emit_pre_init_status(&app, "Initializing");
-        }
-        // Update last activity
-        if let Ok(mut activity) = state.last_activity.lock() {
-            *activity = Instant::now();
-        }
+    if let Ok(mut activity) = state.last_activity.lock() {
+        *activity = Instant::now();
     }
-    
-    // Clone app handle for async block
-    let app_clone = app.clone();
-    
-    // Pre-initialize in background (don't block UI)
-    tauri::async_runtime::spawn(async move {
-        // Create capturer inside the background task
-        let mut capturer = ScreenCapturer::new(output_path, mic_enabled, audio_device_id);
-        
-        let bg_start = std::time::Instant::now();
-        match capturer.pre_initialize(capture_region).await {
-            Ok(()) => {
-                let bg_elapsed = bg_start.elapsed();
-                info!("✅ Capturer pre-initialized in background in {:.1}ms and ready for instant recording", bg_elapsed.as_millis() as f32);
-                
-                // Store the initialized capturer in state
-                if let Some(app_state) = app.try_state::<AppState>() {
-                    if let Ok(mut cap) = app_state.capturer.lock() {
-                        *cap = Some(capturer);
-                    } else {
-                        warn!("⚠️  Failed to lock capturer state");
-                    }
-                    
-                    // Update status to ready
-                    if let Ok(mut status) = app_state.pre_init_status.lock() {
-                        *status = PreInitStatus::Ready;
-                        let _ = events::emit_pre_init_status(&app_clone, "Ready");
-                    }
-                } else {
-                    warn!("⚠️  Failed to get app state");
-                }
-                
-                // Clear initializing flag - ready to record
-                IS_INITIALIZING.store(false, Ordering::SeqCst);
-            }
-            Err(e) => {
-                let bg_elapsed = bg_start.elapsed();
-                warn!("⚠️  Failed to pre-initialize capturer in background after {:.1}ms: {}", bg_elapsed.as_millis() as f32, e);
-                
-                // Update status back to not initialized on error
-                if let Some(app_state) = app_clone.try_state::<AppState>() {
-                    if let Ok(mut status) = app_state.pre_init_status.lock() {
-                        *status = PreInitStatus::NotInitialized;
-                        let _ = events::emit_pre_init_status(&app_clone, "NotInitialized");
-                    }
-                }
-                
-                IS_INITIALIZING.store(false, Ordering::SeqCst); // Clear flag on error
-            }
-        }
-    });
+
+    // Pre-initialize capturer for instant recording startup (in the background, don't block UI)
+    info!("⚡ Pre-initializing capturer for project: {}", project_name);
+    recorder::recording_event(&app, RecordingEvent::Reconfigure);
 
     let total_time = start_time.elapsed();
     info!("🎯 set_current_project completed in {:.1}ms total (capturer initializing in background)", total_time.as_millis() as f32);
@@ -1188,341 +902,189 @@ pub async fn get_current_project(state: State<'_, AppState>) -> Result<Option<St
 
 /// Get timeline for a specific project
 #[tauri::command]
-pub async fn get_project_timeline(project_name: String, state: State<'_, AppState>) -> Result<ProjectTimeline, String> {
-    let output_folder = {
-        let folder = state.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?;
-        folder.clone()
-    };
-
-    let timeline_path = output_folder.join(&project_name).join("timeline.json");
-
-    if !timeline_path.exists() {
-        return Err("Timeline not found".to_string());
-    }
-
-    let content = fs::read_to_string(&timeline_path)
-        .map_err(|e| format!("Failed to read timeline: {}", e))?;
-
-    let timeline: ProjectTimeline = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse timeline: {}", e))?;
-
-    Ok(timeline)
-}
-
-/// Calculate SHA256 checksum of a file
-fn calculate_file_checksum(file_path: &PathBuf) -> Result<String, String> {
-    let mut file = fs::File::open(file_path)
-        .map_err(|e| format!("Failed to open file for checksum: {}", e))?;
-    
-    let mut hasher = Sha256::new();
-    let mut buffer = [0u8; 8192]; // 8KB buffer for efficient reading
-    
-    loop {
-        let bytes_read = file.read(&mut buffer)
-            .map_err(|e| format!("Failed to read file for checksum: {}", e))?;
-        
-        if bytes_read == 0 {
-            break;
-        }
-        
-        hasher.update(&buffer[..bytes_read]);
-    }
-    
-    let result = hasher.finalize();
-    Ok(format!("{:x}", result))
+pub async fn get_project_timeline(project_name: String, state: State<'_, AppState>) -> Result<Timeline, String> {
+    state.library()?.project(&project_name)?.timeline()
 }
 
 /// Save timeline for a specific project
+/// Returns the new lastModified timestamp
 #[tauri::command]
-pub async fn save_project_timeline(project_name: String, timeline: ProjectTimeline, state: State<'_, AppState>) -> Result<(), String> {
-    let output_folder = {
-        let folder = state.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?;
-        folder.clone()
-    };
-
-    let timeline_path = output_folder.join(&project_name).join("timeline.json");
-
-    // Update lastModified timestamp
-    let mut updated_timeline = timeline;
-    updated_timeline.last_modified = chrono::Utc::now().to_rfc3339();
-
-    let json = serde_json::to_string_pretty(&updated_timeline)
-        .map_err(|e| format!("Failed to serialize timeline: {}", e))?;
-
-    fs::write(&timeline_path, json)
-        .map_err(|e| format!("Failed to write timeline: {}", e))?;
-
-    Ok(())
+pub async fn save_project_timeline(project_name: String, timeline: Timeline, state: State<'_, AppState>) -> Result<String, String> {
+    // Rejects the save if the file changed since the frontend loaded it
+    // (e.g. a recording finished in between); the frontend reloads and retries
+    let saved = state.library()?.project(&project_name)?.save_timeline(timeline)?;
+    Ok(saved.last_modified)
 }
 
 /// Reconcile timeline with actual files in project folder
 /// This function:
-/// 1. Detects deleted files and removes them from timeline
-/// 2. Detects new files and adds them to timeline
-/// 3. Uses checksums to detect renamed files
-/// 4. Updates/calculates checksums for all files
+/// 1. Plans the changes: removed, renamed and added files, changed checksums
+///    (`pulse_core::Project::plan_reconcile`)
+/// 2. Applies them - unless they'd drop more of the timeline than the
+///    `max_removal_percent` setting allows (half by default), which
+///    usually means the folder is only partly there (e.g. an unmounted network
+///    drive). Then nothing changes: the plan is sent as a
+///    `reconcile-confirmation-required` event for the user to review and
+///    apply with `apply_project_reconcile`.
+/// 3. With `remove_short_clips`, soft-deletes clips shorter than the minimum
+///    clip length (their files are kept)
+/// 4. Generates missing thumbnails
+///
+/// Only new or changed files are hashed (progress is reported as
+/// `checksum-progress` events). Reconciles run one at a time, so the bursts
+/// of calls that follow `filesystem-changed` events don't hash a file twice.
 #[tauri::command]
-pub async fn reconcile_project_timeline(project_name: String, state: State<'_, AppState>) -> Result<u32, String> {
-    let output_folder = {
-        let folder = state.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?;
-        folder.clone()
-    };
-
-    let project_folder = output_folder.join(&project_name);
-    let timeline_path = project_folder.join("timeline.json");
-
-    if !project_folder.exists() {
-        return Err("Project folder does not exist".to_string());
-    }
+pub async fn reconcile_project_timeline(
+    project_name: String,
+    remove_short_clips: Option<bool>,
+    app: AppHandle,
+) -> Result<u32, String> {
+    reconcile_project(&app, &project_name, remove_short_clips.unwrap_or(false)).await
+}
 
-    // Read existing timeline or create new one
-    let mut timeline = if timeline_path.exists() {
-        let content = fs::read_to_string(&timeline_path)
-            .map_err(|e| format!("Failed to read timeline: {}", e))?;
-        serde_json::from_str::<ProjectTimeline>(&content)
-            .map_err(|e| format!("Failed to parse timeline: {}", e))?
+/// Reconcile a project's timeline with its folder (also used by the watcher's auto-reconcile)
+///
+/// A reconcile that would drop too much of the timeline is held back with a
+/// `reconcile-confirmation-required` event instead.
+pub async fn reconcile_project(app: &AppHandle, project_name: &str, remove_short_clips: bool) -> Result<u32, String> {
+    let state = app.state::<AppState>();
+    let project = state.library()?.project(project_name)?;
+    let project_name = project_name.to_string();
+    let app = app.clone();
+    let max_removal_percent = *state.max_removal_percent.lock().map_err(|e| format!("Failed to lock max_removal_percent: {}", e))?;
+    let min_duration_ms = if remove_short_clips {
+        *state.min_clip_duration_ms.lock().map_err(|e| format!("Failed to lock min_clip_duration_ms: {}", e))?
     } else {
-        let now = chrono::Utc::now().to_rfc3339();
-        ProjectTimeline {
-            project_name: project_name.clone(),
-            created_at: now.clone(),
-            last_modified: now,
-            entries: Vec::new(),
-            metadata: TimelineMetadata {
-                total_videos: 0,
-                total_duration: 0,
-                default_aspect_ratio: None,
-                tags: None,
-            },
-        }
+        0
     };
 
-    // Get all video files in the project folder with their checksums
-    let entries = fs::read_dir(&project_folder).map_err(|e| format!("Failed to read project folder: {}", e))?;
-    
-    let mut actual_files: HashMap<String, String> = HashMap::new(); // filename -> checksum
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-        let path = entry.path();
-        
-        if path.is_file() {
-            if let Some(extension) = path.extension() {
-                let ext_str = extension.to_string_lossy().to_lowercase();
-                if matches!(ext_str.as_str(), "mp4" | "mov" | "avi" | "mkv" | "webm" | "m4v") {
-                    if let Some(filename) = path.file_name() {
-                        let filename_str = filename.to_string_lossy().to_string();
-                        // Calculate checksum for the file
-                        if let Ok(checksum) = calculate_file_checksum(&path) {
-                            actual_files.insert(filename_str, checksum);
-                        } else {
-                            warn!("⚠️  Failed to calculate checksum for {}", filename_str);
-                        }
-                    }
-                }
-            }
+    let _hashing = state.hashing.lock().await;
+
+    // Checksums and video decoding are blocking work - keep them off the async executor
+    tauri::async_runtime::spawn_blocking(move || {
+        let plan = plan_reconcile_with_progress(&app, &project)?;
+        if plan.needs_confirmation(max_removal_percent) {
+            warn!("⚠️  Reconcile of {} would remove {} of {} entries - waiting for confirmation", project_name, plan.removed.len(), plan.total_entries);
+            let message = format!("Reconcile would remove {} of {} clips - review the changes first", plan.removed.len(), plan.total_entries);
+            let _ = events::emit_reconcile_confirmation_required(&app, events::ReconcileConfirmationEvent {
+                project: project_name,
+                plan,
+            });
+            return Err(message);
         }
-    }
-
-    let mut changes_count = 0u32;
+        let mut changes_count = project.apply_reconcile(&plan)?;
 
-    // Step 1: Remove deleted files from timeline
-    let original_count = timeline.entries.len();
-    timeline.entries.retain(|entry| {
-        let exists = actual_files.contains_key(&entry.filename);
-        if !exists {
-            info!("🗑️  Removed deleted file from timeline: {}", entry.filename);
-        }
-        exists
-    });
-    let deleted_count = original_count - timeline.entries.len();
-    changes_count += deleted_count as u32;
-
-    // Step 2: Build checksum maps for rename detection
-    let mut timeline_checksums: HashMap<String, usize> = HashMap::new(); // checksum -> index in entries
-    for (idx, entry) in timeline.entries.iter().enumerate() {
-        if let Some(checksum) = &entry.checksum {
-            timeline_checksums.insert(checksum.clone(), idx);
-        }
-    }
+        changes_count += project.delete_short_clips(min_duration_ms)?.len() as u32;
 
-    let mut actual_checksums: HashMap<String, String> = HashMap::new(); // checksum -> filename
-    for (filename, checksum) in &actual_files {
-        actual_checksums.insert(checksum.clone(), filename.clone());
-    }
+        changes_count += thumbnails::update_project(&project, false, false)?;
+        Ok(changes_count)
+    })
+    .await
+    .map_err(|e| format!("Reconcile task failed: {}", e))?
+}
 
-    // Step 3: Detect renames by matching checksums
-    for entry in timeline.entries.iter_mut() {
-        if let Some(old_checksum) = &entry.checksum {
-            // If the current filename doesn't exist but checksum matches another file
-            if !actual_files.contains_key(&entry.filename) {
-                if let Some(new_filename) = actual_checksums.get(old_checksum) {
-                    info!("📝 Detected rename: {} -> {}", entry.filename, new_filename);
-                    entry.filename = new_filename.clone();
-                    entry.notes = Some(format!("File renamed during reconciliation"));
-                    changes_count += 1;
-                }
-            }
-        }
-    }
+/// Whether changed projects are reconciled automatically
+#[tauri::command]
+pub async fn get_auto_reconcile(state: State<'_, AppState>) -> Result<bool, String> {
+    let enabled = state.auto_reconcile.lock().map_err(|e| format!("Failed to lock auto_reconcile: {}", e))?;
+    Ok(*enabled)
+}
 
-    // Step 4: Update checksums for existing entries
-    for entry in timeline.entries.iter_mut() {
-        if let Some(checksum) = actual_files.get(&entry.filename) {
-            if entry.checksum.is_none() || entry.checksum.as_ref() != Some(checksum) {
-                entry.checksum = Some(checksum.clone());
-            }
-        }
+/// Reconcile projects automatically when their files change (instead of leaving it to the UI)
+#[tauri::command]
+pub async fn set_auto_reconcile(enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let mut auto_reconcile = state.auto_reconcile.lock().map_err(|e| format!("Failed to lock auto_reconcile: {}", e))?;
+        *auto_reconcile = enabled;
     }
+    state.save_settings();
+    info!("🔄 Auto-reconcile {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
 
-    // Step 5: Add new files that aren't in timeline
-    let existing_filenames: std::collections::HashSet<String> = timeline.entries
-        .iter()
-        .map(|entry| entry.filename.clone())
-        .collect();
+/// Get the share of a timeline (percent) a reconcile may remove without confirmation
+#[tauri::command]
+pub async fn get_max_removal_percent(state: State<'_, AppState>) -> Result<u32, String> {
+    let percent = state.max_removal_percent.lock().map_err(|e| format!("Failed to lock max_removal_percent: {}", e))?;
+    Ok(*percent)
+}
 
-    for (filename, checksum) in &actual_files {
-        if !existing_filenames.contains(filename) {
-            let file_path = project_folder.join(filename);
-            
-            // Try to get file metadata
-            let metadata = fs::metadata(&file_path);
-            let (created_time, file_size) = if let Ok(meta) = metadata {
-                let created = meta.created().unwrap_or(std::time::SystemTime::now());
-                let size = meta.len();
-                (created, size)
-            } else {
-                (std::time::SystemTime::now(), 0)
-            };
-
-            // Convert system time to RFC3339 string
-            let created_rfc3339 = match created_time.duration_since(std::time::UNIX_EPOCH) {
-                Ok(duration) => {
-                    let datetime = chrono::DateTime::<chrono::Utc>::from_timestamp(duration.as_secs() as i64, 0);
-                    datetime.map(|dt| dt.to_rfc3339()).unwrap_or_else(|| chrono::Utc::now().to_rfc3339())
-                }
-                Err(_) => chrono::Utc::now().to_rfc3339(),
-            };
-
-            // Try to detect video properties from filename patterns
-            let (aspect_ratio, width, height) = detect_video_properties_from_filename(filename);
-
-            // Create timeline entry for new file
-            let entry_id = uuid::Uuid::new_v4().to_string();
-            
-            // Calculate actual video duration
-            let duration_ms = match calculate_video_duration(&file_path) {
-                Ok(duration) => {
-                    info!("📊 Calculated actual video duration: {}ms", duration);
-                    duration
-                },
-                Err(e) => {
-                    warn!("⚠️ Failed to calculate video duration, using file size estimate: {}", e);
-                    estimate_duration_from_file_size(file_size)
-                }
-            };
-
-            let entry = TimelineEntry {
-                id: entry_id,
-                filename: filename.clone(),
-                recorded_at: created_rfc3339,
-                duration_ms,
-                aspect_ratio,
-                resolution: Resolution { width, height },
-                mic_enabled: true,
-                notes: Some("Added during timeline reconciliation".to_string()),
-                checksum: Some(checksum.clone()),
-            };
-
-            info!("➕ Added new file to timeline: {}", filename);
-            timeline.entries.push(entry);
-            changes_count += 1;
-        }
+/// Set the share of a timeline (percent) a reconcile may remove without confirmation (100 never asks)
+#[tauri::command]
+pub async fn set_max_removal_percent(percent: u32, state: State<'_, AppState>) -> Result<(), String> {
+    if percent > settings::MAX_MAX_REMOVAL_PERCENT {
+        return Err(format!("Removal threshold must be between 0 and {}%", settings::MAX_MAX_REMOVAL_PERCENT));
     }
-
-    // Sort entries by recorded_at timestamp
-    timeline.entries.sort_by(|a, b| a.recorded_at.cmp(&b.recorded_at));
-
-    // Update timeline metadata
-    timeline.last_modified = chrono::Utc::now().to_rfc3339();
-    timeline.metadata.total_videos = timeline.entries.len() as u32;
-    timeline.metadata.total_duration = timeline.entries.iter().map(|e| e.duration_ms).sum();
-
-    // Only save timeline if changes were detected (avoid infinite filesystem watcher loop)
-    if changes_count > 0 {
-        let timeline_json = serde_json::to_string_pretty(&timeline)
-            .map_err(|e| format!("Failed to serialize timeline: {}", e))?;
-
-        fs::write(&timeline_path, timeline_json)
-            .map_err(|e| format!("Failed to write timeline.json: {}", e))?;
-        
-        info!("✅ Reconciliation complete: {} changes detected and saved", changes_count);
-    } else {
-        debug!("✅ Reconciliation complete: 0 changes detected (no file write needed)");
+    {
+        let mut max_removal_percent = state.max_removal_percent.lock().map_err(|e| format!("Failed to lock max_removal_percent: {}", e))?;
+        *max_removal_percent = percent;
     }
-    Ok(changes_count)
+    state.save_settings();
+    info!("🛡️  Reconciles removing more than {}% of a timeline now need confirmation", percent);
+    Ok(())
 }
 
-// Helper function to detect video properties from filename
-fn detect_video_properties_from_filename(filename: &str) -> (String, u32, u32) {
-    let lower = filename.to_lowercase();
-    
-    // Look for common resolution patterns in filename
-    if lower.contains("1920x1080") || lower.contains("1080p") || lower.contains("fhd") {
-        ("16:9".to_string(), 1920, 1080)
-    } else if lower.contains("2560x1440") || lower.contains("1440p") || lower.contains("qhd") {
-        ("16:9".to_string(), 2560, 1440)
-    } else if lower.contains("3840x2160") || lower.contains("4k") || lower.contains("uhd") {
-        ("16:9".to_string(), 3840, 2160)
-    } else if lower.contains("1080x1920") || lower.contains("vertical") || lower.contains("portrait") {
-        ("9:16".to_string(), 1080, 1920)
-    } else if lower.contains("1440x2560") {
-        ("9:16".to_string(), 1440, 2560)
-    } else if lower.contains("2160x3840") {
-        ("9:16".to_string(), 2160, 3840)
-    } else {
-        // Default to common recording resolution
-        ("16:9".to_string(), 1920, 1080)
-    }
+/// Dry run of `reconcile_project_timeline`: what would change, without changing anything
+#[tauri::command]
+pub async fn plan_project_reconcile(project_name: String, app: AppHandle, state: State<'_, AppState>) -> Result<ReconcilePlan, String> {
+    let project = state.library()?.project(&project_name)?;
+    let _hashing = state.hashing.lock().await;
+
+    tauri::async_runtime::spawn_blocking(move || plan_reconcile_with_progress(&app, &project))
+        .await
+        .map_err(|e| format!("Reconcile task failed: {}", e))?
 }
 
-// Helper function to calculate actual video duration from file
-#[cfg(target_os = "macos")]
-fn calculate_video_duration(file_path: &std::path::Path) -> Result<u64, String> {
-    use std::process::Command;
-    
-    // Use ffprobe to get actual video duration
-    let output = Command::new("ffprobe")
-        .args([
-            "-v", "quiet",
-            "-show_entries", "format=duration",
-            "-of", "csv=p=0",
-            file_path.to_str().ok_or("Invalid file path")?
-        ])
-        .output()
-        .map_err(|e| format!("Failed to run ffprobe: {}", e))?;
-    
-    if !output.status.success() {
-        return Err("ffprobe failed to read video duration".to_string());
+/// Apply a plan from `plan_project_reconcile` (or a held-back reconcile)
+///
+/// A plan that drops more of the timeline than `max_removal_percent` allows is refused unless
+/// `confirmed` is set. Changes that no longer hold (a missing file came
+/// back) are skipped.
+#[tauri::command]
+pub async fn apply_project_reconcile(
+    project_name: String,
+    plan: ReconcilePlan,
+    confirmed: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let max_removal_percent = *state.max_removal_percent.lock().map_err(|e| format!("Failed to lock max_removal_percent: {}", e))?;
+    if plan.needs_confirmation(max_removal_percent) && !confirmed.unwrap_or(false) {
+        return Err(format!(
+            "Reconcile would remove {} of {} clips - confirm to apply it",
+            plan.removed.len(),
+            plan.total_entries
+        ));
     }
-    
-    let duration_str = String::from_utf8_lossy(&output.stdout);
-    let duration_seconds: f64 = duration_str.trim().parse()
-        .map_err(|e| format!("Failed to parse duration: {}", e))?;
-    
-    Ok((duration_seconds * 1000.0) as u64) // Convert to milliseconds
-}
 
-#[cfg(not(target_os = "macos"))]
-fn calculate_video_duration(file_path: &std::path::Path) -> Result<u64, String> {
-    // Fallback for non-macOS platforms
-    estimate_duration_from_file_size(std::fs::metadata(file_path)?.len())
+    let project = state.library()?.project(&project_name)?;
+    let _hashing = state.hashing.lock().await;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let changes_count = project.apply_reconcile(&plan)?;
+        Ok(changes_count + thumbnails::update_project(&project, false, false)?)
+    })
+    .await
+    .map_err(|e| format!("Reconcile task failed: {}", e))?
 }
 
-// Helper function to estimate duration from file size (fallback)
-fn estimate_duration_from_file_size(file_size: u64) -> u64 {
-    // Very rough estimate: assume ~1MB per second for typical screen recordings
-    let estimated_seconds = (file_size / (1024 * 1024)).max(1); // At least 1 second
-    estimated_seconds * 1000 // Convert to milliseconds
+/// Plan a reconcile, reporting hashing progress as throttled `checksum-progress` events
+fn plan_reconcile_with_progress(app: &AppHandle, project: &Project) -> Result<ReconcilePlan, String> {
+    let mut last_progress = Instant::now();
+    project.plan_reconcile_with_progress(|progress| {
+        // Throttled, except for the final "done" report
+        if progress.current_file.is_some() && last_progress.elapsed() < CHECKSUM_PROGRESS_INTERVAL {
+            return;
+        }
+        last_progress = Instant::now();
+        let _ = events::emit_checksum_progress(app, events::ChecksumProgressEvent {
+            project: project.name().to_string(),
+            files_done: progress.files_done,
+            files_total: progress.files_total,
+            bytes_done: progress.bytes_done,
+            bytes_total: progress.bytes_total,
+            current_file: progress.current_file.clone(),
+        });
+    })
 }
 
 /// Add a recording entry to the current project's timeline
@@ -1535,8 +1097,11 @@ pub async fn add_timeline_entry(
     aspect_ratio: String,
     width: u32,
     height: u32,
+    markers: Option<Vec<u64>>,
+    label: Option<String>,
+    checksum: Option<String>,
     state: State<'_, AppState>
-) -> Result<(), String> {
+) -> Result<TimelineEntry, String> {
     let (output_folder, current_project) = {
         let folder = state.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?;
         let project = state.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))?;
@@ -1544,29 +1109,8 @@ pub async fn add_timeline_entry(
     };
 
     let project_name = current_project.ok_or("No current project set")?;
-    let timeline_path = output_folder.join(&project_name).join("timeline.json");
-
-    // Read existing timeline or create new one
-    let mut timeline = if timeline_path.exists() {
-        let content = fs::read_to_string(&timeline_path)
-            .map_err(|e| format!("Failed to read timeline: {}", e))?;
-        serde_json::from_str::<ProjectTimeline>(&content)
-            .map_err(|e| format!("Failed to parse timeline: {}", e))?
-    } else {
-        let now = chrono::Utc::now().to_rfc3339();
-        ProjectTimeline {
-            project_name: project_name.clone(),
-            created_at: now.clone(),
-            last_modified: now,
-            entries: Vec::new(),
-            metadata: TimelineMetadata {
-                total_videos: 0,
-                total_duration: 0,
-                default_aspect_ratio: None,
-                tags: None,
-            },
-        }
-    };
+    let project = Library::new(output_folder).ensure_project(&project_name)?;
+    let project_folder = project.folder().to_path_buf();
 
     // Get mic enabled state
     let mic_enabled = {
@@ -1574,54 +1118,101 @@ pub async fn add_timeline_entry(
         *mic
     };
 
-    // Calculate checksum for the newly recorded file
-    let file_path = output_folder.join(&project_name).join(&filename);
-    let checksum = calculate_file_checksum(&file_path).ok(); // Optional, may fail if file is still being written
+    // Recordings come with the checksum taken when they were published
+    let file_path = project_folder.join(&filename);
+    let checksum = checksum.or_else(|| checksum::file_checksum(&file_path).ok());
+
+    // Generate the poster frame off the async executor (decodes the video)
+    let thumbnail = {
+        let project_folder = project_folder.clone();
+        let filename = filename.clone();
+        tauri::async_runtime::spawn_blocking(move || thumbnails::generate_poster(&project_folder, &filename))
+            .await
+            .map_err(|e| format!("Thumbnail task failed: {}", e))?
+            .map_err(|e| warn!("⚠️ Failed to generate poster frame for {}: {}", file_path.display(), e))
+            .ok()
+    };
 
-    // Create new entry
-    let entry_id = uuid::Uuid::new_v4().to_string();
+    // Create new entry (with the clip id the recorder embedded, if any)
+    let entry_id = media::embedded_clip_id(&project_folder.join(&filename))
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
     let now = chrono::Utc::now().to_rfc3339();
 
     let entry = TimelineEntry {
         id: entry_id,
+        kind: EntryKind::Video,
         filename,
-        recorded_at: now.clone(),
+        recorded_at: now,
         duration_ms,
         aspect_ratio,
         resolution: Resolution { width, height },
         mic_enabled,
         notes: None,
         checksum,
+        thumbnail,
+        markers: markers.unwrap_or_default(),
+        label,
+        ..Default::default()
     };
 
-    // Add entry and update metadata
-    timeline.entries.push(entry);
-    timeline.last_modified = now;
-    timeline.metadata.total_videos = timeline.entries.len() as u32;
-    timeline.metadata.total_duration = timeline.entries.iter().map(|e| e.duration_ms).sum();
+    project.add_entry(entry.clone())?;
+    Ok(entry)
+}
+
+/// Regenerate thumbnails for every entry in a project
+/// Returns the number of entries that were updated
+#[tauri::command]
+pub async fn regenerate_thumbnails(project_name: String, include_filmstrip: bool, state: State<'_, AppState>) -> Result<u32, String> {
+    let project = state.library()?.project(&project_name)?;
 
+    info!("🖼️ Regenerating thumbnails for project: {}", project_name);
 
-    // Save updated timeline
-    let timeline_json = serde_json::to_string_pretty(&timeline)
-        .map_err(|e| format!("Failed to serialize timeline: {}", e))?;
+    let updated = tauri::async_runtime::spawn_blocking(move || thumbnails::update_project(&project, true, include_filmstrip))
+        .await
+        .map_err(|e| format!("Thumbnail task failed: {}", e))??;
 
-    fs::write(&timeline_path, timeline_json)
-        .map_err(|e| format!("Failed to write timeline.json: {}", e))?;
+    info!("✅ Regenerated thumbnails: {} entries updated", updated);
+    Ok(updated)
+}
 
-    Ok(())
+/// Export a timeline entry as an animated GIF or WebP
+/// Output goes to <project>/exports/<clip name>.<gif|webp>
+#[tauri::command]
+pub async fn export_clip(
+    project_name: String,
+    entry_id: String,
+    options: screen_capture::AnimationOptions,
+    state: State<'_, AppState>,
+) -> Result<screen_capture::AnimationExport, String> {
+    let project = state.library()?.project(&project_name)?;
+    let project_folder = project.folder();
+
+    let entry = project.find_entry(&entry_id)?;
+    if entry.kind != EntryKind::Video {
+        return Err("Only video clips can be exported as animations".to_string());
+    }
+
+    let input = project_folder.join(&entry.filename);
+    let exports_folder = project.exports_folder();
+    fs::create_dir_all(&exports_folder)
+        .map_err(|e| format!("Failed to create exports folder: {}", e))?;
+    let output = media::next_export_path(&exports_folder, &input, options.format.extension());
+
+    info!("🎞️ Exporting {} as {}", entry.filename, options.format.extension());
+
+    // Decoding and encoding are CPU-bound - keep them off the async executor
+    tauri::async_runtime::spawn_blocking(move || {
+        screen_capture::export_animation(&input, &output, &options)
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))?
 }
 
 /// Get the current pre-initialization status
 #[tauri::command]
 pub async fn get_pre_init_status(state: State<'_, AppState>) -> Result<String, String> {
-    let status = state.pre_init_status.lock().map_err(|e| format!("Failed to lock pre_init_status: {}", e))?;
-    let status_str = match *status {
-        PreInitStatus::NotInitialized => "not_initialized",
-        PreInitStatus::Initializing => "initializing", 
-        PreInitStatus::Ready => "ready",
-        PreInitStatus::ShuttingDown => "shutting_down",
-    };
-    Ok(status_str.to_string())
+    let controller = state.recording.lock().map_err(|e| format!("Failed to lock recording controller: {}", e))?;
+    Ok(recorder::displayed_pre_init_status(&state, &controller).as_str().to_string())
 }
 
 /// Get the idle timeout setting in minutes
@@ -1634,12 +1225,138 @@ pub async fn get_idle_timeout_mins(state: State<'_, AppState>) -> Result<u32, St
 /// Set the idle timeout setting in minutes
 #[tauri::command]
 pub async fn set_idle_timeout_mins(timeout_mins: u32, state: State<'_, AppState>) -> Result<(), String> {
-    let mut timeout = state.idle_timeout_mins.lock().map_err(|e| format!("Failed to lock idle_timeout_mins: {}", e))?;
-    *timeout = timeout_mins;
+    {
+        let mut timeout = state.idle_timeout_mins.lock().map_err(|e| format!("Failed to lock idle_timeout_mins: {}", e))?;
+        *timeout = timeout_mins;
+    }
+    state.save_settings();
     info!("⏰ Idle timeout set to {} minutes", timeout_mins);
     Ok(())
 }
 
+/// Get the minimum clip length in milliseconds (shorter recordings are discarded)
+#[tauri::command]
+pub async fn get_min_clip_duration_ms(state: State<'_, AppState>) -> Result<u64, String> {
+    let min_duration = state.min_clip_duration_ms.lock().map_err(|e| format!("Failed to lock min_clip_duration_ms: {}", e))?;
+    Ok(*min_duration)
+}
+
+/// Set the minimum clip length in milliseconds (0 keeps every recording)
+#[tauri::command]
+pub async fn set_min_clip_duration_ms(min_duration_ms: u64, state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let mut min_duration = state.min_clip_duration_ms.lock().map_err(|e| format!("Failed to lock min_clip_duration_ms: {}", e))?;
+        *min_duration = min_duration_ms;
+    }
+    state.save_settings();
+    info!("✂️  Minimum clip length set to {}ms", min_duration_ms);
+    Ok(())
+}
+
+/// Get the template new clips are named with (e.g. "recording-{seq}")
+#[tauri::command]
+pub async fn get_filename_template(state: State<'_, AppState>) -> Result<String, String> {
+    let template = state.filename_template.lock().map_err(|e| format!("Failed to lock filename_template: {}", e))?;
+    Ok(template.as_str().to_string())
+}
+
+/// Set the filename template for new clips
+///
+/// Tokens: {project}, {date}, {time}, {seq}, {label}, {uuid}. Applies from
+/// the next saved clip on; existing clips keep their names.
+#[tauri::command]
+pub async fn set_filename_template(template: String, state: State<'_, AppState>) -> Result<(), String> {
+    let template = FilenameTemplate::parse(&template)?;
+    info!("📝 Filename template set to {}", template.as_str());
+    *state.filename_template.lock().map_err(|e| format!("Failed to lock filename_template: {}", e))? = template;
+    state.save_settings();
+    Ok(())
+}
+
+/// Recordings that never finished (found at startup, or whose save failed)
+#[tauri::command]
+pub async fn get_recoverable_recordings(state: State<'_, AppState>) -> Result<Vec<RecoverableClip>, String> {
+    let clips = state.recoverable_clips.lock().map_err(|e| format!("Failed to lock recoverable clips: {}", e))?;
+    Ok(clips.clone())
+}
+
+/// Take a recoverable recording off the list, so it's only handled once
+///
+/// Only files on the list can be touched, which keeps the partial file of
+/// the recording in progress safe.
+fn take_recoverable(state: &AppState, project: &str, filename: &str) -> Result<RecoverableClip, String> {
+    let mut clips = state.recoverable_clips.lock().map_err(|e| format!("Failed to lock recoverable clips: {}", e))?;
+    let index = clips.iter()
+        .position(|c| c.project == project && c.filename == filename)
+        .ok_or_else(|| format!("No recoverable recording {} in {}", filename, project))?;
+    Ok(clips.remove(index))
+}
+
+/// Publish an interrupted recording and add it to its project's timeline
+#[tauri::command]
+pub async fn recover_recording(project: String, filename: String, state: State<'_, AppState>) -> Result<TimelineEntry, String> {
+    let clip = take_recoverable(&state, &project, &filename)?;
+    let template = state.filename_template.lock().map_err(|e| format!("Failed to lock filename_template: {}", e))?.clone();
+    let project = state.library()?.project(&clip.project)?;
+    let partial = project.folder().join(&clip.filename);
+
+    let result = tauri::async_runtime::spawn_blocking(move || project.recover_partial(&partial, &template))
+        .await
+        .map_err(|e| format!("Recovery task failed: {}", e))?;
+    if result.is_err() {
+        // Still on disk, so still recoverable (or discardable)
+        state.recoverable_clips.lock().map_err(|e| format!("Failed to lock recoverable clips: {}", e))?.push(clip);
+    }
+    result
+}
+
+/// Delete an interrupted recording
+#[tauri::command]
+pub async fn discard_recoverable_recording(project: String, filename: String, state: State<'_, AppState>) -> Result<(), String> {
+    let clip = take_recoverable(&state, &project, &filename)?;
+    let path = state.library()?.project(&clip.project)?.folder().join(&clip.filename);
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete {:?}: {}", path, e))?;
+    info!("🗑️  Discarded interrupted recording {:?}", path);
+    Ok(())
+}
+
+/// Maximum clip duration and free disk space limits, as set by the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingLimitSettings {
+    pub max_clip_duration_mins: u32, // 0 = no limit
+    pub min_free_disk_mb: u64, // 0 = no check
+}
+
+/// Get the recording limits
+#[tauri::command]
+pub async fn get_recording_limits(state: State<'_, AppState>) -> Result<RecordingLimitSettings, String> {
+    Ok(RecordingLimitSettings {
+        max_clip_duration_mins: *state.max_clip_duration_mins.lock().map_err(|e| format!("Failed to lock max_clip_duration_mins: {}", e))?,
+        min_free_disk_mb: *state.min_free_disk_mb.lock().map_err(|e| format!("Failed to lock min_free_disk_mb: {}", e))?,
+    })
+}
+
+/// Set the recording limits (applies from the next recording)
+#[tauri::command]
+pub async fn set_recording_limits(limits: RecordingLimitSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let mut max_mins = state.max_clip_duration_mins.lock().map_err(|e| format!("Failed to lock max_clip_duration_mins: {}", e))?;
+        *max_mins = limits.max_clip_duration_mins;
+        let mut min_free = state.min_free_disk_mb.lock().map_err(|e| format!("Failed to lock min_free_disk_mb: {}", e))?;
+        *min_free = limits.min_free_disk_mb;
+    }
+    state.save_settings();
+    info!("🛑 Recording limits set: max {} min, min free {} MB", limits.max_clip_duration_mins, limits.min_free_disk_mb);
+
+    // A lower free space minimum may be enough to arm the capturer now
+    let was_low_disk = std::mem::take(&mut *state.low_disk.lock().map_err(|e| format!("Failed to lock low_disk: {}", e))?);
+    if was_low_disk {
+        recorder::reinitialize_capturer_if_needed(&app);
+    }
+    Ok(())
+}
+
 /// Update last activity timestamp (called on user interactions)
 #[tauri::command] 
 pub async fn update_activity(state: State<'_, AppState>) -> Result<(), String> {
@@ -1650,87 +1367,15 @@ pub async fn update_activity(state: State<'_, AppState>) -> Result<(), String> {
 }
 
 /// Shutdown capturer due to idle timeout
+///
+/// Only an armed capturer is released; one that's recording or busy is left alone.
 #[tauri::command]
-pub async fn shutdown_idle_capturer(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn shutdown_idle_capturer(app: AppHandle) -> Result<(), String> {
     info!("💤 Shutting down capturer due to idle timeout");
-    
-    // Update status to shutting down
-    {
-        let mut status = state.pre_init_status.lock().map_err(|e| format!("Failed to lock pre_init_status: {}", e))?;
-        *status = PreInitStatus::ShuttingDown;
-        // Note: emit_pre_init_status needs AppHandle, but this command doesn't have it
-        // Status change will be detected by frontend polling or by idle checker
-    }
-    
-    // Clear the capturer
-    {
-        let mut capturer = state.capturer.lock().map_err(|e| format!("Failed to lock capturer: {}", e))?;
-        *capturer = None;
-    }
-    
-    // Update status to not initialized
-    {
-        let mut status = state.pre_init_status.lock().map_err(|e| format!("Failed to lock pre_init_status: {}", e))?;
-        *status = PreInitStatus::NotInitialized;
-        // Note: emit_pre_init_status needs AppHandle, but this command doesn't have it
-        // Status change will be detected by frontend polling or by idle checker
-    }
-    
-    // Clear initializing flag
-    IS_INITIALIZING.store(false, Ordering::SeqCst);
-    
-    info!("✅ Capturer shutdown complete");
+    recorder::recording_event(&app, RecordingEvent::Shutdown);
     Ok(())
 }
 
-/// Start the idle timeout checker background task
-pub fn start_idle_checker(app_handle: AppHandle) {
-    tauri::async_runtime::spawn(async move {
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60)); // Check every minute
-        
-        loop {
-            interval.tick().await;
-            
-            if let Some(state) = app_handle.try_state::<AppState>() {
-                // Check if we should shut down due to idle timeout
-                let should_shutdown = {
-                    let timeout_result = state.idle_timeout_mins.lock();
-                    let activity_result = state.last_activity.lock();
-                    let status_result = state.pre_init_status.lock();
-                    
-                    match (timeout_result, activity_result, status_result) {
-                        (Ok(timeout_mins), Ok(last_activity), Ok(status)) => {
-                            if *timeout_mins == 0 {
-                                false // Timeout disabled
-                            } else {
-                                let timeout_duration = Duration::from_secs((*timeout_mins as u64) * 60);
-                                let idle_time = last_activity.elapsed();
-                                
-                                idle_time > timeout_duration && matches!(*status, PreInitStatus::Ready)
-                            }
-                        }
-                        _ => false
-                    }
-                };
-                
-                if should_shutdown {
-                    info!("💤 Idle timeout reached, shutting down pre-initialized capturer");
-                    let _ = events::emit_pre_init_status(&app_handle, "ShuttingDown");
-                    let _ = app_handle.emit_to("main", "pre-init-idle-shutdown", ());
-                    
-                    // Call shutdown command
-                    if let Err(e) = shutdown_idle_capturer(app_handle.state::<AppState>()).await {
-                        warn!("Failed to shutdown idle capturer: {}", e);
-                    }
-                    let _ = events::emit_pre_init_status(&app_handle, "NotInitialized");
-                }
-            }
-        }
-    });
-    
-    info!("⏰ Idle timeout checker started");
-}
-
 /// Toggle pre-initialization state (manual control)
 #[tauri::command]
 pub async fn toggle_pre_init(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
@@ -1743,39 +1388,33 @@ pub async fn toggle_pre_init(app: AppHandle, state: State<'_, AppState>) -> Resu
     
     // Check current status
     let current_status = {
-        let status = state.pre_init_status.lock().map_err(|e| format!("Failed to lock pre_init_status: {}", e))?;
-        status.clone()
+        let controller = state.recording.lock().map_err(|e| format!("Failed to lock recording controller: {}", e))?;
+        recorder::displayed_pre_init_status(&state, &controller)
     };
     
     match current_status {
-        PreInitStatus::NotInitialized => {
+        // Low disk: retry, in case space was freed up
+        PreInitStatus::NotInitialized | PreInitStatus::LowDisk => {
             info!("🚀 Starting manual pre-initialization");
-            
-            // Get current project
-            let project_name = {
+            {
                 let current = state.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))?;
-                current.as_ref().ok_or("No current project set")?.clone()
-            };
-            
-            // Trigger pre-initialization using set_current_project logic
-            let _ = set_current_project(project_name, app, state).await;
-            Ok("Initializing".to_string())
+                if current.is_none() {
+                    return Err("No current project set".to_string());
+                }
+            }
+            recorder::recording_event(&app, RecordingEvent::Arm);
         }
         PreInitStatus::Initializing => {
             info!("⚠️  Pre-initialization already in progress");
-            Ok("Initializing".to_string())
         }
         PreInitStatus::Ready => {
             info!("💤 Shutting down pre-initialized capturer (manual)");
-            let _ = shutdown_idle_capturer(state).await?;
-            let _ = events::emit_pre_init_status(&app, "NotInitialized");
-            Ok("NotInitialized".to_string())
-        }
-        PreInitStatus::ShuttingDown => {
-            info!("⚠️  Capturer is already shutting down");
-            Ok("ShuttingDown".to_string())
+            recorder::recording_event(&app, RecordingEvent::Shutdown);
         }
     }
+
+    let controller = state.recording.lock().map_err(|e| format!("Failed to lock recording controller: {}", e))?;
+    Ok(recorder::displayed_pre_init_status(&state, &controller).as_str().to_string())
 }
 
 /// Handle window focus gained event
@@ -1800,36 +1439,7 @@ pub async fn on_window_focus_gained(app: AppHandle, state: State<'_, AppState>)
             *activity = Instant::now();
         }
         
-        // Check if we have a current project and restart pre-initialization
-        let current_project = {
-            let project = state.current_project.lock().map_err(|e| format!("Failed to lock current_project: {}", e))?;
-            project.clone()
-        };
-        
-        if let Some(project_name) = current_project {
-            // Check current pre-init status
-            let current_status = {
-                let status = state.pre_init_status.lock().map_err(|e| format!("Failed to lock pre_init_status: {}", e))?;
-                status.clone()
-            };
-            
-            // Only restart if not already initializing or ready
-            match current_status {
-                PreInitStatus::NotInitialized | PreInitStatus::ShuttingDown => {
-                    info!("🚀 Restarting pre-initialization for project: {}", project_name);
-                    // Trigger pre-initialization by setting current project again
-                    let _ = set_current_project(project_name, app, state).await;
-                }
-                PreInitStatus::Initializing => {
-                    info!("⚡ Pre-initialization already in progress, no restart needed");
-                }
-                PreInitStatus::Ready => {
-                    info!("✅ Pre-initialization already ready, no restart needed");
-                }
-            }
-        } else {
-            info!("📋 No current project set, skipping pre-initialization restart");
-        }
+        recorder::arm_for_current_project(&app);
     }
     
     Ok(())
@@ -1893,57 +1503,14 @@ fn handle_window_focus_gained_sync(app_handle: AppHandle) {
     };
     
     if !was_focused {
-        info!("🔄 Window came back into view - scheduling pre-initialization restart");
+        info!("🔄 Window came back into view - restarting pre-initialization");
         
         // Update activity timestamp
         if let Ok(mut activity) = state.last_activity.lock() {
             *activity = Instant::now();
         }
         
-        // Spawn async task to handle pre-initialization restart
-        let app_clone = app_handle.clone();
-        tauri::async_runtime::spawn(async move {
-            let state = app_clone.state::<AppState>();
-            
-            // Check if we have a current project and restart pre-initialization
-            let current_project = {
-                if let Ok(project) = state.current_project.lock() {
-                    project.clone()
-                } else {
-                    return; // Failed to lock
-                }
-            };
-            
-            if let Some(project_name) = current_project {
-                // Check current pre-init status
-                let current_status = {
-                    if let Ok(status) = state.pre_init_status.lock() {
-                        status.clone()
-                    } else {
-                        return; // Failed to lock
-                    }
-                };
-                
-                // Only restart if not already initializing or ready
-                match current_status {
-                    PreInitStatus::NotInitialized | PreInitStatus::ShuttingDown => {
-                        info!("🚀 Restarting pre-initialization for project: {}", project_name);
-                        // Trigger pre-initialization by setting current project again
-                        if let Err(e) = set_current_project(project_name, app_clone.clone(), state).await {
-                            warn!("Failed to restart pre-initialization: {}", e);
-                        }
-                    }
-                    PreInitStatus::Initializing => {
-                        info!("⚡ Pre-initialization already in progress, no restart needed");
-                    }
-                    PreInitStatus::Ready => {
-                        info!("✅ Pre-initialization already ready, no restart needed");
-                    }
-                }
-            } else {
-                info!("📋 No current project set, skipping pre-initialization restart");
-            }
-        });
+        recorder::arm_for_current_project(&app_handle);
     }
 }
 
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
+use crate::hotkey::trigger::TriggerMode;
+use crate::fs_changes::FileChange;
+use crate::limits::LimitKind;
+use pulse_core::ReconcilePlan;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CaptureRegion {
@@ -16,6 +20,73 @@ pub struct ClipSavedEvent {
     pub duration_ms: u64,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenshotSavedEvent {
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarkerEvent {
+    #[serde(rename = "offsetMs")]
+    pub offset_ms: u64,
+}
+
+/// A recording that was thrown away automatically, and why
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingDiscardedEvent {
+    pub reason: String, // "too-short"
+    pub duration_ms: u64,
+    pub min_duration_ms: u64,
+}
+
+/// Recording is close to (or hit) its maximum duration or the free disk space minimum
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingLimitEvent {
+    pub kind: LimitKind,
+    pub message: String,
+}
+
+/// Progress of hashing new or changed files while a project is reconciled
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumProgressEvent {
+    pub project: String,
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub current_file: Option<String>, // None once hashing is done
+}
+
+/// Media files or project folders changed in the output folder (one event per burst)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilesystemChangedEvent {
+    pub changes: Vec<FileChange>,
+    /// The backend reconciles the affected projects itself (auto-reconcile
+    /// setting) and reports each with a `project-reconciled` event
+    pub reconciling: bool,
+}
+
+/// The backend reconciled a project after filesystem changes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReconciledEvent {
+    pub project: String,
+    pub changes: u32,
+}
+
+/// A reconcile was held back because it would drop too much of a timeline
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileConfirmationEvent {
+    pub project: String,
+    pub plan: ReconcilePlan,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ErrorEvent {
     pub code: String,
@@ -40,6 +111,78 @@ pub fn emit_clip_saved(app: &AppHandle, event: ClipSavedEvent) -> Result<(), Str
         .map_err(|e| format!("Failed to emit clip-saved: {}", e))
 }
 
+/// Emit screenshot saved event to frontend
+pub fn emit_screenshot_saved(app: &AppHandle, event: ScreenshotSavedEvent) -> Result<(), String> {
+    app.emit("screenshot-saved", event)
+        .map_err(|e| format!("Failed to emit screenshot-saved: {}", e))
+}
+
+/// Emit marker added event to frontend (offset into the clip being recorded)
+pub fn emit_marker(app: &AppHandle, offset_ms: u64) -> Result<(), String> {
+    app.emit("recording-marker", MarkerEvent { offset_ms })
+        .map_err(|e| format!("Failed to emit recording-marker: {}", e))
+}
+
+/// Emit recording cancelled event to frontend (the clip was discarded)
+pub fn emit_recording_cancelled(app: &AppHandle) -> Result<(), String> {
+    app.emit("recording-cancelled", ())
+        .map_err(|e| format!("Failed to emit recording-cancelled: {}", e))
+}
+
+/// Emit recording discarded event to frontend (too short to keep - no timeline entry)
+pub fn emit_recording_discarded(app: &AppHandle, event: RecordingDiscardedEvent) -> Result<(), String> {
+    app.emit("recording-discarded", event)
+        .map_err(|e| format!("Failed to emit recording-discarded: {}", e))
+}
+
+/// Emit recording limit warning to frontend (the recording will stop soon)
+pub fn emit_recording_limit_warning(app: &AppHandle, event: RecordingLimitEvent) -> Result<(), String> {
+    app.emit("recording-limit-warning", event)
+        .map_err(|e| format!("Failed to emit recording-limit-warning: {}", e))
+}
+
+/// Emit recording limit reached event to frontend (the recording was stopped and saved)
+pub fn emit_recording_limit_reached(app: &AppHandle, event: RecordingLimitEvent) -> Result<(), String> {
+    app.emit("recording-limit-reached", event)
+        .map_err(|e| format!("Failed to emit recording-limit-reached: {}", e))
+}
+
+/// Emit checksum progress to frontend (while reconciling a project)
+pub fn emit_checksum_progress(app: &AppHandle, event: ChecksumProgressEvent) -> Result<(), String> {
+    app.emit("checksum-progress", event)
+        .map_err(|e| format!("Failed to emit checksum-progress: {}", e))
+}
+
+/// Emit filesystem changed event to frontend
+pub fn emit_filesystem_changed(app: &AppHandle, event: FilesystemChangedEvent) -> Result<(), String> {
+    app.emit("filesystem-changed", event)
+        .map_err(|e| format!("Failed to emit filesystem-changed: {}", e))
+}
+
+/// Emit project reconciled event to frontend (after an automatic reconcile)
+pub fn emit_project_reconciled(app: &AppHandle, event: ProjectReconciledEvent) -> Result<(), String> {
+    app.emit("project-reconciled", event)
+        .map_err(|e| format!("Failed to emit project-reconciled: {}", e))
+}
+
+/// Emit reconcile confirmation required event to frontend (the plan waits for the user to review it)
+pub fn emit_reconcile_confirmation_required(app: &AppHandle, event: ReconcileConfirmationEvent) -> Result<(), String> {
+    app.emit("reconcile-confirmation-required", event)
+        .map_err(|e| format!("Failed to emit reconcile-confirmation-required: {}", e))
+}
+
+/// Emit recording locked event to frontend (double tap - keeps recording until the next tap)
+pub fn emit_recording_locked(app: &AppHandle) -> Result<(), String> {
+    app.emit("recording-locked", ())
+        .map_err(|e| format!("Failed to emit recording-locked: {}", e))
+}
+
+/// Emit trigger mode changed event to frontend
+pub fn emit_trigger_mode(app: &AppHandle, mode: TriggerMode) -> Result<(), String> {
+    app.emit("trigger-mode-changed", mode)
+        .map_err(|e| format!("Failed to emit trigger-mode-changed: {}", e))
+}
+
 /// Emit error event to frontend
 pub fn emit_error(app: &AppHandle, code: &str, message: &str) -> Result<(), String> {
     app.emit(
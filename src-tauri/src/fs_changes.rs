@@ -0,0 +1,271 @@
+// Coalescing raw filesystem events into per-file changes
+//
+// notify reports every write of a copy, both halves of a rename and the
+// thumbnails we generate ourselves. `ChangeBatch` collects a burst of events
+// and keeps one change per project file (a file created and then written is
+// just "created", one created and deleted again is nothing). Kept free of
+// Tauri so it can be tested with synthetic events; `fs_watcher` owns the
+// debounce timing and emits the result.
+//
+// Only media files directly in a project folder and the project folders
+// themselves are reported. Platforms that report the two halves of a rename
+// separately (FSEvents) show up as removed + created rather than renamed.
+
+use notify::event::{CreateKind, ModifyKind, RemoveKind, RenameMode};
+use notify::{Event, EventKind};
+use pulse_core::{media, EntryKind};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Removed,
+    Renamed,
+    Modified,
+}
+
+/// One coalesced change in the output folder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChange {
+    pub project: String,
+    /// Media file in the project (None when the project folder itself changed)
+    pub file: Option<String>,
+    pub kind: ChangeKind,
+    /// Previous name (file, or project for a renamed project folder)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+}
+
+/// Where a path sits in the library
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+    project: String,
+    file: Option<String>,
+}
+
+/// Changes collected from a burst of notify events
+#[derive(Debug)]
+pub struct ChangeBatch {
+    root: PathBuf,
+    changes: Vec<FileChange>,
+}
+
+impl ChangeBatch {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root, changes: Vec::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Take the collected changes, leaving the batch empty
+    pub fn take(&mut self) -> Vec<FileChange> {
+        std::mem::take(&mut self.changes)
+    }
+
+    pub fn add(&mut self, event: &Event) {
+        let folder = matches!(event.kind, EventKind::Create(CreateKind::Folder) | EventKind::Remove(RemoveKind::Folder));
+        let kind = match event.kind {
+            EventKind::Create(_) => ChangeKind::Created,
+            EventKind::Remove(_) => ChangeKind::Removed,
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                self.rename(&event.paths[0], &event.paths[1]);
+                return;
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => ChangeKind::Removed,
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => ChangeKind::Created,
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                for path in &event.paths {
+                    let kind = if path.exists() { ChangeKind::Created } else { ChangeKind::Removed };
+                    if let Some(target) = self.target(path, false) {
+                        self.record(target, kind);
+                    }
+                }
+                return;
+            }
+            // Permissions, timestamps and extended attributes don't matter to a timeline
+            EventKind::Modify(ModifyKind::Metadata(_)) => return,
+            EventKind::Modify(_) => ChangeKind::Modified,
+            _ => return,
+        };
+
+        for path in &event.paths {
+            if let Some(target) = self.target(path, folder) {
+                self.record(target, kind);
+            }
+        }
+    }
+
+    /// Projects whose timeline may need reconciling (not removed ones)
+    pub fn affected_projects(changes: &[FileChange]) -> BTreeSet<String> {
+        changes
+            .iter()
+            .filter(|change| change.file.is_some() || change.kind != ChangeKind::Removed)
+            .map(|change| change.project.clone())
+            .collect()
+    }
+
+    fn target(&self, path: &Path, folder: bool) -> Option<Target> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let parts: Vec<&str> = relative
+            .components()
+            .map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+        if parts.iter().any(|part| part.starts_with('.')) {
+            return None;
+        }
+
+        match parts.as_slice() {
+            // A removed folder can't be checked any more; project folders have no extension
+            [project] if folder || path.is_dir() || (!path.exists() && Path::new(project).extension().is_none()) => {
+                Some(Target { project: project.to_string(), file: None })
+            }
+            [project, file] if EntryKind::from_path(path).is_some() && !media::is_thumbnail_file(path) => {
+                Some(Target { project: project.to_string(), file: Some(file.to_string()) })
+            }
+            _ => None,
+        }
+    }
+
+    fn rename(&mut self, from: &Path, to: &Path) {
+        let (from, to) = (self.target(from, false), self.target(to, to.is_dir()));
+        match (from, to) {
+            (Some(from), Some(to)) if from.project == to.project || (from.file.is_none() && to.file.is_none()) => {
+                // A preceding From/To pair for the same rename is superseded
+                let earlier = self.remove(&from);
+                self.remove(&to);
+                let (kind, original) = match earlier {
+                    Some(FileChange { kind: ChangeKind::Created, .. }) => (ChangeKind::Created, None),
+                    Some(FileChange { kind: ChangeKind::Renamed, from: original, .. }) => (ChangeKind::Renamed, original),
+                    _ => (ChangeKind::Renamed, from.file.clone().or(Some(from.project.clone()))),
+                };
+                if kind == ChangeKind::Renamed && original.as_deref() == to.file.as_deref().or(Some(&to.project)) {
+                    return; // Renamed back
+                }
+                self.changes.push(FileChange { project: to.project, file: to.file, kind, from: original });
+            }
+            // Moved between projects (or in/out of the library): gone here, new there
+            (from, to) => {
+                if let Some(from) = from {
+                    self.record(from, ChangeKind::Removed);
+                }
+                if let Some(to) = to {
+                    self.record(to, ChangeKind::Created);
+                }
+            }
+        }
+    }
+
+    fn record(&mut self, target: Target, kind: ChangeKind) {
+        let Some(earlier) = self.remove(&target) else {
+            self.changes.push(FileChange { project: target.project, file: target.file, kind, from: None });
+            return;
+        };
+
+        let merged = match (earlier.kind, kind) {
+            (ChangeKind::Created, ChangeKind::Removed) => None,
+            (ChangeKind::Created, _) => Some((ChangeKind::Created, None)),
+            (ChangeKind::Removed, ChangeKind::Created) => Some((ChangeKind::Modified, None)),
+            (ChangeKind::Renamed, ChangeKind::Modified) => Some((ChangeKind::Renamed, earlier.from)),
+            // The renamed file is gone again: its original name is what went missing
+            (ChangeKind::Renamed, ChangeKind::Removed) if target.file.is_some() => {
+                self.changes.push(FileChange { project: target.project, file: earlier.from, kind: ChangeKind::Removed, from: None });
+                return;
+            }
+            (_, kind) => Some((kind, None)),
+        };
+        if let Some((kind, from)) = merged {
+            self.changes.push(FileChange { project: target.project, file: target.file, kind, from });
+        }
+    }
+
+    fn remove(&mut self, target: &Target) -> Option<FileChange> {
+        let index = self
+            .changes
+            .iter()
+            .position(|change| change.project == target.project && change.file == target.file)?;
+        Some(self.changes.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::DataChange;
+
+    fn event(kind: EventKind, paths: &[&str]) -> Event {
+        paths.iter().fold(Event::new(kind), |event, path| event.add_path(PathBuf::from("/lib").join(path)))
+    }
+
+    fn change(project: &str, file: Option<&str>, kind: ChangeKind, from: Option<&str>) -> FileChange {
+        FileChange { project: project.to_string(), file: file.map(str::to_string), kind, from: from.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_copy_burst_is_one_created_change() {
+        let mut batch = ChangeBatch::new(PathBuf::from("/lib"));
+        batch.add(&event(EventKind::Create(CreateKind::File), &["Demo/clip.mp4"]));
+        for _ in 0..20 {
+            batch.add(&event(EventKind::Modify(ModifyKind::Data(DataChange::Content)), &["Demo/clip.mp4"]));
+        }
+        // Ignored: thumbnails, bookkeeping files, exports, metadata-only changes
+        batch.add(&event(EventKind::Create(CreateKind::File), &["Demo/clip.thumb.png"]));
+        batch.add(&event(EventKind::Modify(ModifyKind::Data(DataChange::Content)), &["Demo/timeline.json"]));
+        batch.add(&event(EventKind::Create(CreateKind::File), &["Demo/exports/clip.gif"]));
+        batch.add(&event(EventKind::Create(CreateKind::File), &["Demo/.checksums.json"]));
+
+        assert_eq!(batch.take(), vec![change("Demo", Some("clip.mp4"), ChangeKind::Created, None)]);
+        assert!(batch.is_empty());
+
+        // Created and deleted within one burst: nothing happened
+        batch.add(&event(EventKind::Create(CreateKind::File), &["Demo/tmp.mp4"]));
+        batch.add(&event(EventKind::Remove(RemoveKind::File), &["Demo/tmp.mp4"]));
+        assert!(batch.is_empty());
+    }
+
+    #[test]
+    fn test_renames() {
+        let mut batch = ChangeBatch::new(PathBuf::from("/lib"));
+        // inotify reports From, To and then Both for one rename
+        batch.add(&event(EventKind::Modify(ModifyKind::Name(RenameMode::From)), &["Demo/a.mp4"]));
+        batch.add(&event(EventKind::Modify(ModifyKind::Name(RenameMode::To)), &["Demo/b.mp4"]));
+        batch.add(&event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)), &["Demo/a.mp4", "Demo/b.mp4"]));
+        batch.add(&event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)), &["Demo/b.mp4", "Demo/c.mp4"]));
+        assert_eq!(batch.take(), vec![change("Demo", Some("c.mp4"), ChangeKind::Renamed, Some("a.mp4"))]);
+
+        // Moved to another project
+        batch.add(&event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)), &["Demo/c.mp4", "Other/c.mp4"]));
+        let changes = batch.take();
+        assert_eq!(changes, vec![
+            change("Demo", Some("c.mp4"), ChangeKind::Removed, None),
+            change("Other", Some("c.mp4"), ChangeKind::Created, None),
+        ]);
+        assert_eq!(ChangeBatch::affected_projects(&changes).into_iter().collect::<Vec<_>>(), ["Demo", "Other"]);
+
+        // A recording being published: only the final name is media
+        batch.add(&event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)), &["Demo/x.mp4.partial", "Demo/recording-1.mp4"]));
+        assert_eq!(batch.take(), vec![change("Demo", Some("recording-1.mp4"), ChangeKind::Created, None)]);
+    }
+
+    #[test]
+    fn test_project_folders() {
+        let mut batch = ChangeBatch::new(PathBuf::from("/lib"));
+        batch.add(&event(EventKind::Create(CreateKind::Folder), &["New"]));
+        batch.add(&event(EventKind::Remove(RemoveKind::Any), &["Old"]));
+        batch.add(&event(EventKind::Remove(RemoveKind::Any), &["settings.json"]));
+        let changes = batch.take();
+        assert_eq!(changes, vec![
+            change("New", None, ChangeKind::Created, None),
+            change("Old", None, ChangeKind::Removed, None),
+        ]);
+        assert_eq!(ChangeBatch::affected_projects(&changes).into_iter().collect::<Vec<_>>(), ["New"]);
+    }
+}
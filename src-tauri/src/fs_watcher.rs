@@ -1,37 +1,60 @@
-use notify::{Watcher, RecursiveMode, Result as NotifyResult, Event, EventKind};
-use std::path::PathBuf;
-use std::sync::{mpsc::channel, Arc, atomic::{AtomicBool, Ordering}};
-use tauri::{AppHandle, Emitter};
-use log::{debug, info};
+use notify::{Watcher, RecommendedWatcher, RecursiveMode, Result as NotifyResult, Event};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use log::{debug, info, warn};
+use crate::commands;
+use crate::events;
+use crate::fs_changes::{ChangeBatch, FileChange};
+use crate::state::AppState;
 
 /// Control handle for the filesystem watcher
+///
+/// Owns the watcher: dropping the handle stops watching and ends the event
+/// thread (changes still waiting for the debounce are dropped).
+///
+/// It keeps running while recording: the clip is written to a `.partial`
+/// file, which `fs_changes` ignores, so only the published clip is reported.
 pub struct WatcherControl {
-    enabled: Arc<AtomicBool>,
+    folder: PathBuf,
+    _watcher: RecommendedWatcher,
 }
 
 impl WatcherControl {
-    /// Pause filesystem event emission (during recording)
-    pub fn pause(&self) {
-        info!("⏸️  Pausing filesystem watcher");
-        self.enabled.store(false, Ordering::SeqCst);
+    /// The folder being watched
+    pub fn folder(&self) -> &Path {
+        &self.folder
     }
-    
-    /// Resume filesystem event emission (after recording completes)
-    pub fn resume(&self) {
-        info!("▶️  Resuming filesystem watcher");
-        self.enabled.store(true, Ordering::SeqCst);
+
+    /// Stop watching
+    pub fn stop(self) {
+        info!("🛑 Stopping filesystem watcher for: {:?}", self.folder);
     }
-    
-    /// Check if watcher is currently enabled
-    /// Utility method for potential future debugging or status checks
-    #[allow(dead_code)]
-    pub fn is_enabled(&self) -> bool {
-        self.enabled.load(Ordering::SeqCst)
+
+    /// Watch another folder instead of this one
+    ///
+    /// The new watcher is started first; if it can't be, this one keeps
+    /// watching the old folder.
+    pub fn retarget(&mut self, app: AppHandle, output_folder: PathBuf) -> NotifyResult<()> {
+        let watcher = watch_output_folder(app, output_folder)?;
+        std::mem::replace(self, watcher).stop();
+        Ok(())
     }
 }
 
+/// How long the output folder has to be quiet before a batch of changes is reported
+const DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Report at least this often during a long burst (e.g. copying a large clip)
+const MAX_BATCH_DELAY: Duration = Duration::from_secs(10);
+
 /// Start watching the output folder for filesystem changes
-/// Returns a WatcherControl handle to pause/resume event emission
+/// Returns a WatcherControl handle that owns the watcher
+///
+/// Raw events are coalesced (see `fs_changes`) and reported as one
+/// `filesystem-changed` event per burst. With auto-reconcile enabled, the
+/// affected projects are reconciled here as well.
 pub fn watch_output_folder(app: AppHandle, output_folder: PathBuf) -> NotifyResult<WatcherControl> {
     info!("📁 Starting filesystem watcher for: {:?}", output_folder);
     
@@ -47,66 +70,58 @@ pub fn watch_output_folder(app: AppHandle, output_folder: PathBuf) -> NotifyResu
     // Watch the output folder recursively
     watcher.watch(&output_folder, RecursiveMode::Recursive)?;
     
-    // Create control handle
-    let enabled = Arc::new(AtomicBool::new(true));
-    let enabled_clone = enabled.clone();
-    
     // Spawn background thread to handle filesystem events
+    let folder = output_folder.clone();
     std::thread::spawn(move || {
+        let mut batch = ChangeBatch::new(output_folder);
         while let Ok(event) = rx.recv() {
-            // Check if watcher is enabled
-            if !enabled_clone.load(Ordering::SeqCst) {
-                debug!("📂 Filesystem event received but PAUSED: {:?}", event);
-                continue;
-            }
-            
-            // Log all events for debugging
-            debug!("📂 Filesystem event received: {:?}", event);
-            
-            match event.kind {
-                EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_) => {
-                    // Check if any of the changed paths are video files or directories
-                    for path in &event.paths {
-                        debug!("   Path changed: {:?}", path);
-                        
-                        // Check if it's a video file
-                        if is_video_file(path) {
-                            debug!("   ✅ Video file change detected: {:?}", path.file_name().unwrap_or_default());
-                            let _ = app.emit("filesystem-changed", ());
-                            break;
-                        }
-                        
-                        // Check if it's a directory (project folder changes)
-                        if path.is_dir() {
-                            debug!("   ✅ Directory change detected: {:?}", path.file_name().unwrap_or_default());
-                            let _ = app.emit("filesystem-changed", ());
-                            break;
-                        }
-                        
-                        debug!("   ⏭️  Not a video file or directory, skipping");
-                    }
-                }
-                _ => {
-                    debug!("   ⏭️  Ignored event type: {:?}", event.kind);
+            // Collect everything that arrives until the folder has been quiet for a moment
+            let started = Instant::now();
+            let mut next = Some(event);
+            while let Some(event) = next.take() {
+                debug!("📂 Filesystem event received: {:?}", event);
+                batch.add(&event);
+
+                let wait = DEBOUNCE.min(MAX_BATCH_DELAY.saturating_sub(started.elapsed()));
+                match rx.recv_timeout(wait) {
+                    Ok(event) => next = Some(event),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    // Stopped: the folder may no longer be the output folder
+                    Err(RecvTimeoutError::Disconnected) => return,
                 }
             }
+
+            if !batch.is_empty() {
+                report_changes(&app, batch.take());
+            }
         }
+        debug!("📁 Filesystem watcher thread finished");
     });
     
-    // Keep watcher alive by leaking it
-    // This is acceptable because we want it to run for the lifetime of the app
-    Box::leak(Box::new(watcher));
-    
     info!("✅ Filesystem watcher started successfully");
-    Ok(WatcherControl { enabled })
+    Ok(WatcherControl { folder, _watcher: watcher })
 }
 
-/// Check if a path is a video file based on extension
-fn is_video_file(path: &std::path::Path) -> bool {
-    if let Some(ext) = path.extension() {
-        let ext_str = ext.to_string_lossy().to_lowercase();
-        matches!(ext_str.as_str(), "mp4" | "mov" | "avi" | "mkv" | "webm" | "m4v")
-    } else {
-        false
+/// Emit a batch of changes and, with auto-reconcile on, reconcile the projects it touched
+fn report_changes(app: &AppHandle, changes: Vec<FileChange>) {
+    let reconciling = app.state::<AppState>().auto_reconcile.lock().map(|enabled| *enabled).unwrap_or(false);
+    let projects = ChangeBatch::affected_projects(&changes);
+    debug!("📂 {} filesystem change(s) in {:?}", changes.len(), projects);
+
+    let _ = events::emit_filesystem_changed(app, events::FilesystemChangedEvent { changes, reconciling });
+    if !reconciling {
+        return;
+    }
+
+    for project in projects {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            match commands::reconcile_project(&app, &project, false).await {
+                Ok(changes) => {
+                    let _ = events::emit_project_reconciled(&app, events::ProjectReconciledEvent { project, changes });
+                }
+                Err(e) => warn!("⚠️  Auto-reconcile of {} failed: {}", project, e),
+            }
+        });
     }
 }
@@ -0,0 +1,247 @@
+// User-configurable global hotkey bindings
+//
+// Each action has a default accelerator ("CmdOrCtrl+Shift+R" style, as
+// understood by tauri_plugin_global_shortcut). Users can override any of
+// them; only overrides are persisted in settings.json, so changing a default
+// in a later version reaches everyone who never customized that action.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri_plugin_global_shortcut::Shortcut;
+
+/// Something a global hotkey can trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HotkeyAction {
+    /// Hold to record
+    Record,
+    /// Drop a marker into the clip being recorded
+    Marker,
+    /// Stop and throw away the clip being recorded
+    Cancel,
+    /// Save a screenshot into the current project
+    Screenshot,
+}
+
+impl HotkeyAction {
+    pub const ALL: [HotkeyAction; 4] = [
+        HotkeyAction::Record,
+        HotkeyAction::Marker,
+        HotkeyAction::Cancel,
+        HotkeyAction::Screenshot,
+    ];
+
+    /// Accelerator used when the user hasn't picked one
+    /// (CmdOrCtrl is Cmd on macOS and Ctrl elsewhere)
+    pub fn default_accelerator(self) -> &'static str {
+        match self {
+            HotkeyAction::Record => "CmdOrCtrl+Shift+R",
+            HotkeyAction::Marker => "CmdOrCtrl+Shift+M",
+            HotkeyAction::Cancel => "CmdOrCtrl+Shift+Backspace",
+            HotkeyAction::Screenshot => "CmdOrCtrl+Shift+2",
+        }
+    }
+
+    /// Human-readable name for error messages
+    pub fn label(self) -> &'static str {
+        match self {
+            HotkeyAction::Record => "Record",
+            HotkeyAction::Marker => "Add marker",
+            HotkeyAction::Cancel => "Cancel recording",
+            HotkeyAction::Screenshot => "Screenshot",
+        }
+    }
+}
+
+/// A binding as reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyBinding {
+    pub action: HotkeyAction,
+    pub accelerator: String,
+    pub default_accelerator: String,
+    pub is_default: bool,
+}
+
+/// Effective accelerator for every action
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bindings(BTreeMap<HotkeyAction, String>);
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings(
+            HotkeyAction::ALL
+                .iter()
+                .map(|a| (*a, a.default_accelerator().to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl Bindings {
+    /// Defaults with the saved overrides applied
+    ///
+    /// All overrides are applied together, so keys moved or swapped between
+    /// actions survive a restart. Overrides that don't parse or still clash
+    /// with another action are dropped, so a bad settings file can't leave
+    /// an action unbound.
+    pub fn from_overrides(overrides: &BTreeMap<HotkeyAction, String>) -> Self {
+        let mut overrides: BTreeMap<HotkeyAction, String> = overrides
+            .iter()
+            .filter_map(|(action, accelerator)| match parse(accelerator.trim()) {
+                Ok(_) => Some((*action, accelerator.trim().to_string())),
+                Err(e) => {
+                    log::warn!("⚠️ Ignoring saved hotkey for {}: {}", action.label(), e);
+                    None
+                }
+            })
+            .collect();
+
+        loop {
+            let mut bindings = Bindings::default();
+            bindings.0.extend(overrides.clone());
+
+            // Dropping an override brings back its default, which may clash in turn
+            let clashing: Vec<HotkeyAction> = overrides
+                .keys()
+                .copied()
+                .filter(|action| bindings.clashes(*action))
+                .collect();
+            if clashing.is_empty() {
+                return bindings;
+            }
+            for action in clashing {
+                log::warn!("⚠️ Ignoring saved hotkey for {}: {} is bound twice", action.label(), overrides[&action]);
+                overrides.remove(&action);
+            }
+        }
+    }
+
+    /// Whether another action uses the same keys as this one
+    fn clashes(&self, action: HotkeyAction) -> bool {
+        let Ok(shortcut) = parse(self.get(action)) else { return false };
+        HotkeyAction::ALL
+            .into_iter()
+            .any(|other| other != action && parse(self.get(other)).is_ok_and(|s| s == shortcut))
+    }
+
+    /// Only the bindings that differ from the defaults (what gets persisted)
+    pub fn overrides(&self) -> BTreeMap<HotkeyAction, String> {
+        self.0
+            .iter()
+            .filter(|(action, accelerator)| accelerator.as_str() != action.default_accelerator())
+            .map(|(action, accelerator)| (*action, accelerator.clone()))
+            .collect()
+    }
+
+    pub fn get(&self, action: HotkeyAction) -> &str {
+        self.0.get(&action).map(String::as_str).unwrap_or(action.default_accelerator())
+    }
+
+    /// Bind an action, rejecting accelerators that don't parse or are
+    /// already used by another action
+    pub fn set(&mut self, action: HotkeyAction, accelerator: &str) -> Result<(), String> {
+        let accelerator = accelerator.trim();
+        let shortcut = parse(accelerator)?;
+
+        if let Some(other) = self.action_for(&shortcut).filter(|other| *other != action) {
+            return Err(format!("{} is already bound to {}", accelerator, other.label()));
+        }
+
+        self.0.insert(action, accelerator.to_string());
+        Ok(())
+    }
+
+    /// Which action (if any) a shortcut is bound to
+    pub fn action_for(&self, shortcut: &Shortcut) -> Option<HotkeyAction> {
+        HotkeyAction::ALL
+            .into_iter()
+            .find(|action| parse(self.get(*action)).is_ok_and(|s| s == *shortcut))
+    }
+
+    pub fn to_list(&self) -> Vec<HotkeyBinding> {
+        HotkeyAction::ALL
+            .into_iter()
+            .map(|action| HotkeyBinding {
+                action,
+                accelerator: self.get(action).to_string(),
+                default_accelerator: action.default_accelerator().to_string(),
+                is_default: self.get(action) == action.default_accelerator(),
+            })
+            .collect()
+    }
+}
+
+/// Parse an accelerator string
+pub fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid hotkey '{}': {}", accelerator, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_are_valid_and_distinct() {
+        let bindings = Bindings::default();
+        for action in HotkeyAction::ALL {
+            let shortcut = parse(bindings.get(action)).unwrap();
+            assert_eq!(bindings.action_for(&shortcut), Some(action));
+        }
+        assert!(bindings.overrides().is_empty());
+    }
+
+    #[test]
+    fn test_set_rejects_conflicts_and_garbage() {
+        let mut bindings = Bindings::default();
+        bindings.set(HotkeyAction::Record, "Alt+Shift+R").unwrap();
+        assert_eq!(bindings.get(HotkeyAction::Record), "Alt+Shift+R");
+
+        // Same keys spelled differently still conflict
+        let err = bindings.set(HotkeyAction::Marker, "shift+alt+r").unwrap_err();
+        assert!(err.contains("Record"), "{}", err);
+
+        assert!(bindings.set(HotkeyAction::Marker, "Shift+NotAKey").is_err());
+        // Rebinding an action to its own keys is fine
+        bindings.set(HotkeyAction::Record, "Alt+Shift+R").unwrap();
+    }
+
+    #[test]
+    fn test_overrides_round_trip() {
+        let mut bindings = Bindings::default();
+        bindings.set(HotkeyAction::Screenshot, "Alt+Shift+S").unwrap();
+
+        let overrides = bindings.overrides();
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(Bindings::from_overrides(&overrides), bindings);
+
+        // Conflicting saved overrides fall back to the default
+        let mut bad = BTreeMap::new();
+        bad.insert(HotkeyAction::Marker, HotkeyAction::Record.default_accelerator().to_string());
+        assert_eq!(Bindings::from_overrides(&bad), Bindings::default());
+    }
+
+    #[test]
+    fn test_reassigned_default_key_round_trip() {
+        // Marker's default key moved to Record
+        let mut bindings = Bindings::default();
+        bindings.set(HotkeyAction::Marker, "Alt+X").unwrap();
+        bindings.set(HotkeyAction::Record, HotkeyAction::Marker.default_accelerator()).unwrap();
+        assert_eq!(Bindings::from_overrides(&bindings.overrides()), bindings);
+
+        // Two actions swapped
+        let mut swapped = BTreeMap::new();
+        swapped.insert(HotkeyAction::Record, HotkeyAction::Screenshot.default_accelerator().to_string());
+        swapped.insert(HotkeyAction::Screenshot, HotkeyAction::Record.default_accelerator().to_string());
+        let bindings = Bindings::from_overrides(&swapped);
+        assert_eq!(bindings.overrides(), swapped);
+
+        // Overrides clashing with each other are both dropped
+        let mut clash = BTreeMap::new();
+        clash.insert(HotkeyAction::Record, "Alt+Y".to_string());
+        clash.insert(HotkeyAction::Cancel, "Alt+Y".to_string());
+        assert_eq!(Bindings::from_overrides(&clash), Bindings::default());
+    }
+}
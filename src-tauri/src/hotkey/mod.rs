@@ -1,5 +1,8 @@
 // Hotkey module - global hotkey registration and handling
 
+pub mod bindings;
+pub mod trigger;
+
 #[cfg(target_os = "macos")]
 pub mod macos;
 
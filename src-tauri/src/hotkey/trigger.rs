@@ -0,0 +1,222 @@
+// Record-key trigger modes
+//
+// Turns raw press/release events of the record hotkey into start/stop
+// decisions. Kept free of Tauri and timers so every mode can be tested with
+// synthetic timestamps; the caller owns the clock and runs the deferred stop
+// (`TriggerAction::StopAfter`) itself.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// A press shorter than this counts as a tap (double-tap-lock mode)
+pub const TAP_MAX: Duration = Duration::from_millis(250);
+
+/// How long after a tap a second tap still locks the recording
+pub const DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(300);
+
+/// How the record hotkey starts and stops recordings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TriggerMode {
+    /// Record while the key is held (walkie-talkie)
+    #[default]
+    Hold,
+    /// First press starts, next press stops
+    Toggle,
+    /// Record while held; a double tap keeps recording until the next tap
+    DoubleTapLock,
+}
+
+/// What the caller should do in response to a key event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerAction {
+    None,
+    Start,
+    Stop,
+    /// Recording is now locked on (double tap)
+    Lock,
+    /// Call `on_timeout` after this long; stops unless a second tap arrives first
+    StopAfter(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TriggerState {
+    Idle,
+    /// Recording with the key held down
+    Held { pressed_at: Instant },
+    /// Recording after a tap, waiting to see if a second tap follows
+    TapPending { released_at: Instant },
+    /// Second tap is down; its release is swallowed
+    Locking,
+    /// Recording until the next press
+    Locked,
+}
+
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    mode: TriggerMode,
+    state: TriggerState,
+}
+
+impl Trigger {
+    pub fn new(mode: TriggerMode) -> Self {
+        Self { mode, state: TriggerState::Idle }
+    }
+
+    pub fn mode(&self) -> TriggerMode {
+        self.mode
+    }
+
+    /// Change the mode; a recording in progress finishes under the rules it started with
+    pub fn set_mode(&mut self, mode: TriggerMode) {
+        self.mode = mode;
+    }
+
+    /// Whether the current recording keeps going without the key held
+    pub fn is_locked(&self) -> bool {
+        matches!(self.state, TriggerState::Locked | TriggerState::Locking)
+    }
+
+    /// Forget the current recording (it was stopped some other way - cancel, error)
+    pub fn reset(&mut self) {
+        self.state = TriggerState::Idle;
+    }
+
+    pub fn on_press(&mut self, now: Instant) -> TriggerAction {
+        match self.state {
+            TriggerState::Idle => match self.mode {
+                TriggerMode::Hold | TriggerMode::DoubleTapLock => {
+                    self.state = TriggerState::Held { pressed_at: now };
+                    TriggerAction::Start
+                }
+                TriggerMode::Toggle => {
+                    self.state = TriggerState::Locked;
+                    TriggerAction::Start
+                }
+            },
+            TriggerState::TapPending { released_at } if now.duration_since(released_at) <= DOUBLE_TAP_WINDOW => {
+                self.state = TriggerState::Locking;
+                TriggerAction::Lock
+            }
+            // Too late for a double tap: treat it as the stop the timer was about to do
+            TriggerState::TapPending { .. } | TriggerState::Locked => {
+                self.state = TriggerState::Idle;
+                TriggerAction::Stop
+            }
+            // Key repeat while held
+            TriggerState::Held { .. } | TriggerState::Locking => TriggerAction::None,
+        }
+    }
+
+    pub fn on_release(&mut self, now: Instant) -> TriggerAction {
+        match self.state {
+            TriggerState::Held { pressed_at } => {
+                if self.mode == TriggerMode::DoubleTapLock && now.duration_since(pressed_at) <= TAP_MAX {
+                    self.state = TriggerState::TapPending { released_at: now };
+                    TriggerAction::StopAfter(DOUBLE_TAP_WINDOW)
+                } else {
+                    self.state = TriggerState::Idle;
+                    TriggerAction::Stop
+                }
+            }
+            TriggerState::Locking => {
+                self.state = TriggerState::Locked;
+                TriggerAction::None
+            }
+            // Releases of the starting press (toggle) or the stopping press
+            TriggerState::Idle | TriggerState::TapPending { .. } | TriggerState::Locked => TriggerAction::None,
+        }
+    }
+
+    /// The deferred stop from `StopAfter` is due
+    pub fn on_timeout(&mut self, now: Instant) -> TriggerAction {
+        match self.state {
+            TriggerState::TapPending { released_at } if now.duration_since(released_at) >= DOUBLE_TAP_WINDOW => {
+                self.state = TriggerState::Idle;
+                TriggerAction::Stop
+            }
+            _ => TriggerAction::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(start: Instant, millis: u64) -> Instant {
+        start + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_hold_records_while_pressed() {
+        let t0 = Instant::now();
+        let mut trigger = Trigger::new(TriggerMode::Hold);
+
+        assert_eq!(trigger.on_press(t0), TriggerAction::Start);
+        assert_eq!(trigger.on_press(ms(t0, 500)), TriggerAction::None); // key repeat
+        // Even a short tap stops in hold mode
+        assert_eq!(trigger.on_release(ms(t0, 100)), TriggerAction::Stop);
+        assert_eq!(trigger.on_release(ms(t0, 150)), TriggerAction::None);
+    }
+
+    #[test]
+    fn test_toggle_starts_and_stops_on_press() {
+        let t0 = Instant::now();
+        let mut trigger = Trigger::new(TriggerMode::Toggle);
+
+        assert_eq!(trigger.on_press(t0), TriggerAction::Start);
+        assert_eq!(trigger.on_release(ms(t0, 100)), TriggerAction::None);
+        assert!(trigger.is_locked());
+        assert_eq!(trigger.on_press(ms(t0, 5000)), TriggerAction::Stop);
+        assert_eq!(trigger.on_release(ms(t0, 5100)), TriggerAction::None);
+        assert_eq!(trigger.on_press(ms(t0, 6000)), TriggerAction::Start);
+    }
+
+    #[test]
+    fn test_double_tap_locks_until_next_tap() {
+        let t0 = Instant::now();
+        let mut trigger = Trigger::new(TriggerMode::DoubleTapLock);
+
+        assert_eq!(trigger.on_press(t0), TriggerAction::Start);
+        assert_eq!(trigger.on_release(ms(t0, 100)), TriggerAction::StopAfter(DOUBLE_TAP_WINDOW));
+        assert_eq!(trigger.on_press(ms(t0, 250)), TriggerAction::Lock);
+        assert_eq!(trigger.on_release(ms(t0, 320)), TriggerAction::None);
+        assert!(trigger.is_locked());
+
+        // The pending timer fires but the recording is locked now
+        assert_eq!(trigger.on_timeout(ms(t0, 400)), TriggerAction::None);
+
+        assert_eq!(trigger.on_press(ms(t0, 60_000)), TriggerAction::Stop);
+        assert_eq!(trigger.on_release(ms(t0, 60_100)), TriggerAction::None);
+        assert!(!trigger.is_locked());
+    }
+
+    #[test]
+    fn test_double_tap_mode_single_tap_and_hold() {
+        let t0 = Instant::now();
+        let mut trigger = Trigger::new(TriggerMode::DoubleTapLock);
+
+        // A long hold behaves like hold mode
+        trigger.on_press(t0);
+        assert_eq!(trigger.on_release(ms(t0, 3000)), TriggerAction::Stop);
+
+        // A single tap stops once the double-tap window has passed
+        trigger.on_press(ms(t0, 4000));
+        trigger.on_release(ms(t0, 4100));
+        assert_eq!(trigger.on_timeout(ms(t0, 4200)), TriggerAction::None); // too early
+        assert_eq!(trigger.on_timeout(ms(t0, 4400)), TriggerAction::Stop);
+        assert_eq!(trigger.on_press(ms(t0, 5000)), TriggerAction::Start);
+    }
+
+    #[test]
+    fn test_reset_after_external_stop() {
+        let t0 = Instant::now();
+        let mut trigger = Trigger::new(TriggerMode::Toggle);
+        trigger.on_press(t0);
+
+        // Recording was cancelled, so the next press starts a new one
+        trigger.reset();
+        assert_eq!(trigger.on_press(ms(t0, 1000)), TriggerAction::Start);
+    }
+}
@@ -3,7 +3,13 @@ mod events;
 mod state;
 mod capture;
 mod hotkey;
+mod limits;
+mod fs_changes;
 mod fs_watcher;
+mod recorder;
+mod recording;
+mod settings;
+mod thumbnails;
 
 pub mod logging;
 
@@ -17,17 +23,28 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
-        .manage(AppState::new())
+        .manage(AppState::load())
         .invoke_handler(tauri::generate_handler![
             commands::set_output_folder,
             commands::set_mic_enabled,
+            commands::get_variable_frame_rate,
+            commands::set_variable_frame_rate,
+            commands::get_keyframe_interval_secs,
+            commands::set_keyframe_interval_secs,
             commands::get_audio_devices,
             commands::set_audio_device,
             commands::authorize_capture,
             commands::get_output_folder,
+            commands::get_settings,
             commands::init_hotkey,
+            commands::get_hotkeys,
+            commands::set_hotkey,
+            commands::reset_hotkeys,
+            commands::get_trigger_mode,
+            commands::set_trigger_mode,
             commands::start_recording,
             commands::stop_recording,
+            commands::cancel_recording,
             commands::get_performance_settings,
             commands::open_folder,
             commands::open_file,
@@ -44,14 +61,32 @@ pub fn run() {
             commands::save_project_timeline,
             commands::add_timeline_entry,
             commands::reconcile_project_timeline,
+            commands::plan_project_reconcile,
+            commands::apply_project_reconcile,
+            commands::get_auto_reconcile,
+            commands::set_auto_reconcile,
+            commands::get_max_removal_percent,
+            commands::set_max_removal_percent,
             commands::get_pre_init_status,
             commands::get_idle_timeout_mins,
             commands::set_idle_timeout_mins,
+            commands::get_min_clip_duration_ms,
+            commands::set_min_clip_duration_ms,
+            commands::get_filename_template,
+            commands::set_filename_template,
+            commands::get_recoverable_recordings,
+            commands::recover_recording,
+            commands::discard_recoverable_recording,
+            commands::get_recording_limits,
+            commands::set_recording_limits,
             commands::update_activity,
             commands::shutdown_idle_capturer,
             commands::toggle_pre_init,
             commands::on_window_focus_gained,
             commands::on_window_focus_lost,
+            commands::take_screenshot,
+            commands::export_clip,
+            commands::regenerate_thumbnails,
         ])
         .setup(|app| {
             commands::setup_global_shortcut(&app.handle())?;
@@ -75,55 +110,29 @@ pub fn run() {
             }
             
             // Create default project if no projects exist
-            if output_folder.exists() {
-                let default_project_path = output_folder.join("Default");
-                if !default_project_path.exists() {
-                    info!("📁 Creating default project folder");
-                    if let Err(e) = std::fs::create_dir_all(&default_project_path) {
-                        warn!("⚠️  Failed to create default project folder: {}", e);
-                    } else {
-                        // Create timeline.json for default project
-                        let timeline_path = default_project_path.join("timeline.json");
-                        let now = chrono::Utc::now().to_rfc3339();
-                        let default_timeline = serde_json::json!({
-                            "projectName": "Default",
-                            "createdAt": now,
-                            "lastModified": now,
-                            "entries": [],
-                            "metadata": {
-                                "totalVideos": 0,
-                                "totalDuration": 0,
-                                "defaultAspectRatio": null,
-                                "tags": null
-                            }
-                        });
-                        
-                        if let Err(e) = std::fs::write(&timeline_path, serde_json::to_string_pretty(&default_timeline).unwrap()) {
-                            warn!("⚠️  Failed to create default timeline.json: {}", e);
-                        } else {
-                            info!("✅ Default project created with timeline.json");
-                        }
-                    }
-                }
-            }
+            commands::ensure_default_project(&output_folder);
             
-            // Start filesystem watcher for output folder
-            match fs_watcher::watch_output_folder(app_handle.clone(), output_folder) {
-                Ok(watcher_control) => {
-                    info!("✅ Filesystem watcher started, storing control handle");
-                    let mut control = state.watcher_control.lock().unwrap();
-                    *control = Some(watcher_control);
-                }
-                Err(e) => {
-                    warn!("⚠️  Failed to start filesystem watcher: {}", e);
+            // Recordings cut off by a crash are left as .partial files; list
+            // them before anything records, so an active one is never included
+            match pulse_core::Library::new(&output_folder).recoverable_clips() {
+                Ok(clips) if !clips.is_empty() => {
+                    warn!("🩹 Found {} interrupted recording(s) to recover", clips.len());
+                    *state.recoverable_clips.lock().unwrap() = clips;
                 }
+                Ok(_) => {}
+                Err(e) => warn!("⚠️  Failed to look for interrupted recordings: {}", e),
+            }
+
+            // Start filesystem watcher for output folder (moved by set_output_folder)
+            if let Err(e) = commands::watch_output_folder(&app_handle, &state, output_folder) {
+                warn!("⚠️  {}", e);
             }
             
             // Set up window focus event listeners
             commands::setup_window_focus_listeners(app_handle.clone());
             
             // Start idle timeout checker
-            commands::start_idle_checker(app_handle);
+            recorder::start_idle_checker(app_handle);
             
             Ok(())
         })
@@ -0,0 +1,178 @@
+// Recording limits: maximum clip duration and minimum free disk space
+//
+// Checked before the capturer is pre-initialized (no point arming a
+// recorder that can't write) and polled while recording, so a stuck hotkey
+// can't fill the disk. `LimitMonitor` only decides; the caller samples the
+// clock and the disk and stops the recording when a limit is reached.
+
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+
+/// Warn this long before the maximum duration is reached
+pub const DURATION_WARNING: Duration = Duration::from_secs(30);
+
+/// Warn once free space drops below this multiple of the minimum
+pub const DISK_WARNING_FACTOR: u64 = 2;
+
+/// How often the recording is checked against the limits
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+const MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LimitKind {
+    MaxDuration,
+    LowDisk,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitCheck {
+    /// Getting close - tell the user
+    Warning(LimitKind),
+    /// Stop the recording now
+    Reached(LimitKind),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordingLimits {
+    /// None = no maximum
+    pub max_duration: Option<Duration>,
+    pub min_free_bytes: u64,
+}
+
+impl RecordingLimits {
+    /// Build from the settings values (0 disables either limit)
+    pub fn from_settings(max_clip_duration_mins: u32, min_free_disk_mb: u64) -> Self {
+        Self {
+            max_duration: (max_clip_duration_mins > 0).then(|| Duration::from_secs(max_clip_duration_mins as u64 * 60)),
+            min_free_bytes: min_free_disk_mb * MB,
+        }
+    }
+
+    /// Whether there's enough free space to start (or keep) recording
+    pub fn has_room(&self, free_bytes: u64) -> bool {
+        free_bytes >= self.min_free_bytes
+    }
+
+    /// Error for a pre-initialize that has to be refused
+    pub fn low_disk_message(&self, free_bytes: u64) -> String {
+        format!(
+            "Not enough disk space to record: {} MB free, at least {} MB required",
+            free_bytes / MB,
+            self.min_free_bytes / MB
+        )
+    }
+
+    /// What to tell the user about a warning or a stopped recording
+    pub fn message(&self, check: LimitCheck, elapsed: Duration, free_bytes: Option<u64>) -> String {
+        let free_mb = free_bytes.unwrap_or(0) / MB;
+        match check {
+            LimitCheck::Warning(LimitKind::MaxDuration) => {
+                let remaining = self.max_duration.unwrap_or_default().saturating_sub(elapsed);
+                format!("Recording will stop in {}s (maximum clip length)", remaining.as_secs())
+            }
+            LimitCheck::Warning(LimitKind::LowDisk) => {
+                format!("Disk space is running low ({} MB free) - recording will stop at {} MB", free_mb, self.min_free_bytes / MB)
+            }
+            LimitCheck::Reached(LimitKind::MaxDuration) => {
+                "Maximum clip length reached - recording stopped and saved".to_string()
+            }
+            LimitCheck::Reached(LimitKind::LowDisk) => {
+                format!("Disk almost full ({} MB free) - recording stopped and saved", free_mb)
+            }
+        }
+    }
+}
+
+/// Tracks one recording against the limits, reporting each warning once
+#[derive(Debug)]
+pub struct LimitMonitor {
+    limits: RecordingLimits,
+    warned: Vec<LimitKind>,
+}
+
+impl LimitMonitor {
+    pub fn new(limits: RecordingLimits) -> Self {
+        Self { limits, warned: Vec::new() }
+    }
+
+    /// Check a sample; `free_bytes` is None if the disk couldn't be queried
+    pub fn poll(&mut self, elapsed: Duration, free_bytes: Option<u64>) -> Option<LimitCheck> {
+        // A full disk corrupts the clip, so it wins over the duration limit
+        if let Some(free) = free_bytes {
+            if !self.limits.has_room(free) {
+                return Some(LimitCheck::Reached(LimitKind::LowDisk));
+            }
+        }
+        if let Some(max) = self.limits.max_duration {
+            if elapsed >= max {
+                return Some(LimitCheck::Reached(LimitKind::MaxDuration));
+            }
+        }
+
+        if let Some(free) = free_bytes {
+            if free < self.limits.min_free_bytes.saturating_mul(DISK_WARNING_FACTOR) {
+                return self.warn_once(LimitKind::LowDisk);
+            }
+        }
+        if let Some(max) = self.limits.max_duration {
+            if elapsed + DURATION_WARNING >= max {
+                return self.warn_once(LimitKind::MaxDuration);
+            }
+        }
+        None
+    }
+
+    fn warn_once(&mut self, kind: LimitKind) -> Option<LimitCheck> {
+        if self.warned.contains(&kind) {
+            return None;
+        }
+        self.warned.push(kind);
+        Some(LimitCheck::Warning(kind))
+    }
+}
+
+/// Free space on the disk holding `path`
+pub fn free_space(path: &Path) -> Result<u64, String> {
+    fs4::available_space(path).map_err(|e| format!("Failed to check free disk space: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn test_duration_warns_once_then_stops() {
+        let mut monitor = LimitMonitor::new(RecordingLimits::from_settings(1, 0));
+
+        assert_eq!(monitor.poll(secs(10), None), None);
+        assert_eq!(monitor.poll(secs(30), None), Some(LimitCheck::Warning(LimitKind::MaxDuration)));
+        assert_eq!(monitor.poll(secs(45), None), None);
+        assert_eq!(monitor.poll(secs(60), None), Some(LimitCheck::Reached(LimitKind::MaxDuration)));
+    }
+
+    #[test]
+    fn test_low_disk_warns_then_stops() {
+        let mut monitor = LimitMonitor::new(RecordingLimits::from_settings(0, 100));
+
+        assert_eq!(monitor.poll(secs(3600), Some(500 * MB)), None);
+        assert_eq!(monitor.poll(secs(3601), Some(150 * MB)), Some(LimitCheck::Warning(LimitKind::LowDisk)));
+        assert_eq!(monitor.poll(secs(3602), Some(120 * MB)), None);
+        assert_eq!(monitor.poll(secs(3603), Some(99 * MB)), Some(LimitCheck::Reached(LimitKind::LowDisk)));
+    }
+
+    #[test]
+    fn test_disabled_limits_never_trigger() {
+        let limits = RecordingLimits::from_settings(0, 0);
+        let mut monitor = LimitMonitor::new(limits);
+
+        assert!(limits.has_room(0));
+        assert_eq!(monitor.poll(secs(24 * 3600), Some(0)), None);
+    }
+}
@@ -0,0 +1,585 @@
+// Recording orchestration: runs the effects `RecordingController` asks for
+//
+// The controller (recording.rs) decides; this module does the work - arming
+// the capturer, starting and stopping it, saving or discarding the clip - in
+// background tasks, and feeds the outcome back in as the next event.
+
+use tauri::{AppHandle, Manager, Emitter};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use crate::capture::CaptureOptions;
+use crate::commands;
+use crate::state::{AppState, PreInitStatus};
+use crate::limits::{self, LimitCheck, LimitMonitor};
+use crate::recording::{Effect, RecordingController, RecordingEvent, RecordingState};
+use crate::events;
+use pulse_core::TimelineEntry;
+use serde::Serialize;
+use std::fs;
+use log::{debug, info, warn, error};
+
+#[cfg(target_os = "macos")]
+use crate::capture::macos::ScreenCapturer;
+
+#[cfg(target_os = "windows")]
+use crate::capture::windows::ScreenCapturer;
+
+// Performance thresholds (in milliseconds)
+/// Expected maximum time from hotkey press to recording start (includes all overhead)
+/// This includes AVAssetWriter initialization, ScreenCaptureKit activation, and thread overhead
+pub const HOTKEY_TO_RECORDING_THRESHOLD_MS: u128 = 250;
+
+/// Ask for a recording to start (record hotkey, as decided by the trigger mode)
+///
+/// Starts right away when the capturer is armed; otherwise the request is
+/// queued by the controller and replayed once the capturer is ready.
+/// `label` names the clip (the `{label}` filename token and its timeline label).
+pub fn begin_recording(app: &AppHandle, label: Option<String>) {
+    let state = app.state::<AppState>();
+    let has_project = state.current_project.lock().map(|p| p.is_some()).unwrap_or(false);
+    if !has_project {
+        warn!("⚠️  No project selected - requesting project name");
+        let _ = events::emit_project_required(app);
+        return;
+    }
+
+    info!("🎬 Recording requested");
+    // Not clip_label yet: the clip still being saved would pick it up
+    if let Ok(mut pending_label) = state.pending_label.lock() {
+        *pending_label = label;
+    }
+    recording_event(app, RecordingEvent::Start);
+}
+
+/// Ask for the current recording to stop and be saved
+pub fn finish_recording(app: &AppHandle) {
+    recording_event(app, RecordingEvent::Stop);
+}
+
+/// Feed an event to the recording controller and tell the UI about any status change
+///
+/// Returns the work the controller wants done; most callers want `recording_event`.
+fn dispatch(app: &AppHandle, event: RecordingEvent) -> Option<Effect> {
+    let state = app.state::<AppState>();
+    let Ok(mut controller) = state.recording.lock() else {
+        error!("❌ Failed to lock recording controller");
+        return None;
+    };
+
+    let (was_recording, old_status) = (controller.wants_recording(), displayed_pre_init_status(&state, &controller));
+    let effect = controller.handle(event);
+    let (recording, status) = (controller.wants_recording(), displayed_pre_init_status(&state, &controller));
+    debug!("🎛️  {:?} -> {:?} (effect: {:?})", event, controller.state(), effect);
+    drop(controller);
+
+    if recording != was_recording {
+        let _ = events::emit_status(app, if recording { "recording" } else { "idle" });
+    }
+    if status != old_status {
+        let _ = events::emit_pre_init_status(app, status.as_str());
+    }
+    effect
+}
+
+/// Capturer status shown in the UI: the controller's, plus why an idle capturer isn't armed
+pub fn displayed_pre_init_status(state: &AppState, controller: &RecordingController) -> PreInitStatus {
+    match controller.pre_init_status() {
+        PreInitStatus::NotInitialized if state.low_disk.lock().map(|low| *low).unwrap_or(false) => PreInitStatus::LowDisk,
+        status => status,
+    }
+}
+
+/// Feed an event to the recording controller and carry out the resulting effect
+pub fn recording_event(app: &AppHandle, event: RecordingEvent) {
+    match dispatch(app, event) {
+        Some(Effect::PreInitialize) => spawn_pre_initialize(app),
+        Some(Effect::StartCapture) => spawn_start_capture(app),
+        Some(Effect::StopCapture) => spawn_stop_capture(app),
+        Some(Effect::ReleaseCapturer) => {
+            info!("💤 Releasing idle capturer");
+            if let Ok(mut cap) = app.state::<AppState>().capturer.lock() {
+                *cap = None;
+            }
+        }
+        // Only produced by `Stopped`, which the stop task dispatches itself
+        Some(Effect::Finalize { .. }) | None => {}
+    }
+}
+
+/// Build and pre-initialize a capturer for the current project in the background
+fn spawn_pre_initialize(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let event = match pre_initialize_capturer(&state).await {
+            Ok(capturer) => {
+                if let Ok(mut cap) = state.capturer.lock() {
+                    *cap = Some(capturer);
+                }
+                RecordingEvent::Initialized
+            }
+            Err(e) => {
+                warn!("⚠️  Failed to pre-initialize capturer: {}", e);
+                if let Ok(mut cap) = state.capturer.lock() {
+                    *cap = None;
+                }
+                // Only worth an error dialog if someone is waiting to record
+                let waiting = state.recording.lock().map(|c| c.wants_recording()).unwrap_or(false);
+                if waiting {
+                    let _ = events::emit_error(&app, "CAPTURE_ERROR", &e);
+                }
+                RecordingEvent::InitFailed
+            }
+        };
+        recording_event(&app, event);
+    });
+}
+
+/// Create a capturer for the current project and pre-initialize it (slow, ~2-3s)
+async fn pre_initialize_capturer(state: &AppState) -> Result<ScreenCapturer, String> {
+    let project_name = state.current_project.lock()
+        .map_err(|e| format!("Failed to lock current project: {}", e))?
+        .clone()
+        .ok_or("No current project set")?;
+    let project = state.library()?.ensure_project(&project_name)?;
+    let options = CaptureOptions {
+        mic_enabled: *state.mic_enabled.lock().map_err(|e| format!("Failed to lock mic_enabled: {}", e))?,
+        audio_device_id: state.selected_audio_device.lock()
+            .map_err(|e| format!("Failed to lock selected_audio_device: {}", e))?
+            .clone(),
+        variable_frame_rate: *state.variable_frame_rate.lock().map_err(|e| format!("Failed to lock variable_frame_rate: {}", e))?,
+        keyframe_interval_secs: *state.keyframe_interval_secs.lock().map_err(|e| format!("Failed to lock keyframe_interval_secs: {}", e))?,
+    };
+    let capture_region = *state.capture_region.lock().map_err(|e| format!("Failed to lock capture_region: {}", e))?;
+
+    // Don't arm a recorder that would fill the disk
+    let limits = state.recording_limits()?;
+    let low_disk = match limits::free_space(project.folder()) {
+        Ok(free) if !limits.has_room(free) => Some(limits.low_disk_message(free)),
+        Ok(_) => None,
+        Err(e) => {
+            warn!("⚠️  {}", e);
+            None
+        }
+    };
+    *state.low_disk.lock().map_err(|e| format!("Failed to lock low_disk: {}", e))? = low_disk.is_some();
+    if let Some(message) = low_disk {
+        return Err(message);
+    }
+
+    // Recorded to a .partial file and only given its real name once finalized
+    // (see save_recording), so nobody sees a half-written clip
+    let output_path = project.new_partial_path("mp4");
+
+    let init_start = Instant::now();
+    let mut capturer = ScreenCapturer::new(output_path, options);
+    capturer.pre_initialize(capture_region).await?;
+    info!("✅ Capturer pre-initialized in {:?} and ready for instant recording", init_start.elapsed());
+    Ok(capturer)
+}
+
+/// Start the armed capturer in the background
+fn spawn_start_capture(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    if let Ok(mut markers) = state.pending_markers.lock() {
+        markers.clear();
+    }
+    // The queued request is the one starting now
+    let label = state.pending_label.lock().ok().and_then(|mut label| label.take());
+    if let Ok(mut clip_label) = state.clip_label.lock() {
+        *clip_label = label;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let capture_region = state.capture_region.lock().map(|region| *region).map_err(|e| format!("Failed to lock capture_region: {}", e));
+        let capturer = state.capturer.lock().map(|mut cap| cap.take()).map_err(|e| format!("Failed to lock capturer: {}", e));
+
+        let result = match (capture_region, capturer) {
+            (Ok(capture_region), Ok(Some(mut capturer))) => capturer.start_recording(capture_region).await.map(|()| capturer),
+            (Ok(_), Ok(None)) => Err("Capturer initialization failed".to_string()),
+            (Err(e), _) | (_, Err(e)) => Err(e),
+        };
+        // Back in state before `Started`, so a queued stop finds it
+        let result = result.and_then(|capturer| {
+            let mut cap = state.capturer.lock().map_err(|e| format!("Failed to lock capturer: {}", e))?;
+            *cap = Some(capturer);
+            Ok(())
+        });
+
+        match result {
+            Ok(()) => {
+                if let Some((requested_at, cold_start)) = state.recording.lock().ok().and_then(|c| c.start_request()) {
+                    let elapsed = requested_at.elapsed();
+                    if cold_start {
+                        info!("⏰ Recording started {:?} after the request (capturer had to initialize first)", elapsed);
+                    } else {
+                        info!("✅ Screen capture started in {:?}", elapsed);
+                        if elapsed.as_millis() > HOTKEY_TO_RECORDING_THRESHOLD_MS {
+                            error!("⚠️  SLOW START DETECTED: {:?} from key press to recording started", elapsed);
+                            error!("💔 We sincerely apologize - you may have lost the first {:?} of your recording.", elapsed);
+                            error!("🔧 This should not happen with pre-initialization. Please report this issue.");
+                        }
+                    }
+                }
+
+                if let Ok(mut activity) = state.last_activity.lock() {
+                    *activity = Instant::now();
+                }
+                recording_event(&app, RecordingEvent::Started);
+                spawn_limit_monitor(&app);
+            }
+            Err(e) => {
+                error!("❌ Failed to start recording: {}", e);
+                let _ = events::emit_error(&app, "CAPTURE_ERROR", &e);
+                resolve_clip_waiters(&state, &Err(e));
+                recording_event(&app, RecordingEvent::StartFailed);
+            }
+        }
+    });
+}
+
+/// Poll the running recording against the duration and disk limits, and stop
+/// it (saving the clip) when one is reached
+fn spawn_limit_monitor(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let limits = match state.recording_limits() {
+        Ok(limits) => limits,
+        Err(e) => {
+            warn!("⚠️  Recording limits unavailable: {}", e);
+            return;
+        }
+    };
+    // Without a folder the disk check is skipped; the duration limit still applies
+    let folder = {
+        let output_folder = state.output_folder.lock().ok().map(|folder| folder.clone());
+        let project = state.current_project.lock().ok().and_then(|project| project.clone());
+        output_folder.zip(project).map(|(output_folder, project)| output_folder.join(project))
+    };
+    // Identifies this recording, so the monitor ends with it
+    let requested_at = state.recording.lock().ok().and_then(|c| c.start_request()).map(|(at, _)| at);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut monitor = LimitMonitor::new(limits);
+        let mut interval = tokio::time::interval(limits::POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            let state = app.state::<AppState>();
+
+            let current = state.recording.lock().ok()
+                .filter(|c| c.state() == RecordingState::Recording)
+                .and_then(|c| c.start_request())
+                .map(|(at, _)| at);
+            if current.is_none() || current != requested_at {
+                break;
+            }
+
+            let elapsed = state.capturer.lock().ok().and_then(|cap| cap.as_ref().and_then(|c| c.elapsed()));
+            let Some(elapsed) = elapsed else {
+                continue;
+            };
+            let free = match &folder {
+                Some(folder) if limits.min_free_bytes > 0 => limits::free_space(folder)
+                    .map_err(|e| debug!("Free space check failed: {}", e))
+                    .ok(),
+                _ => None,
+            };
+
+            match monitor.poll(elapsed, free) {
+                Some(check @ LimitCheck::Warning(kind)) => {
+                    let message = limits.message(check, elapsed, free);
+                    warn!("⚠️  {}", message);
+                    let _ = events::emit_recording_limit_warning(&app, events::RecordingLimitEvent { kind, message });
+                }
+                Some(check @ LimitCheck::Reached(kind)) => {
+                    let message = limits.message(check, elapsed, free);
+                    warn!("🛑 {}", message);
+                    let _ = events::emit_recording_limit_reached(&app, events::RecordingLimitEvent { kind, message });
+                    // The record key no longer owns this recording
+                    if let Ok(mut trigger) = state.trigger.lock() {
+                        trigger.reset();
+                    }
+                    recording_event(&app, RecordingEvent::Stop);
+                    break;
+                }
+                None => {}
+            }
+        }
+    });
+}
+
+/// Stop the recorder in the background, then save or discard the clip
+fn spawn_stop_capture(app: &AppHandle) {
+    info!("⏹️  Stopping recording...");
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let capturer = state.capturer.lock().map(|mut cap| cap.take()).map_err(|e| format!("Failed to lock capturer: {}", e));
+
+        let result = match capturer {
+            Ok(Some(mut capturer)) => capturer.stop_recording().await,
+            Ok(None) => Err("No active capturer to stop".to_string()),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok((path, duration_seconds)) => {
+                if let Some(Effect::Finalize { discard }) = dispatch(&app, RecordingEvent::Stopped) {
+                    let duration_ms = (duration_seconds * 1000.0) as u64;
+                    // Keep the clip if the minimum can't be read
+                    let min_duration_ms = state.min_clip_duration_ms.lock().map(|ms| *ms).unwrap_or(0);
+                    let result = if discard {
+                        discard_recording(&app, &path);
+                        let _ = events::emit_recording_cancelled(&app);
+                        Err("Recording was cancelled".to_string())
+                    } else if duration_ms < min_duration_ms {
+                        // Accidental tap - don't leave a sub-second clip in the project
+                        info!("✂️  Recording too short ({}ms < {}ms)", duration_ms, min_duration_ms);
+                        discard_recording(&app, &path);
+                        let _ = events::emit_recording_discarded(&app, events::RecordingDiscardedEvent {
+                            reason: "too-short".to_string(),
+                            duration_ms,
+                            min_duration_ms,
+                        });
+                        Err(format!("Recording too short ({}ms, minimum is {}ms)", duration_ms, min_duration_ms))
+                    } else {
+                        save_recording(&app, &path, duration_seconds).await
+                    };
+                    resolve_clip_waiters(&state, &result);
+                }
+                recording_event(&app, RecordingEvent::Finalized);
+            }
+            Err(e) => {
+                error!("❌ Failed to stop recording: {}", e);
+                let _ = events::emit_error(&app, "SAVE_ERROR", &e);
+                resolve_clip_waiters(&state, &Err(e));
+                recording_event(&app, RecordingEvent::StopFailed);
+            }
+        }
+    });
+}
+
+/// Add a finished recording to the current project's timeline
+async fn save_recording(app: &AppHandle, path: &Path, duration_seconds: f64) -> Result<SavedClip, String> {
+    let state = app.state::<AppState>();
+    let duration_ms = (duration_seconds * 1000.0) as u64;
+    let label = state.clip_label.lock().map_err(|e| format!("Failed to lock clip_label: {}", e))?.take();
+
+    // Now that the recorder has finalized the file, sync it and give it its real name
+    let recorded_at = chrono::Local::now() - chrono::Duration::milliseconds(duration_ms as i64);
+    let template = state.filename_template.lock().map_err(|e| format!("Failed to lock filename_template: {}", e))?.clone();
+    let project_name = state.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))?.clone();
+    // Published and added under the reconcile lock, so a reconcile set off by
+    // the watcher seeing the new file finds it on the timeline already
+    let _hashing = state.hashing.lock().await;
+    let published = project_name.clone()
+        .ok_or_else(|| "No current project set".to_string())
+        .and_then(|name| state.library()?.project(&name))
+        .and_then(|project| project.publish_clip(path, &template, label.as_deref(), recorded_at));
+    let published = match published {
+        Ok(published) => published,
+        Err(e) => {
+            error!("❌ Failed to publish recording {:?}: {}", path, e);
+            let message = format!("Recording couldn't be saved and was kept for recovery: {}", e);
+            let _ = events::emit_error(app, "SAVE_ERROR", &message);
+            if let (Some(project), Ok(clips)) = (project_name, state.library().and_then(|l| l.recoverable_clips())) {
+                let clips = clips.into_iter().filter(|c| c.project == project && path.ends_with(&c.filename));
+                state.recoverable_clips.lock().map_err(|e| format!("Failed to lock recoverable clips: {}", e))?.extend(clips);
+            }
+            return Err(message);
+        }
+    };
+    let path = published.path.clone();
+    info!("✅ Recording saved to: {:?}, duration: {:.2}s", path, duration_seconds);
+
+    *state.clip_count.lock().map_err(|e| format!("Failed to lock clip_count: {}", e))? += 1;
+
+    let filename = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("recording.mp4")
+        .to_string();
+
+    let aspect_ratio = "none".to_string(); // Will be updated with actual aspect ratio
+    let width = 1920; // Will be updated with actual width
+    let height = 1080; // Will be updated with actual height
+
+    // Add timeline entry (with any markers dropped while recording)
+    let markers = std::mem::take(&mut *state.pending_markers.lock().map_err(|e| format!("Failed to lock pending_markers: {}", e))?);
+    let result = commands::add_timeline_entry(
+        filename,
+        duration_ms,
+        aspect_ratio,
+        width,
+        height,
+        Some(markers),
+        label,
+        Some(published.checksum),
+        app.state::<AppState>()
+    ).await;
+    if let Err(e) = &result {
+        error!("Failed to add timeline entry: {}", e);
+    }
+
+    let _ = events::emit_clip_saved(app, events::ClipSavedEvent {
+        path: path.to_string_lossy().to_string(),
+        duration_ms,
+    });
+
+    result.map(|entry| SavedClip {
+        path: path.to_string_lossy().to_string(),
+        entry,
+    })
+}
+
+/// Marker hotkey - note the current offset into the clip being recorded
+/// (saved on its timeline entry when the clip is added)
+pub fn add_marker(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let recording = state.recording.lock().map(|c| c.state() == RecordingState::Recording).unwrap_or(false);
+    if !recording {
+        debug!("Marker hotkey pressed while not recording, ignoring");
+        return;
+    }
+
+    let elapsed = state.capturer.lock().ok().and_then(|cap| cap.as_ref().and_then(|c| c.elapsed()));
+    let Some(elapsed) = elapsed else {
+        warn!("⚠️  No active capturer, can't place marker");
+        return;
+    };
+
+    let offset_ms = elapsed.as_millis() as u64;
+    if let Ok(mut markers) = state.pending_markers.lock() {
+        markers.push(offset_ms);
+    }
+    info!("📍 Marker at {}ms", offset_ms);
+    let _ = events::emit_marker(app, offset_ms);
+}
+
+/// Stop the current recording and throw the clip away (cancel hotkey or command)
+///
+/// Returns false if there was nothing to cancel.
+pub fn cancel_current_recording(app: &AppHandle) -> bool {
+    let state = app.state::<AppState>();
+    let (wanted, capturing) = match state.recording.lock() {
+        Ok(controller) => (
+            controller.wants_recording(),
+            matches!(controller.state(), RecordingState::Starting | RecordingState::Recording),
+        ),
+        Err(_) => return false,
+    };
+    if !wanted {
+        debug!("Cancel requested while not recording, ignoring");
+        return false;
+    }
+
+    info!("🚫 Cancelling recording...");
+    // Any later record key events start from idle (and a pending tap timer does nothing)
+    if let Ok(mut trigger) = state.trigger.lock() {
+        trigger.reset();
+    }
+    // The controller stops the recorder and the stop path discards the clip
+    recording_event(app, RecordingEvent::Cancel);
+    if !capturing {
+        // Only a queued start was dropped - there's no file to delete
+        let _ = events::emit_recording_cancelled(app);
+    }
+    true
+}
+
+/// Delete a cancelled or too-short recording instead of adding it to the timeline
+fn discard_recording(app: &AppHandle, path: &Path) {
+    if let Ok(mut markers) = app.state::<AppState>().pending_markers.lock() {
+        markers.clear();
+    }
+    match fs::remove_file(path) {
+        Ok(()) => info!("🗑️  Discarded recording {:?}", path),
+        Err(e) => warn!("⚠️  Failed to delete discarded recording {:?}: {}", path, e),
+    }
+}
+
+/// Helper function to re-initialize capturer when settings change
+///
+/// An idle or armed capturer is rebuilt right away; one that's busy picks up
+/// the new settings when it re-arms after the current recording.
+pub fn reinitialize_capturer_if_needed(app: &AppHandle) {
+    let has_project = app.state::<AppState>().current_project.lock().map(|p| p.is_some()).unwrap_or(false);
+    if has_project {
+        info!("🔄 Re-initializing capturer due to settings change...");
+        recording_event(app, RecordingEvent::Reconfigure);
+    }
+}
+
+/// A recording that was stopped and added to the timeline
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedClip {
+    pub path: String,
+    pub entry: TimelineEntry,
+}
+
+/// Hand the outcome of the clip that just finished to anyone waiting in `stop_recording`
+fn resolve_clip_waiters(state: &AppState, result: &Result<SavedClip, String>) {
+    let waiters = match state.clip_waiters.lock() {
+        Ok(mut waiters) => std::mem::take(&mut *waiters),
+        Err(_) => return,
+    };
+    for waiter in waiters {
+        let _ = waiter.send(result.clone());
+    }
+}
+
+/// Start the idle timeout checker background task
+pub fn start_idle_checker(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60)); // Check every minute
+        
+        loop {
+            interval.tick().await;
+            
+            if let Some(state) = app_handle.try_state::<AppState>() {
+                // Check if we should shut down due to idle timeout
+                let should_shutdown = {
+                    let timeout_result = state.idle_timeout_mins.lock();
+                    let activity_result = state.last_activity.lock();
+                    let recording_result = state.recording.lock();
+                    
+                    match (timeout_result, activity_result, recording_result) {
+                        (Ok(timeout_mins), Ok(last_activity), Ok(controller)) => {
+                            if *timeout_mins == 0 {
+                                false // Timeout disabled
+                            } else {
+                                let timeout_duration = Duration::from_secs((*timeout_mins as u64) * 60);
+                                let idle_time = last_activity.elapsed();
+                                
+                                idle_time > timeout_duration && controller.state() == RecordingState::Armed
+                            }
+                        }
+                        _ => false
+                    }
+                };
+                
+                if should_shutdown {
+                    info!("💤 Idle timeout reached, shutting down pre-initialized capturer");
+                    let _ = app_handle.emit_to("main", "pre-init-idle-shutdown", ());
+                    recording_event(&app_handle, RecordingEvent::Shutdown);
+                }
+            }
+        }
+    });
+    
+    info!("⏰ Idle timeout checker started");
+}
+
+/// Pre-initialize the capturer for the current project if it isn't already
+pub fn arm_for_current_project(app: &AppHandle) {
+    let current_project = app.state::<AppState>().current_project.lock().ok().and_then(|p| p.clone());
+    match current_project {
+        Some(project_name) => {
+            info!("🚀 Ensuring capturer is pre-initialized for project: {}", project_name);
+            recording_event(app, RecordingEvent::Arm);
+        }
+        None => info!("📋 No current project set, skipping pre-initialization restart"),
+    }
+}
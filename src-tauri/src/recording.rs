@@ -0,0 +1,417 @@
+// Recording lifecycle state machine
+//
+// One place that knows where the capturer is in its lifecycle:
+//
+//   Idle --arm--> Initializing --ok--> Armed --start--> Starting --ok--> Recording
+//     ^               |                  ^                                   |
+//     |            failed                |                                 stop
+//     +---------------+             Initializing <-- Finalizing <-- Stopping +
+//                                     (re-arm)
+//
+// Start/stop requests that arrive while the capturer is busy (initializing,
+// starting, saving the previous clip) are queued and replayed once it can act
+// on them, instead of being dropped. The controller doesn't touch the
+// capturer itself: `handle` returns an `Effect` for the caller to carry out,
+// and the caller reports the outcome back as another event.
+
+use crate::state::PreInitStatus;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingState {
+    /// No capturer (never initialized, or shut down while idle)
+    Idle,
+    /// Capturer is being created and pre-initialized (~2-3s)
+    Initializing,
+    /// Capturer is ready to start instantly
+    Armed,
+    /// Capture is starting
+    Starting,
+    Recording,
+    /// Recorder is stopping and writing the file
+    Stopping,
+    /// Clip is being added to the timeline (or discarded)
+    Finalizing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingEvent {
+    /// User wants to record (hotkey or command)
+    Start,
+    /// User wants to stop and keep the clip
+    Stop,
+    /// User wants to stop and throw the clip away
+    Cancel,
+    /// Pre-initialize the capturer if there isn't one (project selected, window focused)
+    Arm,
+    /// Capture settings changed - rebuild the capturer when it isn't in use
+    Reconfigure,
+    /// Release the idle capturer (idle timeout, manual sleep)
+    Shutdown,
+    Initialized,
+    InitFailed,
+    Started,
+    StartFailed,
+    Stopped,
+    StopFailed,
+    Finalized,
+}
+
+/// Work the caller has to do after a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Create and pre-initialize a capturer, then report `Initialized` or `InitFailed`
+    PreInitialize,
+    /// Start the armed capturer, then report `Started` or `StartFailed`
+    StartCapture,
+    /// Stop the recorder, then report `Stopped` or `StopFailed`
+    StopCapture,
+    /// Save the clip (or delete it if `discard`), then report `Finalized`
+    Finalize { discard: bool },
+    /// Drop the idle capturer
+    ReleaseCapturer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Request {
+    Start,
+    Stop,
+}
+
+#[derive(Debug)]
+pub struct RecordingController {
+    state: RecordingState,
+    queue: VecDeque<Request>,
+    /// Current recording was cancelled
+    discard: bool,
+    /// Settings changed during initialization, so the capturer being built is stale
+    reconfigure: bool,
+    /// When the pending/current recording was requested
+    requested_at: Option<Instant>,
+    /// The pending/current recording had to wait for initialization
+    cold_start: bool,
+}
+
+impl Default for RecordingController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecordingController {
+    pub fn new() -> Self {
+        Self {
+            state: RecordingState::Idle,
+            queue: VecDeque::new(),
+            discard: false,
+            reconfigure: false,
+            requested_at: None,
+            cold_start: false,
+        }
+    }
+
+    pub fn state(&self) -> RecordingState {
+        self.state
+    }
+
+    /// Whether the user currently wants a recording running (started, starting or queued)
+    pub fn wants_recording(&self) -> bool {
+        match self.queue.back() {
+            Some(Request::Start) => true,
+            Some(Request::Stop) => false,
+            None => matches!(self.state, RecordingState::Starting | RecordingState::Recording),
+        }
+    }
+
+    /// Capturer status as shown in the UI
+    pub fn pre_init_status(&self) -> PreInitStatus {
+        match self.state {
+            RecordingState::Idle => PreInitStatus::NotInitialized,
+            RecordingState::Initializing => PreInitStatus::Initializing,
+            _ => PreInitStatus::Ready,
+        }
+    }
+
+    /// When the current recording was requested, and whether it had to wait
+    /// for the capturer to initialize first
+    pub fn start_request(&self) -> Option<(Instant, bool)> {
+        self.requested_at.map(|at| (at, self.cold_start))
+    }
+
+    pub fn handle(&mut self, event: RecordingEvent) -> Option<Effect> {
+        use RecordingEvent as E;
+        use RecordingState as S;
+
+        match (self.state, event) {
+            // User requests
+            (S::Idle, E::Start) => {
+                self.request_start(true);
+                self.queue.push_back(Request::Start);
+                self.transition(S::Initializing, Some(Effect::PreInitialize))
+            }
+            (S::Armed, E::Start) => {
+                self.request_start(false);
+                self.transition(S::Starting, Some(Effect::StartCapture))
+            }
+            (S::Initializing | S::Starting | S::Stopping | S::Finalizing, E::Start) => {
+                if !self.wants_recording() {
+                    self.request_start(true);
+                }
+                self.enqueue(Request::Start);
+                None
+            }
+            (S::Recording, E::Stop) => self.transition(S::Stopping, Some(Effect::StopCapture)),
+            (S::Recording, E::Cancel) => {
+                self.discard = true;
+                self.transition(S::Stopping, Some(Effect::StopCapture))
+            }
+            (S::Initializing | S::Starting | S::Stopping | S::Finalizing, E::Stop) => {
+                self.enqueue(Request::Stop);
+                None
+            }
+            (S::Starting, E::Cancel) => {
+                self.discard = true;
+                self.enqueue(Request::Stop);
+                None
+            }
+            (S::Initializing | S::Stopping | S::Finalizing, E::Cancel) => {
+                if self.queue.back() == Some(&Request::Start) {
+                    // Cancel the take that's waiting to start
+                    self.enqueue(Request::Stop);
+                } else if self.state == S::Stopping {
+                    // Not saved yet - discard it instead
+                    self.discard = true;
+                }
+                None
+            }
+
+            // Capturer management
+            (S::Idle, E::Arm | E::Reconfigure) => self.transition(S::Initializing, Some(Effect::PreInitialize)),
+            (S::Armed, E::Reconfigure) => self.transition(S::Initializing, Some(Effect::PreInitialize)),
+            (S::Initializing, E::Reconfigure) => {
+                self.reconfigure = true;
+                None
+            }
+            (S::Armed, E::Shutdown) => self.transition(S::Idle, Some(Effect::ReleaseCapturer)),
+
+            // Pipeline results
+            (S::Initializing, E::Initialized) => {
+                if std::mem::take(&mut self.reconfigure) {
+                    return Some(Effect::PreInitialize);
+                }
+                self.state = S::Armed;
+                self.next_request()
+            }
+            (S::Initializing, E::InitFailed) => {
+                self.reconfigure = false;
+                self.reset(S::Idle)
+            }
+            (S::Starting, E::Started) => {
+                self.state = S::Recording;
+                self.next_request()
+            }
+            (S::Starting, E::StartFailed) => self.reset(S::Idle),
+            (S::Stopping, E::Stopped) => {
+                let discard = std::mem::take(&mut self.discard);
+                self.requested_at = None;
+                self.transition(S::Finalizing, Some(Effect::Finalize { discard }))
+            }
+            // Either way the capturer is used up; build a fresh one for the next clip
+            (S::Stopping, E::StopFailed) | (S::Finalizing, E::Finalized) => {
+                self.discard = false;
+                self.transition(S::Initializing, Some(Effect::PreInitialize))
+            }
+
+            // Everything else is a no-op in that state (key repeat, stop while idle, ...)
+            _ => None,
+        }
+    }
+
+    fn transition(&mut self, state: RecordingState, effect: Option<Effect>) -> Option<Effect> {
+        self.state = state;
+        effect
+    }
+
+    /// Replay the oldest queued request now that the capturer can act on it
+    fn next_request(&mut self) -> Option<Effect> {
+        let request = self.queue.pop_front()?;
+        match (self.state, request) {
+            (RecordingState::Armed, Request::Start) => self.transition(RecordingState::Starting, Some(Effect::StartCapture)),
+            (RecordingState::Recording, Request::Stop) => self.transition(RecordingState::Stopping, Some(Effect::StopCapture)),
+            // A stop with nothing to stop, or a start while already recording
+            _ => self.next_request(),
+        }
+    }
+
+    /// Queue a request, letting a stop cancel a start that hasn't happened yet
+    fn enqueue(&mut self, request: Request) {
+        match (self.queue.back(), request) {
+            (Some(Request::Start), Request::Stop) => {
+                self.queue.pop_back();
+                self.requested_at = None;
+            }
+            (Some(last), _) if *last == request => {}
+            _ => self.queue.push_back(request),
+        }
+    }
+
+    fn request_start(&mut self, cold_start: bool) {
+        self.requested_at = Some(Instant::now());
+        self.cold_start = cold_start;
+    }
+
+    /// Give up on the current attempt and any queued requests
+    fn reset(&mut self, state: RecordingState) -> Option<Effect> {
+        self.queue.clear();
+        self.discard = false;
+        self.requested_at = None;
+        self.transition(state, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use RecordingEvent as E;
+    use RecordingState as S;
+
+    fn armed() -> RecordingController {
+        let mut controller = RecordingController::new();
+        assert_eq!(controller.handle(E::Arm), Some(Effect::PreInitialize));
+        assert_eq!(controller.handle(E::Initialized), None);
+        assert_eq!(controller.state(), S::Armed);
+        controller
+    }
+
+    fn record_and_save(controller: &mut RecordingController) {
+        assert_eq!(controller.handle(E::Start), Some(Effect::StartCapture));
+        assert_eq!(controller.handle(E::Started), None);
+        assert_eq!(controller.handle(E::Stop), Some(Effect::StopCapture));
+        assert_eq!(controller.handle(E::Stopped), Some(Effect::Finalize { discard: false }));
+        assert_eq!(controller.handle(E::Finalized), Some(Effect::PreInitialize));
+        assert_eq!(controller.handle(E::Initialized), None);
+    }
+
+    #[test]
+    fn test_normal_recording_rearms() {
+        let mut controller = armed();
+        record_and_save(&mut controller);
+        assert_eq!(controller.state(), S::Armed);
+        record_and_save(&mut controller);
+        assert_eq!(controller.state(), S::Armed);
+    }
+
+    #[test]
+    fn test_fast_tap_stops_once_started() {
+        let mut controller = armed();
+        assert_eq!(controller.handle(E::Start), Some(Effect::StartCapture));
+        // Released before capture actually started - queued, not dropped
+        assert_eq!(controller.handle(E::Stop), None);
+        assert!(!controller.wants_recording());
+        assert_eq!(controller.handle(E::Started), Some(Effect::StopCapture));
+        assert_eq!(controller.state(), S::Stopping);
+    }
+
+    #[test]
+    fn test_release_during_init_cancels_queued_start() {
+        let mut controller = RecordingController::new();
+        // Capturer was asleep: the press wakes it and waits
+        assert_eq!(controller.handle(E::Start), Some(Effect::PreInitialize));
+        assert!(controller.wants_recording());
+        assert_eq!(controller.handle(E::Stop), None);
+        assert!(!controller.wants_recording());
+
+        // Nothing was captured, so nothing starts once initialized
+        assert_eq!(controller.handle(E::Initialized), None);
+        assert_eq!(controller.state(), S::Armed);
+    }
+
+    #[test]
+    fn test_hold_through_init_starts_when_ready() {
+        let mut controller = RecordingController::new();
+        controller.handle(E::Start);
+        assert_eq!(controller.handle(E::Initialized), Some(Effect::StartCapture));
+        assert!(controller.start_request().unwrap().1); // cold start
+        assert_eq!(controller.handle(E::Started), None);
+        assert_eq!(controller.state(), S::Recording);
+    }
+
+    #[test]
+    fn test_repress_during_finalize_records_after_rearm() {
+        let mut controller = armed();
+        controller.handle(E::Start);
+        controller.handle(E::Started);
+        controller.handle(E::Stop);
+        controller.handle(E::Stopped);
+        assert_eq!(controller.state(), S::Finalizing);
+
+        // Pressed again while the last clip is still being saved
+        assert_eq!(controller.handle(E::Start), None);
+        assert!(controller.wants_recording());
+        assert_eq!(controller.handle(E::Finalized), Some(Effect::PreInitialize));
+        assert_eq!(controller.handle(E::Initialized), Some(Effect::StartCapture));
+        assert_eq!(controller.state(), S::Starting);
+    }
+
+    #[test]
+    fn test_cancel_discards_clip() {
+        let mut controller = armed();
+        controller.handle(E::Start);
+        controller.handle(E::Started);
+        assert_eq!(controller.handle(E::Cancel), Some(Effect::StopCapture));
+        assert_eq!(controller.handle(E::Stopped), Some(Effect::Finalize { discard: true }));
+        controller.handle(E::Finalized);
+        controller.handle(E::Initialized);
+
+        // The next clip is kept again
+        record_and_save(&mut controller);
+    }
+
+    #[test]
+    fn test_cancel_while_starting_discards_once_started() {
+        let mut controller = armed();
+        assert_eq!(controller.handle(E::Start), Some(Effect::StartCapture));
+        assert_eq!(controller.handle(E::Cancel), None);
+        assert!(!controller.wants_recording());
+
+        // The recorder is stopped as soon as it's running, and the clip thrown away
+        assert_eq!(controller.handle(E::Started), Some(Effect::StopCapture));
+        assert_eq!(controller.handle(E::Stopped), Some(Effect::Finalize { discard: true }));
+        assert_eq!(controller.handle(E::Finalized), Some(Effect::PreInitialize));
+    }
+
+    #[test]
+    fn test_reconfigure_during_init_rebuilds() {
+        let mut controller = RecordingController::new();
+        controller.handle(E::Arm);
+        assert_eq!(controller.handle(E::Reconfigure), None);
+        // The capturer that just finished has the old settings
+        assert_eq!(controller.handle(E::Initialized), Some(Effect::PreInitialize));
+        assert_eq!(controller.handle(E::Initialized), None);
+        assert_eq!(controller.state(), S::Armed);
+
+        // Arm is a no-op once armed, Reconfigure rebuilds, Shutdown releases
+        assert_eq!(controller.handle(E::Arm), None);
+        assert_eq!(controller.handle(E::Reconfigure), Some(Effect::PreInitialize));
+        controller.handle(E::Initialized);
+        assert_eq!(controller.handle(E::Shutdown), Some(Effect::ReleaseCapturer));
+        assert_eq!(controller.state(), S::Idle);
+    }
+
+    #[test]
+    fn test_failures_return_to_idle_and_drop_queue() {
+        let mut controller = RecordingController::new();
+        controller.handle(E::Start);
+        assert_eq!(controller.handle(E::InitFailed), None);
+        assert_eq!(controller.state(), S::Idle);
+        assert!(!controller.wants_recording());
+
+        let mut controller = armed();
+        controller.handle(E::Start);
+        controller.handle(E::Stop);
+        assert_eq!(controller.handle(E::StartFailed), None);
+        assert_eq!(controller.state(), S::Idle);
+        assert_eq!(controller.handle(E::Stop), None);
+    }
+}
@@ -0,0 +1,324 @@
+// Persisted application settings
+//
+// Stored as settings.json in the platform config dir:
+//   macOS:   ~/Library/Application Support/com.horner.pulse-desktop/
+//   Windows: %APPDATA%\com.horner.pulse-desktop\
+//   Linux:   $XDG_CONFIG_HOME/com.horner.pulse-desktop/ (or ~/.config/...)
+//
+// Loaded once at startup into AppState and rewritten after every `set_*`
+// command. Values that no longer make sense (deleted output folder, unplugged
+// microphone) are dropped on load so the app falls back to its defaults
+// instead of failing to record.
+
+use crate::hotkey::bindings::HotkeyAction;
+use crate::hotkey::trigger::TriggerMode;
+use crate::state::dirs;
+use pulse_core::project::DEFAULT_MAX_REMOVAL_PERCENT;
+use pulse_core::{naming, FilenameTemplate};
+use screen_capture::DEFAULT_KEYFRAME_INTERVAL_SECS;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use log::{info, warn};
+
+/// Current settings.json version
+pub const SETTINGS_VERSION: u32 = 1;
+
+const APP_IDENTIFIER: &str = "com.horner.pulse-desktop";
+
+/// Idle timeout used when none is saved (0 disables the timeout)
+pub const DEFAULT_IDLE_TIMEOUT_MINS: u32 = 5;
+
+/// Longest idle timeout we accept (one day)
+const MAX_IDLE_TIMEOUT_MINS: u32 = 24 * 60;
+
+/// Recordings shorter than this are treated as accidental taps and discarded (0 keeps everything)
+pub const DEFAULT_MIN_CLIP_DURATION_MS: u64 = 500;
+
+/// Largest minimum clip length we accept (one minute)
+const MAX_MIN_CLIP_DURATION_MS: u64 = 60_000;
+
+/// Recordings stop after this long, in case the hotkey gets stuck (0 = no limit)
+pub const DEFAULT_MAX_CLIP_DURATION_MINS: u32 = 60;
+
+/// Longest maximum clip duration we accept (one day)
+const MAX_MAX_CLIP_DURATION_MINS: u32 = 24 * 60;
+
+/// Recording stops (and won't start) with less free disk space than this (0 = no check)
+pub const DEFAULT_MIN_FREE_DISK_MB: u64 = 1024;
+
+/// Largest removal threshold we accept (100 never asks for confirmation)
+pub const MAX_MAX_REMOVAL_PERCENT: u32 = 100;
+
+/// Keyframe intervals we accept, in seconds (static stretches of a
+/// variable-frame-rate recording still get a keyframe this often)
+pub const MIN_KEYFRAME_INTERVAL_SECS: u32 = 1;
+pub const MAX_KEYFRAME_INTERVAL_SECS: u32 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub version: u32,
+    pub output_folder: Option<PathBuf>, // None = platform default
+    pub mic_enabled: bool,
+    pub audio_device_id: Option<String>, // None = auto-select
+    pub capture_region: Option<SavedRegion>, // None = full screen
+    pub idle_timeout_mins: u32,
+    pub current_project: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub hotkeys: BTreeMap<HotkeyAction, String>, // Only hotkeys changed from their defaults
+    pub trigger_mode: TriggerMode,
+    pub min_clip_duration_ms: u64,
+    pub max_clip_duration_mins: u32,
+    pub min_free_disk_mb: u64,
+    pub filename_template: String, // See pulse_core::naming for the tokens
+    pub auto_reconcile: bool, // Reconcile projects when their files change on disk
+    pub max_removal_percent: u32, // Reconciles removing more of a timeline than this wait for confirmation
+    pub variable_frame_rate: bool, // Skip unchanged frames while recording (smaller files, opt-in)
+    pub keyframe_interval_secs: u32, // Most seconds between keyframes (seeking precision vs. file size)
+    #[serde(flatten)]
+    pub extra: Map<String, Value>, // Fields written by newer versions
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            output_folder: None,
+            mic_enabled: true,
+            audio_device_id: None,
+            capture_region: None,
+            idle_timeout_mins: DEFAULT_IDLE_TIMEOUT_MINS,
+            current_project: None,
+            hotkeys: BTreeMap::new(),
+            trigger_mode: TriggerMode::Hold,
+            min_clip_duration_ms: DEFAULT_MIN_CLIP_DURATION_MS,
+            max_clip_duration_mins: DEFAULT_MAX_CLIP_DURATION_MINS,
+            min_free_disk_mb: DEFAULT_MIN_FREE_DISK_MB,
+            filename_template: naming::DEFAULT_TEMPLATE.to_string(),
+            auto_reconcile: false,
+            max_removal_percent: DEFAULT_MAX_REMOVAL_PERCENT,
+            variable_frame_rate: false,
+            keyframe_interval_secs: DEFAULT_KEYFRAME_INTERVAL_SECS,
+            extra: Map::new(),
+        }
+    }
+}
+
+/// Where settings.json lives on this platform
+pub fn settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_IDENTIFIER).join("settings.json"))
+}
+
+impl Settings {
+    /// Read settings, falling back to defaults if the file is missing or unusable
+    ///
+    /// Unreadable files and files from a newer app version are backed up
+    /// next to settings.json before the defaults replace them.
+    pub fn load(path: &Path) -> Settings {
+        if !path.exists() {
+            info!("⚙️ No settings file yet, using defaults");
+            return Settings::default();
+        }
+
+        let parsed = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read settings: {}", e))
+            .and_then(|content| {
+                serde_json::from_str::<Settings>(&content).map_err(|e| format!("Failed to parse settings: {}", e))
+            })
+            .and_then(|settings| {
+                if settings.version > SETTINGS_VERSION {
+                    Err(format!(
+                        "Settings version {} is newer than this app supports ({})",
+                        settings.version, SETTINGS_VERSION
+                    ))
+                } else {
+                    Ok(settings)
+                }
+            });
+
+        match parsed {
+            Ok(mut settings) => {
+                info!("⚙️ Loaded settings from {:?}", path);
+                settings.version = SETTINGS_VERSION;
+                settings
+            }
+            Err(e) => {
+                warn!("⚠️ {} - using defaults", e);
+                let backup = path.with_file_name("settings.bak.json");
+                if let Err(e) = fs::copy(path, &backup) {
+                    warn!("⚠️ Failed to back up settings to {:?}: {}", backup, e);
+                }
+                Settings::default()
+            }
+        }
+    }
+
+    /// Drop saved values that can't be used anymore
+    ///
+    /// `audio_device_ids` is the list of currently available microphones
+    /// (None if it couldn't be determined, in which case the saved device is kept).
+    pub fn validate(&mut self, default_output_folder: &Path, audio_device_ids: Option<&[String]>) {
+        if let Some(folder) = &self.output_folder {
+            if !folder.is_dir() {
+                warn!("⚠️ Saved output folder {:?} no longer exists, using default", folder);
+                self.output_folder = None;
+            }
+        }
+
+        if let (Some(device), Some(available)) = (&self.audio_device_id, audio_device_ids) {
+            if !available.contains(device) {
+                warn!("⚠️ Saved audio device {} is not connected, auto-selecting", device);
+                self.audio_device_id = None;
+            }
+        }
+
+        if let Some(region) = self.capture_region {
+            if region.width == 0 || region.height == 0 {
+                warn!("⚠️ Ignoring empty saved capture region");
+                self.capture_region = None;
+            }
+        }
+
+        if self.idle_timeout_mins > MAX_IDLE_TIMEOUT_MINS {
+            self.idle_timeout_mins = MAX_IDLE_TIMEOUT_MINS;
+        }
+
+        if self.min_clip_duration_ms > MAX_MIN_CLIP_DURATION_MS {
+            self.min_clip_duration_ms = MAX_MIN_CLIP_DURATION_MS;
+        }
+
+        if self.max_clip_duration_mins > MAX_MAX_CLIP_DURATION_MINS {
+            self.max_clip_duration_mins = MAX_MAX_CLIP_DURATION_MINS;
+        }
+
+        if self.max_removal_percent > MAX_MAX_REMOVAL_PERCENT {
+            self.max_removal_percent = MAX_MAX_REMOVAL_PERCENT;
+        }
+
+        self.keyframe_interval_secs = self.keyframe_interval_secs.clamp(MIN_KEYFRAME_INTERVAL_SECS, MAX_KEYFRAME_INTERVAL_SECS);
+
+        if let Err(e) = FilenameTemplate::parse(&self.filename_template) {
+            warn!("⚠️ Saved filename template is invalid ({}), using default", e);
+            self.filename_template = naming::DEFAULT_TEMPLATE.to_string();
+        }
+
+        if let Some(project) = &self.current_project {
+            let output_folder = self.output_folder.as_deref().unwrap_or(default_output_folder);
+            if !output_folder.join(project).is_dir() {
+                warn!("⚠️ Saved project {} no longer exists", project);
+                self.current_project = None;
+            }
+        }
+    }
+
+    /// Write settings via a temp file + rename, so a crash can't leave a
+    /// truncated settings.json behind
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create settings folder: {}", e))?;
+        }
+
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        let tmp_path = path.with_file_name(".settings.json.tmp");
+        {
+            let mut file = fs::File::create(&tmp_path).map_err(|e| format!("Failed to write settings: {}", e))?;
+            file.write_all(json.as_bytes()).map_err(|e| format!("Failed to write settings: {}", e))?;
+            file.sync_all().map_err(|e| format!("Failed to write settings: {}", e))?;
+        }
+        fs::rename(&tmp_path, path).map_err(|e| format!("Failed to replace settings: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pulse-settings-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_round_trip_keeps_unknown_fields() {
+        let dir = temp_dir("roundtrip");
+        let path = dir.join("settings.json");
+        fs::write(&path, r#"{"version":1,"micEnabled":false,"idleTimeoutMins":0,"hotkeys":{"marker":"Alt+M"},"triggerMode":"double-tap-lock","minClipDurationMs":0,"filenameTemplate":"{project}-{seq}","autoReconcile":true,"variableFrameRate":true,"keyframeIntervalSecs":5,"futureOption":"x"}"#).unwrap();
+
+        let settings = Settings::load(&path);
+        assert!(!settings.mic_enabled);
+        assert_eq!(settings.idle_timeout_mins, 0);
+        assert_eq!(settings.hotkeys[&HotkeyAction::Marker], "Alt+M");
+        assert_eq!(settings.trigger_mode, TriggerMode::DoubleTapLock);
+        assert_eq!(settings.min_clip_duration_ms, 0);
+        assert_eq!(settings.filename_template, "{project}-{seq}");
+        assert!(settings.auto_reconcile);
+        assert!(settings.variable_frame_rate);
+        assert_eq!(settings.keyframe_interval_secs, 5);
+        assert_eq!(settings.extra["futureOption"], "x");
+
+        settings.save(&path).unwrap();
+        assert_eq!(Settings::load(&path), settings);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_and_invalid_files_fall_back_to_defaults() {
+        let dir = temp_dir("invalid");
+        let path = dir.join("settings.json");
+        assert_eq!(Settings::load(&path), Settings::default());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(Settings::load(&path), Settings::default());
+        assert!(dir.join("settings.bak.json").exists());
+
+        fs::write(&path, r#"{"version":99}"#).unwrap();
+        assert_eq!(Settings::load(&path), Settings::default());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_drops_stale_values() {
+        let dir = temp_dir("validate");
+        fs::create_dir(dir.join("Demo")).unwrap();
+
+        let mut settings = Settings {
+            output_folder: Some(dir.join("missing")),
+            audio_device_id: Some("usb-mic".to_string()),
+            capture_region: Some(SavedRegion { x: 0, y: 0, width: 0, height: 100 }),
+            current_project: Some("Demo".to_string()),
+            filename_template: "clip-{number}".to_string(),
+            max_removal_percent: 250,
+            keyframe_interval_secs: 0,
+            ..Default::default()
+        };
+        settings.validate(&dir, Some(&["builtin".to_string()]));
+
+        assert_eq!(settings.output_folder, None);
+        assert_eq!(settings.audio_device_id, None);
+        assert_eq!(settings.capture_region, None);
+        assert_eq!(settings.filename_template, naming::DEFAULT_TEMPLATE);
+        assert_eq!(settings.max_removal_percent, MAX_MAX_REMOVAL_PERCENT);
+        assert_eq!(settings.keyframe_interval_secs, MIN_KEYFRAME_INTERVAL_SECS);
+        // Project still exists in the (default) output folder
+        assert_eq!(settings.current_project.as_deref(), Some("Demo"));
+
+        // Unknown device list keeps the saved device
+        settings.audio_device_id = Some("usb-mic".to_string());
+        settings.validate(&dir, None);
+        assert_eq!(settings.audio_device_id.as_deref(), Some("usb-mic"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
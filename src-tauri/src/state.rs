@@ -1,7 +1,16 @@
 use std::path::PathBuf;
 use std::sync::Mutex;
 use std::time::Instant;
+use tokio::sync::oneshot;
 use crate::fs_watcher::WatcherControl;
+use crate::hotkey::bindings::Bindings;
+use crate::hotkey::trigger::Trigger;
+use crate::limits::RecordingLimits;
+use crate::recorder::SavedClip;
+use crate::recording::RecordingController;
+use crate::settings::{self, SavedRegion, Settings};
+use log::warn;
+use pulse_core::{FilenameTemplate, Library, RecoverableClip};
 
 #[cfg(target_os = "macos")]
 use crate::capture::macos::ScreenCapturer;
@@ -9,13 +18,26 @@ use crate::capture::macos::ScreenCapturer;
 #[cfg(target_os = "windows")]
 use crate::capture::windows::ScreenCapturer;
 
-/// Pre-initialization status for capturer
-#[derive(Debug, Clone, PartialEq)]
+/// Pre-initialization status for capturer (derived from the recording controller)
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PreInitStatus {
     NotInitialized,
     Initializing,
     Ready,
-    ShuttingDown,
+    /// Not initialized because there isn't enough free disk space
+    LowDisk,
+}
+
+impl PreInitStatus {
+    /// Name used in `pre-init-status-changed` events and `get_pre_init_status`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PreInitStatus::NotInitialized => "NotInitialized",
+            PreInitStatus::Initializing => "Initializing",
+            PreInitStatus::Ready => "Ready",
+            PreInitStatus::LowDisk => "LowDisk",
+        }
+    }
 }
 
 /// Application state for managing recording settings
@@ -24,57 +46,168 @@ pub struct AppState {
     pub mic_enabled: Mutex<bool>,
     pub selected_audio_device: Mutex<Option<String>>, // Audio device ID
     pub clip_count: Mutex<u32>,
-    /// Recording state tracked internally (actual state managed via atomic bools in commands.rs)
-    #[allow(dead_code)]
-    pub is_recording: Mutex<bool>,
     pub capturer: Mutex<Option<ScreenCapturer>>,
     pub capture_region: Mutex<Option<(u32, u32, u32, u32)>>, // x, y, width, height
     pub current_project: Mutex<Option<String>>,
     pub watcher_control: Mutex<Option<WatcherControl>>,
     
-    // Pre-initialization state tracking
-    pub pre_init_status: Mutex<PreInitStatus>,
+    // Recording lifecycle (capturer pre-init, start/stop, finalize)
+    pub recording: Mutex<RecordingController>,
+    pub clip_waiters: Mutex<Vec<oneshot::Sender<Result<SavedClip, String>>>>, // stop_recording calls waiting for their clip
     pub last_activity: Mutex<Instant>,
     pub idle_timeout_mins: Mutex<u32>, // Default to 30 minutes
+    pub min_clip_duration_ms: Mutex<u64>, // Shorter recordings are discarded (0 = keep all)
+    pub max_clip_duration_mins: Mutex<u32>, // Recordings stop after this long (0 = no limit)
+    pub min_free_disk_mb: Mutex<u64>, // Recordings stop below this much free space (0 = no check)
+    pub low_disk: Mutex<bool>, // Last pre-initialize was refused for lack of disk space
+    pub filename_template: Mutex<FilenameTemplate>, // How new clips are named
+    pub clip_label: Mutex<Option<String>>, // Label for the clip being recorded ({label}, taken from pending_label when capture starts)
+    pub pending_label: Mutex<Option<String>>, // Label for the requested recording, which may still be queued behind the previous clip
+    pub recoverable_clips: Mutex<Vec<RecoverableClip>>, // Unfinished .partial recordings the user can recover or discard
+    pub hashing: tokio::sync::Mutex<()>, // Held by the reconcile worker, so overlapping reconciles hash each file once
+    pub auto_reconcile: Mutex<bool>, // The watcher reconciles changed projects itself
+    pub max_removal_percent: Mutex<u32>, // Reconciles removing more of a timeline than this wait for confirmation
+    pub variable_frame_rate: Mutex<bool>, // Skip unchanged frames while recording
+    pub keyframe_interval_secs: Mutex<u32>, // Most seconds between keyframes
     
     // Window focus tracking
     pub window_focused: Mutex<bool>, // Track if window is currently focused
+
+    // Hotkeys
+    pub hotkeys: Mutex<Bindings>,
+    pub pending_markers: Mutex<Vec<u64>>, // Marker offsets (ms) for the clip being recorded
+    pub trigger: Mutex<Trigger>, // Record key state machine (hold / toggle / double-tap lock)
+
+    // Settings persistence
+    settings_path: Option<PathBuf>,
+    settings_extra: serde_json::Map<String, serde_json::Value>, // Unknown fields from settings.json, written back as-is
+}
+
+/// Default output folder based on platform
+pub fn default_output_folder() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    return dirs::home_dir()
+        .map(|p| p.join("Movies").join("PushToHold"))
+        .unwrap_or_else(|| PathBuf::from("~/Movies/PushToHold"));
+
+    #[cfg(target_os = "windows")]
+    return dirs::home_dir()
+        .map(|p| p.join("Videos").join("PushToHold"))
+        .unwrap_or_else(|| PathBuf::from("~/Videos/PushToHold"));
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    return PathBuf::from("~/Videos/PushToHold");
 }
 
 impl AppState {
-    pub fn new() -> Self {
-        // Default output folder based on platform
-        #[cfg(target_os = "macos")]
-        let default_folder = dirs::home_dir()
-            .map(|p| p.join("Movies").join("PushToHold"))
-            .unwrap_or_else(|| PathBuf::from("~/Movies/PushToHold"));
+    /// Build the state from the saved settings (defaults for anything missing or stale)
+    pub fn load() -> Self {
+        let settings_path = settings::settings_path();
+        let mut settings = settings_path.as_deref().map(Settings::load).unwrap_or_default();
 
-        #[cfg(target_os = "windows")]
-        let default_folder = dirs::home_dir()
-            .map(|p| p.join("Videos").join("PushToHold"))
-            .unwrap_or_else(|| PathBuf::from("~/Videos/PushToHold"));
+        let audio_device_ids = screen_capture::get_audio_devices()
+            .ok()
+            .map(|devices| devices.into_iter().map(|d| d.id).collect::<Vec<_>>());
+        settings.validate(&default_output_folder(), audio_device_ids.as_deref());
 
-        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-        let default_folder = PathBuf::from("~/Videos/PushToHold");
+        Self::from_settings(settings, settings_path)
+    }
 
+    fn from_settings(settings: Settings, settings_path: Option<PathBuf>) -> Self {
         Self {
-            output_folder: Mutex::new(default_folder),
-            mic_enabled: Mutex::new(true),
-            selected_audio_device: Mutex::new(None), // Auto-select on first use
+            output_folder: Mutex::new(settings.output_folder.unwrap_or_else(default_output_folder)),
+            mic_enabled: Mutex::new(settings.mic_enabled),
+            selected_audio_device: Mutex::new(settings.audio_device_id), // None = auto-select on first use
             clip_count: Mutex::new(0),
-            is_recording: Mutex::new(false),
             capturer: Mutex::new(None),
-            capture_region: Mutex::new(None), // Start with full screen (no region)
-            current_project: Mutex::new(None),
+            capture_region: Mutex::new(settings.capture_region.map(|r| (r.x, r.y, r.width, r.height))), // None = full screen
+            current_project: Mutex::new(settings.current_project),
             watcher_control: Mutex::new(None),
             
-            // Initialize pre-init state tracking
-            pre_init_status: Mutex::new(PreInitStatus::NotInitialized),
+            // Nothing pre-initialized yet
+            recording: Mutex::new(RecordingController::new()),
+            clip_waiters: Mutex::new(Vec::new()),
             last_activity: Mutex::new(Instant::now()),
-            idle_timeout_mins: Mutex::new(5), // 5 mins
+            idle_timeout_mins: Mutex::new(settings.idle_timeout_mins),
+            min_clip_duration_ms: Mutex::new(settings.min_clip_duration_ms),
+            max_clip_duration_mins: Mutex::new(settings.max_clip_duration_mins),
+            min_free_disk_mb: Mutex::new(settings.min_free_disk_mb),
+            low_disk: Mutex::new(false),
+            filename_template: Mutex::new(FilenameTemplate::parse(&settings.filename_template).unwrap_or_default()),
+            clip_label: Mutex::new(None),
+            pending_label: Mutex::new(None),
+            recoverable_clips: Mutex::new(Vec::new()), // Filled in at startup
+            hashing: tokio::sync::Mutex::new(()),
+            auto_reconcile: Mutex::new(settings.auto_reconcile),
+            max_removal_percent: Mutex::new(settings.max_removal_percent),
+            variable_frame_rate: Mutex::new(settings.variable_frame_rate),
+            keyframe_interval_secs: Mutex::new(settings.keyframe_interval_secs),
             
             // Initialize window focus tracking
             window_focused: Mutex::new(true), // Assume focused on startup
+
+            hotkeys: Mutex::new(Bindings::from_overrides(&settings.hotkeys)),
+            pending_markers: Mutex::new(Vec::new()),
+            trigger: Mutex::new(Trigger::new(settings.trigger_mode)),
+
+            settings_path,
+            settings_extra: settings.extra,
+        }
+    }
+
+    /// Current values of the persisted settings
+    pub fn settings(&self) -> Result<Settings, String> {
+        let output_folder = self.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?.clone();
+        let region = *self.capture_region.lock().map_err(|e| format!("Failed to lock capture_region: {}", e))?;
+
+        Ok(Settings {
+            version: settings::SETTINGS_VERSION,
+            // Don't pin the platform default, so it can follow the platform later
+            output_folder: (output_folder != default_output_folder()).then_some(output_folder),
+            mic_enabled: *self.mic_enabled.lock().map_err(|e| format!("Failed to lock mic_enabled: {}", e))?,
+            audio_device_id: self.selected_audio_device.lock().map_err(|e| format!("Failed to lock selected_audio_device: {}", e))?.clone(),
+            capture_region: region.map(|(x, y, width, height)| SavedRegion { x, y, width, height }),
+            idle_timeout_mins: *self.idle_timeout_mins.lock().map_err(|e| format!("Failed to lock idle_timeout_mins: {}", e))?,
+            min_clip_duration_ms: *self.min_clip_duration_ms.lock().map_err(|e| format!("Failed to lock min_clip_duration_ms: {}", e))?,
+            max_clip_duration_mins: *self.max_clip_duration_mins.lock().map_err(|e| format!("Failed to lock max_clip_duration_mins: {}", e))?,
+            min_free_disk_mb: *self.min_free_disk_mb.lock().map_err(|e| format!("Failed to lock min_free_disk_mb: {}", e))?,
+            filename_template: self.filename_template.lock().map_err(|e| format!("Failed to lock filename_template: {}", e))?.as_str().to_string(),
+            current_project: self.current_project.lock().map_err(|e| format!("Failed to lock current project: {}", e))?.clone(),
+            hotkeys: self.hotkeys.lock().map_err(|e| format!("Failed to lock hotkeys: {}", e))?.overrides(),
+            trigger_mode: self.trigger.lock().map_err(|e| format!("Failed to lock trigger: {}", e))?.mode(),
+            auto_reconcile: *self.auto_reconcile.lock().map_err(|e| format!("Failed to lock auto_reconcile: {}", e))?,
+            max_removal_percent: *self.max_removal_percent.lock().map_err(|e| format!("Failed to lock max_removal_percent: {}", e))?,
+            variable_frame_rate: *self.variable_frame_rate.lock().map_err(|e| format!("Failed to lock variable_frame_rate: {}", e))?,
+            keyframe_interval_secs: *self.keyframe_interval_secs.lock().map_err(|e| format!("Failed to lock keyframe_interval_secs: {}", e))?,
+            extra: self.settings_extra.clone(),
+        })
+    }
+
+    /// Current maximum duration / free space limits
+    pub fn recording_limits(&self) -> Result<RecordingLimits, String> {
+        let max_mins = *self.max_clip_duration_mins.lock().map_err(|e| format!("Failed to lock max_clip_duration_mins: {}", e))?;
+        let min_free_mb = *self.min_free_disk_mb.lock().map_err(|e| format!("Failed to lock min_free_disk_mb: {}", e))?;
+        Ok(RecordingLimits::from_settings(max_mins, min_free_mb))
+    }
+
+    /// The project library in the current output folder
+    pub fn library(&self) -> Result<Library, String> {
+        let folder = self.output_folder.lock().map_err(|e| format!("Failed to lock output folder: {}", e))?;
+        Ok(Library::new(folder.clone()))
+    }
+
+    /// Write the current settings to disk
+    ///
+    /// Failures are logged, not returned - the change itself already took
+    /// effect and shouldn't be reported as failed just because it won't persist.
+    pub fn save_settings(&self) {
+        let Some(path) = &self.settings_path else {
+            warn!("⚠️ No config folder on this platform, settings won't persist");
+            return;
+        };
+
+        if let Err(e) = self.settings().and_then(|settings| settings.save(path)) {
+            warn!("⚠️ Failed to save settings: {}", e);
         }
     }
 }
@@ -88,4 +221,18 @@ pub mod dirs {
             .or_else(|| std::env::var_os("USERPROFILE"))
             .map(PathBuf::from)
     }
+
+    /// Per-user config folder (Application Support, %APPDATA% or XDG config)
+    pub fn config_dir() -> Option<PathBuf> {
+        #[cfg(target_os = "macos")]
+        return home_dir().map(|p| p.join("Library").join("Application Support"));
+
+        #[cfg(target_os = "windows")]
+        return std::env::var_os("APPDATA").map(PathBuf::from);
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        return std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|p| p.join(".config")));
+    }
 }
@@ -0,0 +1,167 @@
+// Poster frames and filmstrip sprites for timeline entries
+//
+// Thumbnails are stored next to the clip they belong to:
+//   recording-1.mp4 -> recording-1.thumb.png (poster frame)
+//                   -> recording-1.filmstrip.png (optional sprite)
+// Timeline entries reference them by filename, relative to the project folder.
+// The naming itself lives in pulse_core::media, so the core library can tell
+// thumbnails apart from media and clean them up when a clip is removed.
+
+use pulse_core::checksum::Fingerprint;
+use pulse_core::media::{filmstrip_filename, poster_filename};
+use pulse_core::{EntryKind, Project, TimelineEntry};
+use screen_capture::{FrameExtractor, RgbaImage};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use log::{debug, info, warn};
+
+/// Poster frame width in pixels (height follows the clip's aspect ratio)
+const POSTER_WIDTH: u32 = 480;
+
+/// Frames in a filmstrip sprite
+const FILMSTRIP_FRAMES: u32 = 10;
+
+/// Width of each filmstrip frame in pixels
+const FILMSTRIP_FRAME_WIDTH: u32 = 160;
+
+/// Take the poster frame this far into the clip, skipping the first moments
+/// (hotkey release, windows still settling)
+const POSTER_TIME_SECS: f64 = 1.0;
+
+/// Generate the poster frame for a clip, returning its filename
+pub fn generate_poster(project_folder: &Path, clip_filename: &str) -> Result<String, String> {
+    let mut extractor = FrameExtractor::open(&project_folder.join(clip_filename), Some((POSTER_WIDTH, POSTER_WIDTH * 4)))?;
+    let time = POSTER_TIME_SECS.min(extractor.duration() / 2.0);
+    let frame = extractor.frame_at(time)?.fit_width(POSTER_WIDTH);
+
+    let filename = poster_filename(clip_filename);
+    frame.save_png(&project_folder.join(&filename))?;
+    Ok(filename)
+}
+
+/// Generate a filmstrip sprite (frames side by side, evenly spaced through
+/// the clip), returning its filename
+pub fn generate_filmstrip(project_folder: &Path, clip_filename: &str) -> Result<String, String> {
+    let mut extractor = FrameExtractor::open(
+        &project_folder.join(clip_filename),
+        Some((FILMSTRIP_FRAME_WIDTH, FILMSTRIP_FRAME_WIDTH * 4)),
+    )?;
+    let duration = extractor.duration();
+
+    let mut frames = Vec::with_capacity(FILMSTRIP_FRAMES as usize);
+    for i in 0..FILMSTRIP_FRAMES {
+        let time = duration * (i as f64 + 0.5) / FILMSTRIP_FRAMES as f64;
+        frames.push(extractor.frame_at(time)?.fit_width(FILMSTRIP_FRAME_WIDTH));
+    }
+
+    let filename = filmstrip_filename(clip_filename);
+    join_horizontally(&frames)?.save_png(&project_folder.join(&filename))?;
+    Ok(filename)
+}
+
+/// Place same-height frames next to each other
+fn join_horizontally(frames: &[RgbaImage]) -> Result<RgbaImage, String> {
+    let height = frames.first().ok_or("No frames to join")?.height;
+    let width: u32 = frames.iter().map(|f| f.width).sum();
+
+    let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+    for y in 0..height as usize {
+        for frame in frames {
+            let row = frame.width as usize * 4;
+            let start = y.min(frame.height as usize - 1) * row;
+            data.extend_from_slice(&frame.data[start..start + row]);
+        }
+    }
+    RgbaImage::new(width, height, data)
+}
+
+/// Bring the thumbnails of every entry in a project up to date (see `update_entry`)
+///
+/// Decoding every clip can take a while, so it works on a snapshot of the
+/// timeline without holding its lock; only the results are written back, to
+/// entries that still point at the same file. Returns the number of entries
+/// updated (the timeline is only written if there are any).
+pub fn update_project(project: &Project, regenerate: bool, filmstrip: bool) -> Result<u32, String> {
+    let mut entries = project.timeline()?.entries;
+    let updated: HashMap<String, TimelineEntry> = entries
+        .iter_mut()
+        .filter_map(|entry| update_entry(project.folder(), entry, regenerate, filmstrip).then(|| (entry.id.clone(), entry.clone())))
+        .collect();
+    if updated.is_empty() {
+        return Ok(0);
+    }
+
+    project.update_timeline(|timeline| {
+        let mut count = 0u32;
+        for entry in timeline.entries.iter_mut() {
+            let Some(new) = updated.get(&entry.id).filter(|new| new.filename == entry.filename) else {
+                continue;
+            };
+            entry.thumbnail = new.thumbnail.clone();
+            entry.filmstrip = new.filmstrip.clone();
+            entry.thumbnail_failed = new.thumbnail_failed;
+            count += 1;
+        }
+        Ok(count)
+    })
+}
+
+/// Make sure an entry has an up-to-date poster frame (and filmstrip if requested)
+///
+/// Images are their own thumbnail. Returns true if the entry was changed.
+/// Existing thumbnail files are kept unless `regenerate` is set. A clip that
+/// couldn't be decoded is remembered by its fingerprint and not tried again
+/// until the file changes (or `regenerate` is set).
+pub fn update_entry(project_folder: &Path, entry: &mut TimelineEntry, regenerate: bool, filmstrip: bool) -> bool {
+    let before = (entry.thumbnail.clone(), entry.filmstrip.clone(), entry.thumbnail_failed);
+
+    match entry.kind {
+        EntryKind::Image => {
+            entry.thumbnail = Some(entry.filename.clone());
+        }
+        EntryKind::Video => {
+            let fingerprint = fs::metadata(project_folder.join(&entry.filename)).ok().map(|meta| Fingerprint::of(&meta));
+            let known_broken = !regenerate && fingerprint.is_some() && entry.thumbnail_failed == fingerprint;
+
+            let poster = poster_filename(&entry.filename);
+            if known_broken {
+                debug!("Skipping poster frame for {}: it couldn't be decoded and hasn't changed since", entry.filename);
+                entry.thumbnail = None;
+            } else if regenerate || !project_folder.join(&poster).exists() {
+                match generate_poster(project_folder, &entry.filename) {
+                    Ok(poster) => {
+                        info!("🖼️ Generated poster frame: {}", poster);
+                        entry.thumbnail = Some(poster);
+                        entry.thumbnail_failed = None;
+                    }
+                    Err(e) => {
+                        warn!("⚠️ Failed to generate poster frame for {}: {}", entry.filename, e);
+                        entry.thumbnail = None;
+                        entry.thumbnail_failed = fingerprint;
+                    }
+                }
+            } else {
+                entry.thumbnail = Some(poster);
+                entry.thumbnail_failed = None;
+            }
+
+            let strip = filmstrip_filename(&entry.filename);
+            let strip_exists = project_folder.join(&strip).exists();
+            if filmstrip && !known_broken && (regenerate || !strip_exists) {
+                match generate_filmstrip(project_folder, &entry.filename) {
+                    Ok(strip) => entry.filmstrip = Some(strip),
+                    Err(e) => {
+                        warn!("⚠️ Failed to generate filmstrip for {}: {}", entry.filename, e);
+                        entry.filmstrip = None;
+                    }
+                }
+            } else {
+                // Keep a filmstrip generated earlier, drop references to missing ones
+                entry.filmstrip = strip_exists.then_some(strip);
+            }
+        }
+    }
+
+    (entry.thumbnail.clone(), entry.filmstrip.clone(), entry.thumbnail_failed) != before
+}
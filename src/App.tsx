@@ -11,7 +11,7 @@ import DebugControls from './components/DebugControls';
 import { useProjects } from './hooks/useProjects';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import type { CaptureRegion, ClipSavedEvent } from './types';
+import type { CaptureRegion, ClipSavedEvent, RecoverableClip } from './types';
 import './App.css';
 
 function App() {
@@ -43,6 +43,26 @@ function App() {
     return project?.videoCount || 0;
   }, [currentProject, projects]);
 
+  // Recordings interrupted by a crash, found by the backend at startup
+  const [recoverableClips, setRecoverableClips] = useState<RecoverableClip[]>([]);
+
+  useEffect(() => {
+    invoke<RecoverableClip[]>('get_recoverable_recordings')
+      .then(setRecoverableClips)
+      .catch((err) => console.error('Failed to load interrupted recordings:', err));
+  }, []);
+
+  const handleRecoverableClip = async (clip: RecoverableClip, recover: boolean) => {
+    try {
+      const args = { project: clip.project, filename: clip.filename };
+      await invoke(recover ? 'recover_recording' : 'discard_recoverable_recording', args);
+      setRecoverableClips((clips) => clips.filter((c) => c !== clip));
+      if (recover) await refreshProjects();
+    } catch (err) {
+      console.error('Failed to handle interrupted recording:', err);
+    }
+  };
+
   const [isRegionSelectorMode, setIsRegionSelectorMode] = useState(false);
   const [regionSelectorConfig, setRegionSelectorConfig] = useState<{
     aspectRatio: string;
@@ -272,6 +292,24 @@ function App() {
         onAriaFocusChange={setDebugAriaFocus}
       />
 
+      {recoverableClips.map((clip) => (
+        <div key={`${clip.project}/${clip.filename}`} className="recovery-message" role="status">
+          <div className="success-content">
+            <span>
+              🩹 Interrupted recording in {clip.project} ({(clip.sizeBytes / (1024 * 1024)).toFixed(1)} MB)
+            </span>
+            <div className="success-actions">
+              <button onClick={() => handleRecoverableClip(clip, true)} className="action-button">
+                Recover
+              </button>
+              <button onClick={() => handleRecoverableClip(clip, false)} className="action-button">
+                Discard
+              </button>
+            </div>
+          </div>
+        </div>
+      ))}
+
       {recordingState.error && (
         <div className="error-message" role="alert" aria-live="assertive">
           <strong>Error:</strong> {recordingState.error}
@@ -1,6 +1,6 @@
-import { useEffect, useState } from 'react';
+import { useEffect, useRef, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { TimelineEntry } from '../types';
+import { ProjectTimeline, TimelineEntry } from '../types';
 import { useUndoRedo } from '../hooks/useUndoRedo';
 import { useActivity } from '../hooks/useActivity';
 import './ClipsList.css';
@@ -29,29 +29,71 @@ export default function ClipsList({
   const [editLabel, setEditLabel] = useState('');
   const [focusedClipId, setFocusedClipId] = useState<string | null>(null);
 
-  // Debounced save to backend
-  const saveTimeline = async (updatedClips: TimelineEntry[]) => {
-    if (!projectName) return;
+  // Timeline as last loaded or saved - saves are checked against its lastModified
+  const timelineRef = useRef<ProjectTimeline | null>(null);
+
+  // Every entry, newest first - soft-deleted ones stay (they're saved back
+  // with the rest) and are only hidden when rendering
+  const toClips = (entries: TimelineEntry[]): TimelineEntry[] =>
+    [...entries].sort((a, b) => new Date(b.recordedAt).getTime() - new Date(a.recordedAt).getTime());
+
+  // Save the edited clips. If the timeline changed since it was loaded (e.g. a
+  // recording was added), the backend rejects the save; then the timeline is
+  // reloaded and only `applyEdit` is redone on its entries, so nothing added in
+  // between is overwritten.
+  const saveTimeline = async (updatedClips: TimelineEntry[], applyEdit: (entries: TimelineEntry[]) => TimelineEntry[]) => {
+    if (!projectName || !timelineRef.current) return;
 
+    const loaded = timelineRef.current;
     try {
-      // Get the full timeline structure
-      const timeline = await invoke<{ entries: TimelineEntry[]; projectName: string; createdAt: string; lastModified: string; metadata: any }>('get_project_timeline', { projectName });
-      
-      // Update with new clips
-      timeline.entries = updatedClips;
-      
-      await invoke('save_project_timeline', { 
-        projectName, 
-        timeline 
+      const lastModified = await invoke<string>('save_project_timeline', {
+        projectName,
+        timeline: { ...loaded, entries: updatedClips }
       });
+      timelineRef.current = { ...loaded, entries: updatedClips, lastModified };
+      return;
+    } catch (err) {
+      console.warn('Timeline changed since it was loaded, reapplying the edit:', err);
+    }
+
+    try {
+      const fresh = await invoke<ProjectTimeline>('get_project_timeline', { projectName });
+      const entries = applyEdit(toClips(fresh.entries));
+      const lastModified = await invoke<string>('save_project_timeline', {
+        projectName,
+        timeline: { ...fresh, entries }
+      });
+      timelineRef.current = { ...fresh, entries, lastModified };
+      setClips(entries, false);
+      onClipsUpdate?.(entries.filter(c => !c.deleted).length);
     } catch (err) {
       console.error('Failed to save timeline:', err);
     }
   };
 
+  // Move `id` to where it is in `reordered`, next to the same neighbour
+  const reorderEdit = (reordered: TimelineEntry[], id: string) => (entries: TimelineEntry[]): TimelineEntry[] => {
+    const index = reordered.findIndex(c => c.id === id);
+    const moved = entries.find(c => c.id === id);
+    if (index === -1 || !moved) return entries;
+
+    const rest = entries.filter(c => c.id !== id);
+    const next = reordered[index + 1];
+    const prev = reordered[index - 1];
+    let insertIndex = rest.length;
+    if (next && rest.some(c => c.id === next.id)) {
+      insertIndex = rest.findIndex(c => c.id === next.id);
+    } else if (prev && rest.some(c => c.id === prev.id)) {
+      insertIndex = rest.findIndex(c => c.id === prev.id) + 1;
+    }
+    rest.splice(insertIndex, 0, moved);
+    return rest;
+  };
+
   // Load clips when project changes
   useEffect(() => {
     if (!projectName) {
+      timelineRef.current = null;
       setClips([]);
       return;
     }
@@ -59,16 +101,14 @@ export default function ClipsList({
     const loadClips = async () => {
       setLoading(true);
       try {
-        const timeline = await invoke<{ entries: TimelineEntry[] }>('get_project_timeline', { projectName });
-        // Filter out soft-deleted clips and sort by recordedAt (newest first)
-        const activeClips = timeline.entries.filter(clip => !clip.deleted);
-        const sorted = [...activeClips].sort((a, b) => 
-          new Date(b.recordedAt).getTime() - new Date(a.recordedAt).getTime()
-        );
+        const timeline = await invoke<ProjectTimeline>('get_project_timeline', { projectName });
+        timelineRef.current = timeline;
+        const sorted = toClips(timeline.entries);
         setClips(sorted, false); // false = don't create undo checkpoint for initial load
-        onClipsUpdate?.(sorted.length);
+        onClipsUpdate?.(sorted.filter(c => !c.deleted).length);
       } catch (err) {
         console.error('Failed to load clips:', err);
+        timelineRef.current = null;
         setClips([], false);
       } finally {
         setLoading(false);
@@ -161,7 +201,7 @@ export default function ClipsList({
         // Create undo checkpoint
         setClips(newClips, true);
         
-        await saveTimeline(newClips);
+        await saveTimeline(newClips, reorderEdit(newClips, draggedId));
       }
     }
   };
@@ -185,7 +225,10 @@ export default function ClipsList({
     setEditingId(null);
 
     // Save to backend
-    await saveTimeline(updatedClips);
+    const label = editLabel;
+    await saveTimeline(updatedClips, entries =>
+      entries.map(c => (c.id === clip.id ? { ...c, label } : c))
+    );
   };
 
   const handleLabelCancel = () => {
@@ -288,7 +331,11 @@ export default function ClipsList({
     const clipIndex = clips.findIndex(c => c.id === clipId);
     if (clipIndex === -1) return;
 
-    const newIndex = direction === 'up' ? clipIndex - 1 : clipIndex + 1;
+    // Step over soft-deleted clips to the next visible one
+    let newIndex = clipIndex;
+    do {
+      newIndex += direction === 'up' ? -1 : 1;
+    } while (newIndex >= 0 && newIndex < clips.length && clips[newIndex].deleted);
     if (newIndex < 0 || newIndex >= clips.length) return;
 
     const newClips = [...clips];
@@ -296,7 +343,7 @@ export default function ClipsList({
     newClips.splice(newIndex, 0, movedClip);
 
     setClips(newClips, true); // Create undo checkpoint
-    await saveTimeline(newClips);
+    await saveTimeline(newClips, reorderEdit(newClips, clipId));
   };
 
   // Delete clip handler (soft delete)
@@ -305,11 +352,10 @@ export default function ClipsList({
 
     updateActivity();
     // Mark as deleted instead of removing from array
-    const updatedClips = clips.map(c =>
-      c.id === clip.id 
-        ? { ...c, deleted: true, deletedAt: new Date().toISOString() }
-        : c
-    );
+    const deletedAt = new Date().toISOString();
+    const markDeleted = (entries: TimelineEntry[]) =>
+      entries.map(c => (c.id === clip.id ? { ...c, deleted: true, deletedAt } : c));
+    const updatedClips = markDeleted(clips);
 
     // Filter out deleted clips for display, but keep in state for undo
     const visibleClips = updatedClips.filter(c => !c.deleted);
@@ -318,7 +364,7 @@ export default function ClipsList({
     onClipsUpdate?.(visibleClips.length);
 
     // Save to backend (including soft-deleted clips)
-    await saveTimeline(updatedClips);
+    await saveTimeline(updatedClips, markDeleted);
   };
 
   // Open clip in system player
@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import type { Project } from '../types';
+import type { ChecksumProgressEvent, FilesystemChangedEvent, Project, ProjectReconciledEvent, ReconcileConfirmationEvent } from '../types';
 import { useActivity } from '../hooks/useActivity';
 import ClipsList from './ClipsList';
 import './ProjectPanel.css';
@@ -49,6 +49,52 @@ export function ProjectPanel({
   const [newProjectName, setNewProjectName] = useState('');
   const [createError, setCreateError] = useState<string | null>(null);
   const [reconcileMessage, setReconcileMessage] = useState<string | null>(null);
+  const [hashProgress, setHashProgress] = useState<ChecksumProgressEvent | null>(null);
+  const [pendingReconcile, setPendingReconcile] = useState<ReconcileConfirmationEvent | null>(null);
+
+  // Hashing new or changed files can take a while in big projects
+  useEffect(() => {
+    const unsubscribe = listen<ChecksumProgressEvent>('checksum-progress', (event) => {
+      if (event.payload.project !== currentProject) return;
+      setHashProgress(event.payload.currentFile ? event.payload : null);
+    });
+
+    return () => {
+      unsubscribe.then((fn) => fn());
+    };
+  }, [currentProject]);
+
+  // Reconciles that would drop most of the timeline wait for the user
+  // (often the project folder is on a drive that isn't mounted)
+  useEffect(() => {
+    setPendingReconcile(null);
+    const unsubscribe = listen<ReconcileConfirmationEvent>('reconcile-confirmation-required', (event) => {
+      if (event.payload.project !== currentProject) return;
+      setPendingReconcile(event.payload);
+    });
+
+    return () => {
+      unsubscribe.then((fn) => fn());
+    };
+  }, [currentProject]);
+
+  const handleApplyReconcile = async () => {
+    if (!pendingReconcile) return;
+    updateActivity();
+    try {
+      await invoke<number>('apply_project_reconcile', {
+        projectName: pendingReconcile.project,
+        plan: pendingReconcile.plan,
+        confirmed: true,
+      });
+      setPendingReconcile(null);
+      await refreshProjects();
+    } catch (err) {
+      console.error('Failed to apply reconcile:', err);
+      setReconcileMessage(`❌ ${err}`);
+      setTimeout(() => setReconcileMessage(null), 5000);
+    }
+  };
 
   // Shared handler for refreshing projects and reconciling timeline
   const handleRefresh = async () => {
@@ -72,8 +118,14 @@ export function ProjectPanel({
   // Listen for filesystem changes (from Finder, CLI, or other apps)
   useEffect(() => {
     console.log('🎧 Setting up filesystem-changed listener');
-    const unlisten = listen('filesystem-changed', async () => {
-      console.log('� Filesystem changed event received - refreshing projects list');
+    const unlisten = listen<FilesystemChangedEvent>('filesystem-changed', async (event) => {
+      const { changes, reconciling } = event.payload;
+      console.log(`📂 ${changes.length} filesystem change(s) received`);
+      // The backend reconciles the projects itself and reports back with project-reconciled
+      if (reconciling || !currentProject || !changes.some((c) => c.project === currentProject)) {
+        await refreshProjects();
+        return;
+      }
       await handleRefresh();
     });
 
@@ -83,6 +135,21 @@ export function ProjectPanel({
     };
   }, [currentProject, reconcileProjectTimeline, refreshProjects]);
 
+  // Listen for projects reconciled by the backend (auto-reconcile)
+  useEffect(() => {
+    const unlisten = listen<ProjectReconciledEvent>('project-reconciled', async (event) => {
+      await refreshProjects();
+      if (event.payload.project === currentProject && event.payload.changes > 0) {
+        setReconcileMessage(`Updated timeline with ${event.payload.changes} change${event.payload.changes === 1 ? '' : 's'} from disk`);
+        setTimeout(() => setReconcileMessage(null), 5000);
+      }
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [currentProject, refreshProjects]);
+
   // Listen for clip-saved events (from app recordings)
   useEffect(() => {
     console.log('🎧 Setting up clip-saved listener');
@@ -205,8 +272,10 @@ export function ProjectPanel({
             </option>
             
             {projects.map((project) => (
-              <option key={project.name} value={project.name}>
-                {project.name} ({project.videoCount} videos)
+              <option key={project.name} value={project.name} title={project.error}>
+                {project.error
+                  ? `${project.name} (⚠️ timeline unreadable)`
+                  : `${project.name} (${project.videoCount} videos)`}
               </option>
             ))}
             
@@ -284,6 +353,48 @@ export function ProjectPanel({
         </form>
       )}
 
+      {hashProgress && (
+        <div className="reconcile-message reconcile-message--progress" role="status" aria-live="polite">
+          Checking files {hashProgress.filesDone + 1}/{hashProgress.filesTotal}
+          <progress
+            value={hashProgress.bytesDone}
+            max={Math.max(hashProgress.bytesTotal, 1)}
+            aria-label="Checksum progress"
+          />
+        </div>
+      )}
+
+      {pendingReconcile && (
+        <div className="reconcile-message reconcile-message--confirm" role="alert">
+          <p>
+            ⚠️ {pendingReconcile.plan.removed.length} of {pendingReconcile.plan.totalEntries} clips are missing from the
+            project folder. If it's on a drive that isn't connected, reconnect it and dismiss this.
+          </p>
+          <details>
+            <summary>Review changes</summary>
+            <ul className="reconcile-changes">
+              {pendingReconcile.plan.removed.map((entry) => (
+                <li key={entry.id}>Remove {entry.filename}</li>
+              ))}
+              {pendingReconcile.plan.renamed.map((entry) => (
+                <li key={entry.id}>Rename {entry.from} → {entry.to}</li>
+              ))}
+              {pendingReconcile.plan.added.map((file) => (
+                <li key={file.filename}>Add {file.filename}</li>
+              ))}
+            </ul>
+          </details>
+          <div className="reconcile-actions">
+            <button type="button" onClick={handleApplyReconcile} className="reconcile-btn reconcile-btn--danger">
+              Remove missing clips
+            </button>
+            <button type="button" onClick={() => setPendingReconcile(null)} className="reconcile-btn">
+              Dismiss
+            </button>
+          </div>
+        </div>
+      )}
+
       {reconcileMessage && (
         <div 
           className={`reconcile-message ${
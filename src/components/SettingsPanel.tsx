@@ -1,9 +1,23 @@
 import { invoke } from '@tauri-apps/api/core';
 import { useEffect, useState } from 'react';
-import type { AppSettings, CaptureMode, AspectRatio, AudioDevice } from '../types';
+import type { AppSettings, CaptureMode, AspectRatio, AudioDevice, HotkeyAction, RecordingLimitSettings, TriggerMode } from '../types';
 import { useActivity } from '../hooks/useActivity';
+import { formatAccelerator, useHotkeys } from '../hooks/useHotkeys';
 import './SettingsPanel.css';
 
+const HOTKEY_LABELS: Record<HotkeyAction, string> = {
+  record: 'Record (hold)',
+  marker: 'Add marker',
+  cancel: 'Cancel recording',
+  screenshot: 'Screenshot',
+};
+
+const TRIGGER_MODES: { mode: TriggerMode; label: string; instruction: string }[] = [
+  { mode: 'hold', label: 'Hold', instruction: 'Hold to record' },
+  { mode: 'toggle', label: 'Toggle', instruction: 'Press to start, press again to stop' },
+  { mode: 'double-tap-lock', label: 'Hold / double-tap lock', instruction: 'Hold to record, double-tap to lock' },
+];
+
 interface SettingsPanelProps {
   settings: AppSettings;
   onSettingsChange: (settings: Partial<AppSettings>) => void;
@@ -18,6 +32,97 @@ export function SettingsPanel({
   const { updateActivity } = useActivity();
   const [audioDevices, setAudioDevices] = useState<AudioDevice[]>([]);
   const [loadingDevices, setLoadingDevices] = useState(true);
+  const { hotkeys, triggerMode, error: hotkeyError, setHotkey, resetHotkeys, setTriggerMode, getAccelerator } = useHotkeys();
+  const [minClipDurationMs, setMinClipDurationMs] = useState<number | null>(null);
+  const [recordingLimits, setRecordingLimits] = useState<RecordingLimitSettings | null>(null);
+  const [filenameTemplate, setFilenameTemplate] = useState<string | null>(null);
+  const [filenameTemplateError, setFilenameTemplateError] = useState<string | null>(null);
+  const [autoReconcile, setAutoReconcile] = useState<boolean | null>(null);
+  const [maxRemovalPercent, setMaxRemovalPercent] = useState<number | null>(null);
+  const [variableFrameRate, setVariableFrameRate] = useState<boolean | null>(null);
+  const [keyframeIntervalSecs, setKeyframeIntervalSecs] = useState<number | null>(null);
+
+  useEffect(() => {
+    invoke<number>('get_min_clip_duration_ms')
+      .then(setMinClipDurationMs)
+      .catch((err) => console.error('Failed to load minimum clip length:', err));
+    invoke<RecordingLimitSettings>('get_recording_limits')
+      .then(setRecordingLimits)
+      .catch((err) => console.error('Failed to load recording limits:', err));
+    invoke<string>('get_filename_template')
+      .then(setFilenameTemplate)
+      .catch((err) => console.error('Failed to load filename template:', err));
+    invoke<boolean>('get_auto_reconcile')
+      .then(setAutoReconcile)
+      .catch((err) => console.error('Failed to load auto-reconcile setting:', err));
+    invoke<number>('get_max_removal_percent')
+      .then(setMaxRemovalPercent)
+      .catch((err) => console.error('Failed to load removal threshold:', err));
+    invoke<boolean>('get_variable_frame_rate')
+      .then(setVariableFrameRate)
+      .catch((err) => console.error('Failed to load frame rate setting:', err));
+    invoke<number>('get_keyframe_interval_secs')
+      .then(setKeyframeIntervalSecs)
+      .catch((err) => console.error('Failed to load keyframe interval:', err));
+  }, []);
+
+  const handleVariableFrameRateChange = (enabled: boolean) => {
+    updateActivity();
+    setVariableFrameRate(enabled);
+    invoke('set_variable_frame_rate', { enabled })
+      .catch((err) => console.error('Failed to save frame rate setting:', err));
+  };
+
+  const handleKeyframeIntervalChange = (value: number) => {
+    updateActivity();
+    const secs = Math.min(60, Math.max(1, Math.round(value) || 1));
+    setKeyframeIntervalSecs(secs);
+    invoke('set_keyframe_interval_secs', { secs })
+      .catch((err) => console.error('Failed to save keyframe interval:', err));
+  };
+
+  const handleMaxRemovalPercentChange = (value: number) => {
+    updateActivity();
+    const percent = Math.min(100, Math.max(0, Math.round(value)));
+    setMaxRemovalPercent(percent);
+    invoke('set_max_removal_percent', { percent })
+      .catch((err) => console.error('Failed to save removal threshold:', err));
+  };
+
+  const handleAutoReconcileChange = (enabled: boolean) => {
+    updateActivity();
+    setAutoReconcile(enabled);
+    invoke('set_auto_reconcile', { enabled })
+      .catch((err) => console.error('Failed to save auto-reconcile setting:', err));
+  };
+
+  const handleFilenameTemplateChange = (template: string) => {
+    if (template === filenameTemplate) return;
+    updateActivity();
+    invoke('set_filename_template', { template })
+      .then(() => {
+        setFilenameTemplate(template);
+        setFilenameTemplateError(null);
+      })
+      .catch((err) => setFilenameTemplateError(String(err)));
+  };
+
+  const handleRecordingLimitsChange = (partial: Partial<RecordingLimitSettings>) => {
+    if (!recordingLimits) return;
+    updateActivity();
+    const limits = { ...recordingLimits, ...partial };
+    setRecordingLimits(limits);
+    invoke('set_recording_limits', { limits })
+      .catch((err) => console.error('Failed to save recording limits:', err));
+  };
+
+  const handleMinClipDurationChange = (seconds: number) => {
+    updateActivity();
+    const ms = Math.max(0, Math.round(seconds * 1000));
+    setMinClipDurationMs(ms);
+    invoke('set_min_clip_duration_ms', { minDurationMs: ms })
+      .catch((err) => console.error('Failed to save minimum clip length:', err));
+  };
 
   useEffect(() => {
     // Load audio devices on mount
@@ -201,10 +306,213 @@ export function SettingsPanel({
       {/* Hotkey instruction */}
       <div className="hotkey-instruction">
         <div className="hotkey-badge">
-          {navigator.platform.includes('Mac') ? '⌘' : 'Ctrl'}+Shift+R
+          {formatAccelerator(getAccelerator('record') ?? 'CmdOrCtrl+Shift+R')}
         </div>
-        <span className="instruction-text">Hold to record</span>
+        <span className="instruction-text">
+          {TRIGGER_MODES.find((t) => t.mode === triggerMode)?.instruction}
+        </span>
       </div>
+
+      {/* Hotkey bindings - applied on Enter or when the field loses focus */}
+      <details className="hotkey-settings">
+        <summary className="setting-label">Hotkeys &amp; limits</summary>
+        <div className="hotkey-row">
+          <label htmlFor="trigger-mode-select" className="hotkey-label">Record key</label>
+          <select
+            id="trigger-mode-select"
+            value={triggerMode}
+            onChange={(e) => {
+              updateActivity();
+              setTriggerMode(e.target.value as TriggerMode);
+            }}
+            className="audio-device-select"
+          >
+            {TRIGGER_MODES.map((t) => (
+              <option key={t.mode} value={t.mode}>{t.label}</option>
+            ))}
+          </select>
+        </div>
+        {minClipDurationMs !== null && (
+          <div className="hotkey-row">
+            <label htmlFor="min-clip-duration" className="hotkey-label">
+              Discard clips shorter than (s)
+            </label>
+            <input
+              id="min-clip-duration"
+              type="number"
+              min={0}
+              max={60}
+              step={0.1}
+              className="hotkey-input"
+              defaultValue={minClipDurationMs / 1000}
+              onBlur={(e) => handleMinClipDurationChange(Number(e.target.value))}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') e.currentTarget.blur();
+              }}
+              title="Accidental taps shorter than this are deleted instead of saved (0 keeps everything)"
+            />
+          </div>
+        )}
+        {filenameTemplate !== null && (
+          <div className="hotkey-row">
+            <label htmlFor="filename-template" className="hotkey-label">
+              Clip filenames
+            </label>
+            <input
+              id="filename-template"
+              type="text"
+              className="hotkey-input"
+              defaultValue={filenameTemplate}
+              onBlur={(e) => handleFilenameTemplateChange(e.target.value.trim())}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') e.currentTarget.blur();
+              }}
+              title="Tokens: {project}, {date}, {time}, {seq}, {label}, {uuid}"
+            />
+          </div>
+        )}
+        {filenameTemplateError && <div className="hotkey-error" role="alert">{filenameTemplateError}</div>}
+        {variableFrameRate !== null && (
+          <div className="hotkey-row">
+            <label htmlFor="variable-frame-rate" className="hotkey-label">
+              Skip unchanged frames
+            </label>
+            <input
+              id="variable-frame-rate"
+              type="checkbox"
+              checked={variableFrameRate}
+              onChange={(e) => handleVariableFrameRateChange(e.target.checked)}
+              title="Variable frame rate: much smaller files for mostly static screens, but some editors expect a constant frame rate"
+            />
+          </div>
+        )}
+        {keyframeIntervalSecs !== null && (
+          <div className="hotkey-row">
+            <label htmlFor="keyframe-interval" className="hotkey-label">
+              Keyframe at least every (s)
+            </label>
+            <input
+              id="keyframe-interval"
+              type="number"
+              min={1}
+              max={60}
+              className="hotkey-input"
+              defaultValue={keyframeIntervalSecs}
+              onBlur={(e) => handleKeyframeIntervalChange(Number(e.target.value))}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') e.currentTarget.blur();
+              }}
+              title="Shorter intervals make seeking in editors more precise; longer ones make smaller files"
+            />
+          </div>
+        )}
+        {autoReconcile !== null && (
+          <div className="hotkey-row">
+            <label htmlFor="auto-reconcile" className="hotkey-label">
+              Reconcile automatically when files change
+            </label>
+            <input
+              id="auto-reconcile"
+              type="checkbox"
+              checked={autoReconcile}
+              onChange={(e) => handleAutoReconcileChange(e.target.checked)}
+              title="Update project timelines as soon as clips are added, renamed or removed outside the app"
+            />
+          </div>
+        )}
+        {maxRemovalPercent !== null && (
+          <div className="hotkey-row">
+            <label htmlFor="max-removal-percent" className="hotkey-label">
+              Confirm removing more than (%)
+            </label>
+            <input
+              id="max-removal-percent"
+              type="number"
+              min={0}
+              max={100}
+              step={5}
+              className="hotkey-input"
+              defaultValue={maxRemovalPercent}
+              onBlur={(e) => handleMaxRemovalPercentChange(Number(e.target.value))}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') e.currentTarget.blur();
+              }}
+              title="A reconcile that would remove more of a timeline than this waits for you to review it (100 = never ask)"
+            />
+          </div>
+        )}
+        {recordingLimits && (
+          <>
+            <div className="hotkey-row">
+              <label htmlFor="max-clip-duration" className="hotkey-label">
+                Stop recording after (min)
+              </label>
+              <input
+                id="max-clip-duration"
+                type="number"
+                min={0}
+                step={1}
+                className="hotkey-input"
+                defaultValue={recordingLimits.maxClipDurationMins}
+                onBlur={(e) => handleRecordingLimitsChange({ maxClipDurationMins: Math.max(0, Math.round(Number(e.target.value))) })}
+                onKeyDown={(e) => {
+                  if (e.key === 'Enter') e.currentTarget.blur();
+                }}
+                title="Recordings are stopped and saved after this long, e.g. if the hotkey gets stuck (0 = no limit)"
+              />
+            </div>
+            <div className="hotkey-row">
+              <label htmlFor="min-free-disk" className="hotkey-label">
+                Keep free disk space (MB)
+              </label>
+              <input
+                id="min-free-disk"
+                type="number"
+                min={0}
+                step={256}
+                className="hotkey-input"
+                defaultValue={recordingLimits.minFreeDiskMb}
+                onBlur={(e) => handleRecordingLimitsChange({ minFreeDiskMb: Math.max(0, Math.round(Number(e.target.value))) })}
+                onKeyDown={(e) => {
+                  if (e.key === 'Enter') e.currentTarget.blur();
+                }}
+                title="Recording won't start, and stops, below this much free space (0 = no check)"
+              />
+            </div>
+          </>
+        )}
+        {hotkeys.map((hotkey) => (
+          <div key={`${hotkey.action}-${hotkey.accelerator}`} className="hotkey-row">
+            <label htmlFor={`hotkey-${hotkey.action}`} className="hotkey-label">
+              {HOTKEY_LABELS[hotkey.action]}
+            </label>
+            <input
+              id={`hotkey-${hotkey.action}`}
+              className="hotkey-input"
+              defaultValue={hotkey.accelerator}
+              placeholder={hotkey.defaultAccelerator}
+              onBlur={(e) => {
+                const value = e.target.value.trim();
+                if (value && value !== hotkey.accelerator) {
+                  setHotkey(hotkey.action, value);
+                }
+              }}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') e.currentTarget.blur();
+              }}
+            />
+            <button
+              className="hotkey-reset"
+              onClick={() => resetHotkeys(hotkey.action)}
+              disabled={hotkey.isDefault}
+              title={`Reset to ${formatAccelerator(hotkey.defaultAccelerator)}`}
+            >
+              Reset
+            </button>
+          </div>
+        ))}
+        {hotkeyError && <div className="hotkey-error" role="alert">{hotkeyError}</div>}
+      </details>
     </div>
   );
 }
@@ -14,7 +14,7 @@ const PRE_INIT_LABELS: Record<PreInitStatus, string> = {
   NotInitialized: 'Idle',
   Initializing: 'Waking...',
   Ready: 'Ready',
-  ShuttingDown: 'Sleeping...',
+  LowDisk: 'Low disk',
 };
 
 const RECORDING_LABELS: Record<RecordingStatus, string> = {
@@ -28,7 +28,7 @@ const PRE_INIT_DESCRIPTIONS: Record<PreInitStatus, string> = {
   NotInitialized: 'Click to pre-initialize capturer for fast recording startup (~3s)',
   Initializing: 'Pre-initializing capturer for fast recording startup...',
   Ready: 'Click to shut down pre-initialized capturer and save resources',
-  ShuttingDown: 'Capturer shutting down...',
+  LowDisk: 'Not enough free disk space to record. Free up space, then click to retry',
 };
 
 export function StatusIndicator({ className = '' }: StatusIndicatorProps) {
@@ -41,8 +41,7 @@ export function StatusIndicator({ className = '' }: StatusIndicatorProps) {
     updateActivity();
     
     // Don't allow clicking during recording or transitions
-    if (isToggling || recordingState.status !== 'idle' || 
-        preInitStatus === 'Initializing' || preInitStatus === 'ShuttingDown') {
+    if (isToggling || recordingState.status !== 'idle' || preInitStatus === 'Initializing') {
       return;
     }
     
@@ -72,7 +71,10 @@ export function StatusIndicator({ className = '' }: StatusIndicatorProps) {
     if (recordingState.status === 'recording') {
       return {
         label: RECORDING_LABELS.recording,
-        description: 'Currently recording. Release hotkey to stop.',
+        description: recordingState.limitWarning
+          ?? (recordingState.locked
+            ? 'Recording locked. Tap hotkey to stop.'
+            : 'Currently recording. Release hotkey to stop.'),
         cssClass: 'recording-active',
         isClickable: false
       };
@@ -97,8 +99,8 @@ export function StatusIndicator({ className = '' }: StatusIndicatorProps) {
     }
 
     // Show pre-init status when idle
-    const preInitClass = `pre-init-${preInitStatus.toLowerCase().replace(/([A-Z])/g, '-$1').toLowerCase()}`;
-    const isClickable = !isToggling && (preInitStatus === 'NotInitialized' || preInitStatus === 'Ready');
+    const preInitClass = `pre-init-${preInitStatus.replace(/([a-z])([A-Z])/g, '$1-$2').toLowerCase()}`;
+    const isClickable = !isToggling && preInitStatus !== 'Initializing';
     
     return {
       label: PRE_INIT_LABELS[preInitStatus],
@@ -0,0 +1,73 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { HotkeyAction, HotkeyBinding, TriggerMode } from '../types';
+
+const isMac = navigator.platform.includes('Mac');
+
+/**
+ * Format an accelerator for display, e.g. "CmdOrCtrl+Shift+R" -> "⌘+Shift+R"
+ */
+export function formatAccelerator(accelerator: string): string {
+  return accelerator
+    .split('+')
+    .map((key) => {
+      const lower = key.toLowerCase();
+      if (lower === 'cmdorctrl' || lower === 'commandorcontrol') return isMac ? '⌘' : 'Ctrl';
+      if (lower === 'cmd' || lower === 'command' || lower === 'super') return isMac ? '⌘' : 'Win';
+      if (lower === 'alt' || lower === 'option') return isMac ? '⌥' : 'Alt';
+      return key;
+    })
+    .join('+');
+}
+
+/**
+ * Hook for reading and rebinding the global hotkeys
+ */
+export function useHotkeys() {
+  const [hotkeys, setHotkeys] = useState<HotkeyBinding[]>([]);
+  const [triggerMode, setTriggerModeState] = useState<TriggerMode>('hold');
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<HotkeyBinding[]>('get_hotkeys')
+      .then(setHotkeys)
+      .catch((err) => console.error('Failed to load hotkeys:', err));
+    invoke<TriggerMode>('get_trigger_mode')
+      .then(setTriggerModeState)
+      .catch((err) => console.error('Failed to load trigger mode:', err));
+  }, []);
+
+  const setTriggerMode = useCallback(async (mode: TriggerMode) => {
+    try {
+      await invoke('set_trigger_mode', { mode });
+      setTriggerModeState(mode);
+    } catch (err) {
+      setError(String(err));
+    }
+  }, []);
+
+  const setHotkey = useCallback(async (action: HotkeyAction, accelerator: string) => {
+    try {
+      setHotkeys(await invoke<HotkeyBinding[]>('set_hotkey', { action, accelerator }));
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  }, []);
+
+  const resetHotkeys = useCallback(async (action?: HotkeyAction) => {
+    try {
+      setHotkeys(await invoke<HotkeyBinding[]>('reset_hotkeys', { action: action ?? null }));
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  }, []);
+
+  const getAccelerator = useCallback(
+    (action: HotkeyAction) => hotkeys.find((h) => h.action === action)?.accelerator,
+    [hotkeys]
+  );
+
+  return { hotkeys, triggerMode, error, setHotkey, resetHotkeys, setTriggerMode, getAccelerator };
+}
@@ -1,6 +1,30 @@
 import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import type { RecordingState, ClipSavedEvent, ErrorEvent } from '../types';
+import type { RecordingState, ClipSavedEvent, ErrorEvent, RecordingDiscardedEvent, RecordingLimitEvent, SavedClip } from '../types';
+
+/**
+ * Start recording without the hotkey (rejects if no project is selected or already recording)
+ *
+ * The optional label becomes the clip's timeline label and its {label} filename token.
+ */
+export function startRecording(label?: string): Promise<void> {
+  return invoke('start_recording', { label: label ?? null });
+}
+
+/**
+ * Stop the current recording; resolves once the clip is saved to the timeline
+ */
+export function stopRecording(): Promise<SavedClip> {
+  return invoke<SavedClip>('stop_recording');
+}
+
+/**
+ * Stop the current recording and delete the clip instead of saving it
+ */
+export function cancelRecording(): Promise<void> {
+  return invoke('cancel_recording');
+}
 
 /**
  * Hook to manage recording state and listen to backend events
@@ -22,9 +46,39 @@ export function useRecording() {
       setRecordingState((prev) => ({
         ...prev,
         status: event.payload as RecordingState['status'],
+        locked: event.payload === 'recording' ? prev.locked : false,
+        limitWarning: event.payload === 'recording' ? prev.limitWarning : undefined,
       }));
     });
 
+    // Listen for double-tap lock
+    const unlistenLocked = listen('recording-locked', () => {
+      setRecordingState((prev) => ({ ...prev, locked: true }));
+    });
+
+    // Listen for cancelled recordings (nothing is saved)
+    const unlistenCancelled = listen('recording-cancelled', () => {
+      console.log('🚫 Frontend received recording-cancelled');
+      setRecordingState((prev) => ({ ...prev, locked: false }));
+    });
+
+    // Listen for recordings discarded as accidental taps
+    const unlistenDiscarded = listen<RecordingDiscardedEvent>('recording-discarded', (event) => {
+      console.log('✂️ Frontend received recording-discarded:', event.payload);
+      setRecordingState((prev) => ({ ...prev, locked: false }));
+    });
+
+    // Listen for maximum length / low disk warnings while recording
+    const unlistenLimitWarning = listen<RecordingLimitEvent>('recording-limit-warning', (event) => {
+      console.warn('⚠️ Recording limit warning:', event.payload.message);
+      setRecordingState((prev) => ({ ...prev, limitWarning: event.payload.message }));
+    });
+
+    const unlistenLimitReached = listen<RecordingLimitEvent>('recording-limit-reached', (event) => {
+      console.warn('🛑 Recording limit reached:', event.payload.message);
+      setRecordingState((prev) => ({ ...prev, locked: false, limitWarning: undefined }));
+    });
+
     // Listen for clip saved events
     const unlistenClipSaved = listen<ClipSavedEvent>('clip-saved', (event) => {
       console.log('💾 Frontend received clip-saved:', event.payload);
@@ -51,6 +105,11 @@ export function useRecording() {
     return () => {
       console.log('🧹 Cleaning up event listeners...');
       unlistenStatus.then((fn) => fn());
+      unlistenLocked.then((fn) => fn());
+      unlistenCancelled.then((fn) => fn());
+      unlistenDiscarded.then((fn) => fn());
+      unlistenLimitWarning.then((fn) => fn());
+      unlistenLimitReached.then((fn) => fn());
       unlistenClipSaved.then((fn) => fn());
       unlistenError.then((fn) => fn());
     };
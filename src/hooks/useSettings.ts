@@ -1,11 +1,13 @@
-import { useState } from 'react';
-import type { AppSettings } from '../types';
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { AppSettings, PersistedSettings } from '../types';
 import { useActivity } from './useActivity';
 
 const DEFAULT_OUTPUT_FOLDER = '~/Movies/PushToHold'; // macOS default
 
 /**
  * Hook to manage app settings
+ * Starts from the settings the backend persisted across restarts
  */
 export function useSettings() {
   const { updateActivity } = useActivity();
@@ -18,6 +20,22 @@ export function useSettings() {
     micEnabled: true,
   });
 
+  useEffect(() => {
+    invoke<PersistedSettings>('get_settings')
+      .then((saved) => {
+        setSettings((prev) => ({
+          ...prev,
+          outputFolder: saved.outputFolder ?? prev.outputFolder,
+          micEnabled: saved.micEnabled,
+          selectedAudioDevice: saved.audioDeviceId ?? prev.selectedAudioDevice,
+          captureRegion: saved.captureRegion ?? undefined,
+          captureMode: saved.captureRegion ? 'region' : 'full',
+          currentProject: saved.currentProject ?? undefined,
+        }));
+      })
+      .catch((err) => console.error('Failed to load settings:', err));
+  }, []);
+
   const updateSettings = (partial: Partial<AppSettings>) => {
     updateActivity();
     setSettings((prev) => ({ ...prev, ...partial }));
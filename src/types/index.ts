@@ -3,7 +3,7 @@
 export type CaptureMode = 'full' | 'region';
 export type AspectRatio = '16:9' | '9:16' | 'none';
 export type RecordingStatus = 'idle' | 'recording' | 'saving' | 'error';
-export type PreInitStatus = 'NotInitialized' | 'Initializing' | 'Ready' | 'ShuttingDown';
+export type PreInitStatus = 'NotInitialized' | 'Initializing' | 'Ready' | 'LowDisk';
 
 export interface CaptureRegion {
   x: number;
@@ -22,6 +22,8 @@ export interface RecordingState {
   clipCount: number;
   currentClipPath?: string;
   error?: string;
+  locked?: boolean; // double-tap lock: keeps recording until the next tap
+  limitWarning?: string; // recording will stop soon (maximum length or low disk)
 }
 
 export interface AppSettings {
@@ -35,6 +37,44 @@ export interface AppSettings {
   currentProject?: string;
 }
 
+// Settings persisted by the backend (settings.json)
+export interface PersistedSettings {
+  version: number;
+  outputFolder?: string;
+  micEnabled: boolean;
+  audioDeviceId?: string | null;
+  captureRegion?: CaptureRegion | null;
+  idleTimeoutMins: number;
+  currentProject?: string | null;
+  hotkeys?: Partial<Record<HotkeyAction, string>>; // only hotkeys changed from their defaults
+  triggerMode: TriggerMode;
+  minClipDurationMs: number; // shorter recordings are discarded (0 keeps all)
+  maxClipDurationMins: number; // recordings stop after this long (0 = no limit)
+  minFreeDiskMb: number; // recordings stop below this much free space (0 = no check)
+  filenameTemplate: string; // e.g. "recording-{seq}"; tokens {project} {date} {time} {seq} {label} {uuid}
+  autoReconcile: boolean; // the backend reconciles projects when their files change
+  maxRemovalPercent: number; // reconciles removing more of a timeline than this need confirmation (100 = never)
+  variableFrameRate: boolean; // skip unchanged frames while recording (off = constant frame rate)
+  keyframeIntervalSecs: number; // most seconds between keyframes (1-60)
+}
+
+export interface RecordingLimitSettings {
+  maxClipDurationMins: number;
+  minFreeDiskMb: number;
+}
+
+// Global hotkeys
+export type TriggerMode = 'hold' | 'toggle' | 'double-tap-lock';
+
+export type HotkeyAction = 'record' | 'marker' | 'cancel' | 'screenshot';
+
+export interface HotkeyBinding {
+  action: HotkeyAction;
+  accelerator: string; // e.g. "CmdOrCtrl+Shift+R"
+  defaultAccelerator: string;
+  isDefault: boolean;
+}
+
 // Audio device types
 export interface AudioDevice {
   id: string;
@@ -49,12 +89,74 @@ export interface Project {
   createdAt: string;
   videoCount: number;
   lastModified: string;
+  error?: string; // set when the project's timeline.json can't be loaded
+}
+
+// Hashing progress while a project is reconciled (only new or changed files are hashed)
+export interface ChecksumProgressEvent {
+  project: string;
+  filesDone: number;
+  filesTotal: number;
+  bytesDone: number;
+  bytesTotal: number;
+  currentFile: string | null; // null once hashing is done
+}
+
+// How reconcile recognized a renamed file, strongest first
+export type RenameMatch = 'clipId' | 'fileId' | 'checksum' | 'content';
+
+// What reconciling a project would change (see plan_project_reconcile)
+export interface ReconcilePlan {
+  removed: { id: string; filename: string }[];
+  renamed: { id: string; from: string; to: string; matchedBy: RenameMatch; checksum: string }[];
+  added: { filename: string; checksum: string }[];
+  checksumChanged: { id: string; filename: string; checksum: string }[];
+  totalEntries: number; // entries in the timeline when the plan was made
+}
+
+// A reconcile held back because it would drop too much of the timeline
+export interface ReconcileConfirmationEvent {
+  project: string;
+  plan: ReconcilePlan;
+}
+
+// One coalesced change in the output folder
+export interface FileChange {
+  project: string;
+  file: string | null; // null when the project folder itself changed
+  kind: 'created' | 'removed' | 'renamed' | 'modified';
+  from?: string; // previous name of a renamed file or project
+}
+
+// A debounced burst of filesystem changes
+export interface FilesystemChangedEvent {
+  changes: FileChange[];
+  reconciling: boolean; // the backend reconciles the affected projects (auto-reconcile)
+}
+
+// A project the backend reconciled after filesystem changes
+export interface ProjectReconciledEvent {
+  project: string;
+  changes: number;
+}
+
+// A recording left as a .partial file by a crash (see get_recoverable_recordings)
+export interface RecoverableClip {
+  project: string;
+  filename: string;
+  sizeBytes: number;
+  lastModified: string;
 }
 
+export type TimelineEntryKind = 'video' | 'image';
+
 export interface TimelineEntry {
   id: string;
+  kind?: TimelineEntryKind; // missing on entries written before screenshots existed (= video)
   filename: string;
-  thumbnail?: string; // thumbnail filename (e.g., "thumbnail-1.png")
+  thumbnail?: string; // poster frame filename (e.g., "recording-1.thumb.png")
+  filmstrip?: string; // filmstrip sprite filename (e.g., "recording-1.filmstrip.png")
+  thumbnailFailed?: { size: number; modifiedNs: string; inode: string }; // the file couldn't be decoded (retried once it changes); large values are strings
   label?: string; // user-editable label
   recordedAt: string;
   durationMs: number;
@@ -65,9 +167,13 @@ export interface TimelineEntry {
   micEnabled: boolean;
   notes?: string;
   checksum?: string; // SHA256 hash for file integrity and rename detection
+  fileId?: string; // Inode of the file (as a string - it can exceed 2^53) - stays the same when it's renamed or moved
+  contentSignature?: string; // Recognizes the clip after edits
+  markers?: number[]; // marker offsets in ms from the start of the clip
 }
 
 export interface ProjectTimeline {
+  schemaVersion?: number;
   projectName: string;
   createdAt: string;
   lastModified: string;
@@ -80,12 +186,61 @@ export interface ProjectTimeline {
   };
 }
 
+// Returned by the stop_recording command
+export interface SavedClip {
+  path: string;
+  entry: TimelineEntry;
+}
+
 // Events from Rust backend
+export interface RecordingLimitEvent {
+  kind: 'max-duration' | 'low-disk';
+  message: string;
+}
+
+export interface RecordingDiscardedEvent {
+  reason: 'too-short';
+  durationMs: number;
+  minDurationMs: number;
+}
+
 export interface ClipSavedEvent {
   path: string;
   durationMs: number;
 }
 
+export interface ScreenshotSavedEvent {
+  path: string;
+  width: number;
+  height: number;
+}
+
+export type AnimationFormat = 'gif' | 'webp';
+
+export interface AnimationOptions {
+  format?: AnimationFormat;
+  start_secs?: number;
+  end_secs?: number | null;
+  fps?: number;
+  max_width?: number;
+  max_colors?: number;
+  max_bytes?: number | null;
+  loop_forever?: boolean;
+}
+
+export interface AnimationExport {
+  path: string;
+  width: number;
+  height: number;
+  frame_count: number;
+  fps: number;
+  bytes: number;
+}
+
+export interface MarkerEvent {
+  offsetMs: number;
+}
+
 export interface ErrorEvent {
   code: string;
   message: string;